@@ -6,6 +6,7 @@ import (
 
 const (
 	RecipeImagesOriginalPath = "recipe_images/original"
+	BookImagesOriginalPath   = "book_images/original"
 )
 
 // Return the minimum of two integers
@@ -1,6 +1,7 @@
 package core
 
 import (
+	"strings"
 	"time"
 
 	"github.com/golang-jwt/jwt/v4"
@@ -42,7 +43,61 @@ type CreateLogin struct {
 	Password string `json:"password" validate:"required"`
 }
 
+const (
+	RecipesSortNewest     = "newest"
+	RecipesSortOldest     = "oldest"
+	RecipesSortTitle      = "title"
+	RecipesSortTitleDesc  = "titleDesc"
+	RecipesSortRating     = "rating"
+	RecipesSortCooked     = "cooked"
+	RecipesSortMostCooked = "mostCooked"
+	RecipesSortQuickest   = "quickest"
+)
+
 type RecipesFilterParams struct {
-	Page    uint `query:"page" validate:"required,gt=0"`
-	PerPage uint `query:"perPage" validate:"required,gt=0,lte=120"`
+	Page         uint   `query:"page" validate:"required,gt=0"`
+	PerPage      uint   `query:"perPage" validate:"required,gt=0,lte=120"`
+	Search       string `query:"search"`
+	Tags         string `query:"tags"`
+	Sort         string `query:"sort" validate:"omitempty,oneof=newest oldest title titleDesc rating cooked mostCooked quickest"`
+	Favourites   bool   `query:"favourites"`
+	MaxTotalTime uint   `query:"maxTotalTime"` // in minutes, zero for any time
+	Cuisine      string `query:"cuisine"`
+	Course       string `query:"course"`
+	Difficulty   string `query:"difficulty"`
+}
+
+// Get the requested tags (given comma separated), ignoring empty values
+func (f *RecipesFilterParams) TagsList() []string {
+	tags := make([]string, 0)
+	for _, tag := range strings.Split(f.Tags, ",") {
+		if tag = strings.TrimSpace(tag); tag != "" {
+			tags = append(tags, tag)
+		}
+	}
+	return tags
+}
+
+// A single page of results, with the details needed to paginate accurately
+type Page[T any] struct {
+	Items      []T   `json:"items"`
+	TotalCount int64 `json:"totalCount"`
+	NextCursor *uint `json:"nextCursor"`
+}
+
+// Create a page, setting the next cursor when more results exist after it
+func NewPage[T any](items []T, totalCount int64, page uint, perPage uint) Page[T] {
+	if items == nil {
+		items = make([]T, 0)
+	}
+	var nextCursor *uint
+	if int64(page*perPage) < totalCount {
+		nextPage := page + 1
+		nextCursor = &nextPage
+	}
+	return Page[T]{
+		Items:      items,
+		TotalCount: totalCount,
+		NextCursor: nextCursor,
+	}
 }
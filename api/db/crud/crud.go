@@ -1,8 +1,16 @@
 package crud
 
 import (
+	"bytes"
+	"encoding/json"
+	"strings"
+	"time"
+
+	"github.com/enchant97/my-cooking-codex/api/core"
 	"github.com/enchant97/my-cooking-codex/api/db"
 	"github.com/google/uuid"
+	"gorm.io/datatypes"
+	"gorm.io/gorm"
 )
 
 func CreateUser(user db.CreateUser) (db.User, error) {
@@ -38,18 +46,193 @@ func GetUserCount() (int64, error) {
 }
 
 func CreateRecipe(recipe db.Recipe) (db.Recipe, error) {
-	if err := db.DB.Create(&recipe).Error; err != nil {
+	err := db.DB.Transaction(func(tx *gorm.DB) error {
+		if err := tx.Create(&recipe).Error; err != nil {
+			return err
+		}
+		return createRecipeRevision(tx, recipe.ID, recipe.OwnerID)
+	})
+	if err != nil {
 		return db.Recipe{}, err
 	}
 	return recipe, nil
 }
 
-func GetRecipesByUserID(userID uuid.UUID, offset uint, limit uint) ([]db.Recipe, error) {
-	var recipes []db.Recipe
-	if err := db.DB.Offset(int(offset)).Limit(int(limit)).Order("created_at DESC").Find(&recipes, "owner_id = ?", userID).Error; err != nil {
+// Save the current content of a recipe as a revision,
+// skipped when the content has not changed since the latest revision
+func createRecipeRevision(tx *gorm.DB, recipeID uuid.UUID, authorID uuid.UUID) error {
+	var recipe db.Recipe
+	if err := tx.First(&recipe, "id = ?", recipeID).Error; err != nil {
+		return err
+	}
+	snapshot := recipe.Snapshot()
+	var latestRevisions []db.RecipeRevision
+	if err := tx.Where("recipe_id = ?", recipeID).Order("created_at DESC").Limit(1).Find(&latestRevisions).Error; err != nil {
+		return err
+	}
+	if len(latestRevisions) != 0 {
+		latest, err := json.Marshal(latestRevisions[0].Snapshot.Data)
+		if err != nil {
+			return err
+		}
+		current, err := json.Marshal(snapshot)
+		if err != nil {
+			return err
+		}
+		if bytes.Equal(latest, current) {
+			return nil
+		}
+	}
+	revision := db.RecipeRevision{
+		RecipeID: recipeID,
+		AuthorID: authorID,
+		Snapshot: datatypes.JSONType[db.RecipeSnapshot]{Data: snapshot},
+	}
+	if err := tx.Create(&revision).Error; err != nil {
+		return err
+	}
+	return nil
+}
+
+// Get every revision of a recipe, most recent first
+func GetRecipeRevisions(recipeID uuid.UUID) ([]db.RecipeRevision, error) {
+	var revisions []db.RecipeRevision
+	if err := db.DB.Preload("Author").Where("recipe_id = ?", recipeID).Order("created_at DESC").Find(&revisions).Error; err != nil {
 		return nil, err
 	}
-	return recipes, nil
+	return revisions, nil
+}
+
+func GetRecipeRevisionById(id uuid.UUID) (db.RecipeRevision, error) {
+	var revision db.RecipeRevision
+	if err := db.DB.First(&revision, "id = ?", id).Error; err != nil {
+		return db.RecipeRevision{}, err
+	}
+	return revision, nil
+}
+
+// Replace the content of a recipe with that of a revision, saving it as a new revision
+func RestoreRecipeRevision(recipeID uuid.UUID, revision db.RecipeRevision, authorID uuid.UUID) (db.Recipe, error) {
+	var recipe db.Recipe
+	snapshot := revision.Snapshot.Data
+	err := db.DB.Transaction(func(tx *gorm.DB) error {
+		if err := createRecipeRevision(tx, recipeID, authorID); err != nil {
+			return err
+		}
+		// a map is used, so removed values are also cleared
+		if err := tx.Model(&db.Recipe{}).Where("id = ?", recipeID).Updates(map[string]any{
+			"title":             snapshot.Title,
+			"info_yields":       snapshot.Info.Yields,
+			"info_prep_time":    snapshot.Info.PrepTime,
+			"info_cook_time":    snapshot.Info.CookTime,
+			"info_rest_time":    snapshot.Info.RestTime,
+			"info_total_time":   snapshot.Info.TotalTime,
+			"info_cuisine":      snapshot.Info.Cuisine,
+			"info_course":       snapshot.Info.Course,
+			"info_difficulty":   snapshot.Info.Difficulty,
+			"info_pan":          snapshot.Info.Pan,
+			"short_description": snapshot.ShortDescription,
+			"long_description":  snapshot.LongDescription,
+			"tags":              datatypes.JSONType[[]string]{Data: snapshot.Tags},
+			"ingredients":       datatypes.JSONType[[]db.RecipeIngredient]{Data: snapshot.Ingredients},
+			"steps":             datatypes.JSONType[[]db.RecipeStep]{Data: snapshot.Steps},
+			"source_name":       snapshot.SourceName,
+			"source_url":        snapshot.SourceURL,
+			"equipment":         datatypes.JSONType[[]string]{Data: snapshot.Equipment},
+		}).Error; err != nil {
+			return err
+		}
+		if err := createRecipeRevision(tx, recipeID, authorID); err != nil {
+			return err
+		}
+		return tx.First(&recipe, "id = ?", recipeID).Error
+	})
+	if err != nil {
+		return db.Recipe{}, err
+	}
+	return recipe, nil
+}
+
+// Get a column as text, so JSON columns can be searched with LIKE
+func jsonColumnAsText(column string) string {
+	if db.DB.Dialector.Name() == "postgres" {
+		return column + "::text"
+	}
+	return column
+}
+
+// Total time of a recipe in minutes, which when not given is the sum of its other times
+const totalTimeColumn = "COALESCE(info_total_time, NULLIF(COALESCE(info_prep_time, 0) + COALESCE(info_cook_time, 0) + COALESCE(info_rest_time, 0), 0))"
+
+// Apply the optional search & tag filters to a recipes query
+func filterRecipes(query *gorm.DB, filters core.RecipesFilterParams) *gorm.DB {
+	if search := strings.TrimSpace(filters.Search); search != "" {
+		pattern := "%" + strings.ToLower(search) + "%"
+		query = query.Where("LOWER(title) LIKE ? OR LOWER(short_description) LIKE ?", pattern, pattern)
+	}
+	for _, tag := range filters.TagsList() {
+		// tags are stored as a JSON array, so match the quoted value
+		quotedTag, _ := json.Marshal(tag)
+		query = query.Where(jsonColumnAsText("tags")+" LIKE ?", "%"+string(quotedTag)+"%")
+	}
+	if filters.Favourites {
+		query = query.Where("favourite = ?", true)
+	}
+	// classifications match ignoring case, as they are typed freely
+	classifications := map[string]string{
+		"info_cuisine":    filters.Cuisine,
+		"info_course":     filters.Course,
+		"info_difficulty": filters.Difficulty,
+	}
+	for column, value := range classifications {
+		if value = strings.TrimSpace(value); value != "" {
+			query = query.Where("LOWER("+column+") = ?", strings.ToLower(value))
+		}
+	}
+	if filters.MaxTotalTime != 0 {
+		query = query.Where(totalTimeColumn+" <= ?", filters.MaxTotalTime)
+	}
+	return query
+}
+
+// Apply the requested sort order to a recipes query
+func sortRecipes(query *gorm.DB, filters core.RecipesFilterParams) *gorm.DB {
+	switch filters.Sort {
+	case core.RecipesSortOldest:
+		query = query.Order("created_at ASC")
+	case core.RecipesSortTitle:
+		query = query.Order("title ASC")
+	case core.RecipesSortTitleDesc:
+		query = query.Order("title DESC")
+	case core.RecipesSortRating:
+		// unrated recipes go last
+		query = query.Order("rating IS NULL, rating DESC, title ASC")
+	case core.RecipesSortCooked:
+		// never cooked recipes go last
+		query = query.Order("last_cooked_at IS NULL, last_cooked_at DESC, title ASC")
+	case core.RecipesSortMostCooked:
+		query = query.Order("times_cooked DESC, title ASC")
+	case core.RecipesSortQuickest:
+		// recipes without times go last
+		query = query.Order(totalTimeColumn + " IS NULL, " + totalTimeColumn + " ASC, title ASC")
+	default:
+		query = query.Order("created_at DESC")
+	}
+	return query
+}
+
+// Get a page of the user's recipes, along with the total number matching the filters
+func GetRecipesByUserID(userID uuid.UUID, offset uint, limit uint, filters core.RecipesFilterParams) ([]db.Recipe, int64, error) {
+	var recipes []db.Recipe
+	var count int64
+	query := filterRecipes(db.DB.Model(&db.Recipe{}).Where("owner_id = ?", userID), filters)
+	if err := query.Session(&gorm.Session{}).Count(&count).Error; err != nil {
+		return nil, 0, err
+	}
+	if err := sortRecipes(query, filters).Offset(int(offset)).Limit(int(limit)).Find(&recipes).Error; err != nil {
+		return nil, 0, err
+	}
+	return recipes, count, nil
 }
 
 func GetRecipesByUserIDCount(userID uuid.UUID) (int64, error) {
@@ -69,22 +252,84 @@ func GetRecipeById(id uuid.UUID) (db.Recipe, error) {
 }
 
 func DoesUserOwnRecipe(userID uuid.UUID, recipeId uuid.UUID) (bool, error) {
-	var recipe db.Recipe
-	if err := db.DB.First(&recipe, "id = ?", recipeId, "owner_id = ?", userID).Error; err != nil {
+	var count int64
+	if err := db.DB.Model(&db.Recipe{}).Where("id = ? AND owner_id = ?", recipeId, userID).Count(&count).Error; err != nil {
 		return false, err
 	}
-	return true, nil
+	return count != 0, nil
 }
 
-func UpdateRecipe(recipeID uuid.UUID, recipe db.UpdateRecipe) (db.Recipe, error) {
+func UpdateRecipe(recipeID uuid.UUID, authorID uuid.UUID, recipe db.UpdateRecipe) (db.Recipe, error) {
 	var updatedRecipe db.Recipe
 
-	if err := db.DB.Model(&updatedRecipe).Where("id = ?", recipeID).Updates(recipe.IntoRecipe()).Error; err != nil {
+	err := db.DB.Transaction(func(tx *gorm.DB) error {
+		// recipes from before revisions existed need their original content saving first
+		if err := createRecipeRevision(tx, recipeID, authorID); err != nil {
+			return err
+		}
+		if err := tx.Model(&updatedRecipe).Where("id = ?", recipeID).Updates(recipe.IntoRecipe()).Error; err != nil {
+			return err
+		}
+		if ratingUpdates := recipe.RatingUpdates(); len(ratingUpdates) != 0 {
+			if err := tx.Model(&updatedRecipe).Where("id = ?", recipeID).Updates(ratingUpdates).Error; err != nil {
+				return err
+			}
+		}
+		if clearedUpdates := recipe.ClearedUpdates(); len(clearedUpdates) != 0 {
+			if err := tx.Model(&updatedRecipe).Where("id = ?", recipeID).Updates(clearedUpdates).Error; err != nil {
+				return err
+			}
+		}
+		return createRecipeRevision(tx, recipeID, authorID)
+	})
+	if err != nil {
 		return db.Recipe{}, err
 	}
 	return updatedRecipe, nil
 }
 
+// Whether using the given sub-recipes in a recipe would make it include itself,
+// either directly or through the sub-recipes of those sub-recipes
+func DoSubRecipesCreateCycle(recipeID uuid.UUID, subRecipeIDs []uuid.UUID) (bool, error) {
+	visited := make(map[uuid.UUID]bool)
+	toVisit := subRecipeIDs
+	for len(toVisit) != 0 {
+		var unvisited []uuid.UUID
+		for _, id := range toVisit {
+			if id == recipeID {
+				return true, nil
+			}
+			if !visited[id] {
+				visited[id] = true
+				unvisited = append(unvisited, id)
+			}
+		}
+		if len(unvisited) == 0 {
+			break
+		}
+		var recipes []db.Recipe
+		if err := db.DB.Select("id", "ingredients").Where("id IN ?", unvisited).Find(&recipes).Error; err != nil {
+			return false, err
+		}
+		toVisit = nil
+		for _, recipe := range recipes {
+			if recipe.Ingredients != nil {
+				toVisit = append(toVisit, db.SubRecipeIDs(recipe.Ingredients.Data)...)
+			}
+		}
+	}
+	return false, nil
+}
+
+// Get the recipes that were made as a variation of a recipe
+func GetRecipeVariations(recipeID uuid.UUID) ([]db.Recipe, error) {
+	var recipes []db.Recipe
+	if err := db.DB.Where("origin_id = ?", recipeID).Order("title ASC").Find(&recipes).Error; err != nil {
+		return nil, err
+	}
+	return recipes, nil
+}
+
 func UpdateRecipeImage(recipeID uuid.UUID, imageID *uuid.UUID) error {
 	var updatedRecipe db.Recipe
 	if err := db.DB.Model(&updatedRecipe).Where("id = ?", recipeID).Updates(map[string]any{"image_id": imageID}).Error; err != nil {
@@ -94,7 +339,248 @@ func UpdateRecipeImage(recipeID uuid.UUID, imageID *uuid.UUID) error {
 }
 
 func DeleteRecipe(recipeID uuid.UUID) error {
-	if err := db.DB.Delete(&db.Recipe{}, recipeID).Error; err != nil {
+	return db.DB.Transaction(func(tx *gorm.DB) error {
+		if err := tx.Where("recipe_id = ?", recipeID).Delete(&db.CookLog{}).Error; err != nil {
+			return err
+		}
+		if err := tx.Where("recipe_id = ?", recipeID).Delete(&db.RecipeRevision{}).Error; err != nil {
+			return err
+		}
+		// variations remain, but are no longer linked
+		if err := tx.Model(&db.Recipe{}).Where("origin_id = ?", recipeID).Updates(map[string]any{"origin_id": nil}).Error; err != nil {
+			return err
+		}
+		if err := tx.Delete(&db.Recipe{}, recipeID).Error; err != nil {
+			return err
+		}
+		return nil
+	})
+}
+
+// Recalculate the cooking summary stored on a recipe, from its history
+func updateRecipeCookedSummary(tx *gorm.DB, recipeID uuid.UUID) error {
+	var timesCooked int64
+	if err := tx.Model(&db.CookLog{}).Where("recipe_id = ?", recipeID).Count(&timesCooked).Error; err != nil {
+		return err
+	}
+	var lastCookedAt *time.Time
+	if timesCooked != 0 {
+		var lastCookLog db.CookLog
+		if err := tx.Where("recipe_id = ?", recipeID).Order("cooked_at DESC").First(&lastCookLog).Error; err != nil {
+			return err
+		}
+		lastCookedAt = &lastCookLog.CookedAt
+	}
+	if err := tx.Model(&db.Recipe{}).Where("id = ?", recipeID).Updates(map[string]any{
+		"times_cooked":   timesCooked,
+		"last_cooked_at": lastCookedAt,
+	}).Error; err != nil {
+		return err
+	}
+	return nil
+}
+
+func CreateCookLog(cookLog db.CookLog) (db.CookLog, error) {
+	err := db.DB.Transaction(func(tx *gorm.DB) error {
+		if err := tx.Create(&cookLog).Error; err != nil {
+			return err
+		}
+		return updateRecipeCookedSummary(tx, cookLog.RecipeID)
+	})
+	if err != nil {
+		return db.CookLog{}, err
+	}
+	return cookLog, nil
+}
+
+// Get the cooking history of a recipe, most recent first
+func GetCookLogsByRecipeID(recipeID uuid.UUID) ([]db.CookLog, error) {
+	var cookLogs []db.CookLog
+	if err := db.DB.Where("recipe_id = ?", recipeID).Order("cooked_at DESC, created_at DESC").Find(&cookLogs).Error; err != nil {
+		return nil, err
+	}
+	return cookLogs, nil
+}
+
+func DoesRecipeHaveCookLog(recipeID uuid.UUID, cookLogID uuid.UUID) (bool, error) {
+	var count int64
+	if err := db.DB.Model(&db.CookLog{}).Where("id = ? AND recipe_id = ?", cookLogID, recipeID).Count(&count).Error; err != nil {
+		return false, err
+	}
+	return count != 0, nil
+}
+
+func DeleteCookLog(recipeID uuid.UUID, cookLogID uuid.UUID) error {
+	return db.DB.Transaction(func(tx *gorm.DB) error {
+		if err := tx.Delete(&db.CookLog{}, cookLogID).Error; err != nil {
+			return err
+		}
+		return updateRecipeCookedSummary(tx, recipeID)
+	})
+}
+
+func CreateBook(book db.Book) (db.Book, error) {
+	if err := db.DB.Create(&book).Error; err != nil {
+		return db.Book{}, err
+	}
+	return book, nil
+}
+
+func GetBooksByUserID(userID uuid.UUID) ([]db.Book, error) {
+	var books []db.Book
+	if err := db.DB.Where("owner_id = ?", userID).Order("title ASC").Find(&books).Error; err != nil {
+		return nil, err
+	}
+	return books, nil
+}
+
+func GetBooksByUserIDCount(userID uuid.UUID) (int64, error) {
+	var count int64
+	if err := db.DB.Model(&db.Book{}).Where("owner_id = ?", userID).Count(&count).Error; err != nil {
+		return 0, err
+	}
+	return count, nil
+}
+
+func GetBookById(id uuid.UUID) (db.Book, error) {
+	var book db.Book
+	if err := db.DB.First(&book, "id = ?", id).Error; err != nil {
+		return db.Book{}, err
+	}
+	return book, nil
+}
+
+func DoesUserOwnBook(userID uuid.UUID, bookID uuid.UUID) (bool, error) {
+	var count int64
+	if err := db.DB.Model(&db.Book{}).Where("id = ? AND owner_id = ?", bookID, userID).Count(&count).Error; err != nil {
+		return false, err
+	}
+	return count != 0, nil
+}
+
+// Get the given recipes owned by the user, in the same order as the given ids.
+// Any missing recipes (e.g. ones that have been deleted) are skipped
+func GetRecipesByIDs(userID uuid.UUID, recipeIDs []uuid.UUID) ([]db.Recipe, error) {
+	var recipes []db.Recipe
+	if len(recipeIDs) == 0 {
+		return []db.Recipe{}, nil
+	}
+	if err := db.DB.Where("owner_id = ? AND id IN ?", userID, recipeIDs).Find(&recipes).Error; err != nil {
+		return nil, err
+	}
+	recipesByID := make(map[uuid.UUID]db.Recipe, len(recipes))
+	for _, recipe := range recipes {
+		recipesByID[recipe.ID] = recipe
+	}
+	orderedRecipes := make([]db.Recipe, 0, len(recipes))
+	for _, recipeID := range recipeIDs {
+		if recipe, exists := recipesByID[recipeID]; exists {
+			orderedRecipes = append(orderedRecipes, recipe)
+		}
+	}
+	return orderedRecipes, nil
+}
+
+func UpdateBook(bookID uuid.UUID, book db.UpdateBook) error {
+	values := map[string]any{}
+	if book.Title != "" {
+		values["title"] = book.Title
+	}
+	if book.Description != nil {
+		values["description"] = book.Description
+	}
+	if book.RecipeIDs != nil {
+		values["recipe_ids"] = datatypes.JSONType[[]uuid.UUID]{Data: *book.RecipeIDs}
+	}
+	if len(values) == 0 {
+		return nil
+	}
+	if err := db.DB.Model(&db.Book{}).Where("id = ?", bookID).Updates(values).Error; err != nil {
+		return err
+	}
+	return nil
+}
+
+// Add a recipe to the end of a book, if it is not already in it
+func AddRecipeToBook(bookID uuid.UUID, recipeID uuid.UUID) error {
+	return db.DB.Transaction(func(tx *gorm.DB) error {
+		var book db.Book
+		if err := tx.First(&book, "id = ?", bookID).Error; err != nil {
+			return err
+		}
+		recipeIDs := book.RecipeIDs.Data
+		for _, existingID := range recipeIDs {
+			if existingID == recipeID {
+				return nil
+			}
+		}
+		recipeIDs = append(recipeIDs, recipeID)
+		return tx.Model(&book).Updates(map[string]any{"recipe_ids": datatypes.JSONType[[]uuid.UUID]{Data: recipeIDs}}).Error
+	})
+}
+
+func RemoveRecipeFromBook(bookID uuid.UUID, recipeID uuid.UUID) error {
+	return db.DB.Transaction(func(tx *gorm.DB) error {
+		var book db.Book
+		if err := tx.First(&book, "id = ?", bookID).Error; err != nil {
+			return err
+		}
+		recipeIDs := make([]uuid.UUID, 0, len(book.RecipeIDs.Data))
+		for _, existingID := range book.RecipeIDs.Data {
+			if existingID != recipeID {
+				recipeIDs = append(recipeIDs, existingID)
+			}
+		}
+		return tx.Model(&book).Updates(map[string]any{"recipe_ids": datatypes.JSONType[[]uuid.UUID]{Data: recipeIDs}}).Error
+	})
+}
+
+func UpdateBookImage(bookID uuid.UUID, imageID *uuid.UUID) error {
+	if err := db.DB.Model(&db.Book{}).Where("id = ?", bookID).Updates(map[string]any{"image_id": imageID}).Error; err != nil {
+		return err
+	}
+	return nil
+}
+
+func DeleteBook(bookID uuid.UUID) error {
+	if err := db.DB.Delete(&db.Book{}, bookID).Error; err != nil {
+		return err
+	}
+	return nil
+}
+
+func CreatePantryItem(item db.PantryItem) (db.PantryItem, error) {
+	if err := db.DB.Create(&item).Error; err != nil {
+		return db.PantryItem{}, err
+	}
+	return item, nil
+}
+
+func GetPantryItemsByUserID(userID uuid.UUID) ([]db.PantryItem, error) {
+	var items []db.PantryItem
+	if err := db.DB.Where("owner_id = ?", userID).Order("name ASC").Find(&items).Error; err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+func DoesUserOwnPantryItem(userID uuid.UUID, itemID uuid.UUID) (bool, error) {
+	var count int64
+	if err := db.DB.Model(&db.PantryItem{}).Where("id = ? AND owner_id = ?", itemID, userID).Count(&count).Error; err != nil {
+		return false, err
+	}
+	return count != 0, nil
+}
+
+func UpdatePantryItem(itemID uuid.UUID, item db.UpdatePantryItem) error {
+	if err := db.DB.Model(&db.PantryItem{}).Where("id = ?", itemID).Updates(item.Updates()).Error; err != nil {
+		return err
+	}
+	return nil
+}
+
+func DeletePantryItem(itemID uuid.UUID) error {
+	if err := db.DB.Delete(&db.PantryItem{}, itemID).Error; err != nil {
 		return err
 	}
 	return nil
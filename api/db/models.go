@@ -45,8 +45,17 @@ func (u *User) IsPasswordMatch(plainPassword string) bool {
 	return false
 }
 
+// Times are in minutes
 type RecipeInfo struct {
-	Yields *datatypes.JSONType[RecipeInfoYields] `gorm:"type:json" json:"yields,omitempty"`
+	Yields     *datatypes.JSONType[RecipeInfoYields] `gorm:"type:json" json:"yields,omitempty"`
+	PrepTime   *uint                                 `json:"prepTime,omitempty"`
+	CookTime   *uint                                 `json:"cookTime,omitempty"`
+	RestTime   *uint                                 `json:"restTime,omitempty"`
+	TotalTime  *uint                                 `json:"totalTime,omitempty"` // when not given, the sum of the other times
+	Cuisine    *string                               `gorm:"type:varchar(50)" json:"cuisine,omitempty" validate:"omitempty,max=50"`
+	Course     *string                               `gorm:"type:varchar(50)" json:"course,omitempty" validate:"omitempty,max=50"`
+	Difficulty *string                               `gorm:"type:varchar(10)" json:"difficulty,omitempty" validate:"omitempty,len=0|oneof=easy medium hard"`
+	Pan        *datatypes.JSONType[RecipePan]        `gorm:"type:json" json:"pan,omitempty"`
 }
 
 type Recipe struct {
@@ -57,7 +66,102 @@ type Recipe struct {
     Info             RecipeInfo                              `gorm:"embedded;embeddedPrefix:info_" json:"info"`
 	ShortDescription *string                                 `gorm:"type:varchar(256)" json:"shortDescription,omitempty"`
 	LongDescription  *string                                 `json:"longDescription,omitempty"`
+	Tags             *datatypes.JSONType[[]string]           `gorm:"type:json" json:"tags,omitempty"`
 	Ingredients      *datatypes.JSONType[[]RecipeIngredient] `gorm:"type:json" json:"ingredients,omitempty"`
 	Steps            *datatypes.JSONType[[]RecipeStep]       `gorm:"type:json" json:"steps,omitempty"`
 	ImageID          *uuid.UUID                              `gorm:"type:uuid" json:"imageId"`
+	Favourite        bool                                    `gorm:"not null;default:false" json:"favourite"`
+	Rating           *uint                                   `json:"rating,omitempty"`
+	Review           *string                                 `gorm:"type:varchar(280)" json:"review,omitempty"`
+	LastCookedAt     *time.Time                              `json:"lastCookedAt,omitempty"`
+	TimesCooked      uint                                    `gorm:"not null;default:0" json:"timesCooked"`
+	OriginID         *uuid.UUID                              `gorm:"type:uuid;index" json:"originId,omitempty"`
+	SourceName       *string                                 `gorm:"type:varchar(100)" json:"sourceName,omitempty"`
+	SourceURL        *string                                 `json:"sourceUrl,omitempty"`
+	Equipment        *datatypes.JSONType[[]string]           `gorm:"type:json" json:"equipment,omitempty"`
+}
+
+// The editable content of a recipe, at a point in time
+type RecipeSnapshot struct {
+	Title            string             `json:"title"`
+	Info             RecipeInfo         `json:"info"`
+	ShortDescription *string            `json:"shortDescription,omitempty"`
+	LongDescription  *string            `json:"longDescription,omitempty"`
+	Tags             []string           `json:"tags"`
+	Ingredients      []RecipeIngredient `json:"ingredients"`
+	Steps            []RecipeStep       `json:"steps"`
+	SourceName       *string            `json:"sourceName,omitempty"`
+	SourceURL        *string            `json:"sourceUrl,omitempty"`
+	Equipment        []string           `json:"equipment"`
+}
+
+func (r *Recipe) Snapshot() RecipeSnapshot {
+	snapshot := RecipeSnapshot{
+		Title:            r.Title,
+		Info:             r.Info,
+		ShortDescription: r.ShortDescription,
+		LongDescription:  r.LongDescription,
+		Tags:             []string{},
+		Ingredients:      []RecipeIngredient{},
+		Steps:            []RecipeStep{},
+		SourceName:       r.SourceName,
+		SourceURL:        r.SourceURL,
+		Equipment:        []string{},
+	}
+	if r.Tags != nil {
+		snapshot.Tags = r.Tags.Data
+	}
+	if r.Ingredients != nil {
+		snapshot.Ingredients = r.Ingredients.Data
+	}
+	if r.Steps != nil {
+		snapshot.Steps = r.Steps.Data
+	}
+	if r.Equipment != nil {
+		snapshot.Equipment = r.Equipment.Data
+	}
+	return snapshot
+}
+
+// A saved version of a recipe, made whenever its content changes
+type RecipeRevision struct {
+	UUIDBase
+	TimeBase
+	RecipeID uuid.UUID                          `gorm:"not null;index;type:uuid" json:"recipeId"`
+	AuthorID uuid.UUID                          `gorm:"not null;type:uuid" json:"authorId"`
+	Author   User                               `gorm:"foreignKey:AuthorID" json:"author"`
+	Snapshot datatypes.JSONType[RecipeSnapshot] `gorm:"type:json" json:"snapshot"`
+}
+
+// A single time a recipe was cooked
+type CookLog struct {
+	UUIDBase
+	TimeBase
+	RecipeID uuid.UUID `gorm:"not null;index;type:uuid" json:"recipeId"`
+	OwnerID  uuid.UUID `gorm:"not null;type:uuid" json:"ownerId"`
+	CookedAt time.Time `gorm:"not null" json:"cookedAt"`
+	Scale    float32   `gorm:"not null;default:1" json:"scale"`
+	Rating   *uint     `json:"rating,omitempty"`
+	Notes    *string   `gorm:"type:varchar(280)" json:"notes,omitempty"`
+}
+
+type Book struct {
+	UUIDBase
+	TimeBase
+	OwnerID     uuid.UUID                       `gorm:"not null;type:uuid" json:"ownerId"`
+	Title       string                          `gorm:"not null;type:varchar(60)" json:"title"`
+	Description *string                         `gorm:"type:varchar(256)" json:"description,omitempty"`
+	RecipeIDs   datatypes.JSONType[[]uuid.UUID] `gorm:"type:json" json:"recipeIds"`
+	ImageID     *uuid.UUID                      `gorm:"type:uuid" json:"imageId"`
+}
+
+// Something a user has in their kitchen, used to find what they can cook
+type PantryItem struct {
+	UUIDBase
+	TimeBase
+	OwnerID   uuid.UUID  `gorm:"not null;index;type:uuid" json:"ownerId"`
+	Name      string     `gorm:"not null;type:varchar(100)" json:"name"`
+	Quantity  *float32   `json:"quantity,omitempty"`
+	UnitType  *string    `gorm:"type:varchar(30)" json:"unitType,omitempty"`
+	ExpiresAt *time.Time `json:"expiresAt,omitempty"`
 }
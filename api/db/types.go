@@ -1,25 +1,56 @@
 package db
 
 import (
+	"time"
+
 	"github.com/google/uuid"
 	"gorm.io/datatypes"
 )
 
 type RecipeIngredient struct {
-	Name        string  `json:"name" validate:"required"`
-	Amount      float32 `json:"amount" validate:"required"`
-	UnitType    string  `json:"unitType" validate:"required"`
-	Description *string `json:"description,omitempty"`
+	ID          string     `json:"id,omitempty"` // unique in the recipe, for steps to refer to
+	Name        string     `json:"name" validate:"required"`
+	Amount      *float32   `json:"amount,omitempty" validate:"omitempty,gt=0"`    // none when not measured, e.g. "to taste"
+	AmountMax   *float32   `json:"amountMax,omitempty" validate:"omitempty,gt=0"` // upper end of a range, e.g. the 3 of "2-3"
+	UnitType    string     `json:"unitType"`
+	Optional    bool       `json:"optional,omitempty"`
+	Description *string    `json:"description,omitempty"`
+	RecipeID    *uuid.UUID `json:"recipeId,omitempty"` // another recipe used as the ingredient
+	Section     *string    `json:"section,omitempty"`  // name of the part of the recipe it is for
+}
+
+// The unique ids of recipes used as ingredients
+func SubRecipeIDs(ingredients []RecipeIngredient) []uuid.UUID {
+	seen := make(map[uuid.UUID]bool)
+	recipeIDs := make([]uuid.UUID, 0)
+	for _, ingredient := range ingredients {
+		if ingredient.RecipeID != nil && !seen[*ingredient.RecipeID] {
+			seen[*ingredient.RecipeID] = true
+			recipeIDs = append(recipeIDs, *ingredient.RecipeID)
+		}
+	}
+	return recipeIDs
 }
 
 type RecipeStep struct {
-	Title       *string `json:"title,omitempty"`
-	Description string  `json:"description" validate:"required"`
+	Title       *string  `json:"title,omitempty"`
+	Description string   `json:"description" validate:"required"`
+	Section     *string  `json:"section,omitempty"`     // name of the part of the recipe it is for
+	Ingredients []string `json:"ingredients,omitempty"` // ids of the ingredients it uses
 }
 
 type RecipeInfoYields struct {
-	Value    uint   `json:"value" validate:"required"`
-	UnitType string `json:"unitType" validate:"required"`
+	Value    float32  `json:"value" validate:"required,gt=0"`
+	ValueMax *float32 `json:"valueMax,omitempty" validate:"omitempty,gt=0"` // upper end of a range, e.g. the 6 of "4-6"
+	UnitType string   `json:"unitType" validate:"required"`
+}
+
+// The tin or tray a recipe is made in, with dimensions in cm
+type RecipePan struct {
+	Shape  string   `json:"shape" validate:"required,oneof=round square rectangle loaf bundt"`
+	Width  float32  `json:"width"`                                      // diameter of round & bundt pans, zero to remove the pan
+	Length *float32 `json:"length,omitempty" validate:"omitempty,gt=0"` // of rectangle & loaf pans
+	Depth  *float32 `json:"depth,omitempty" validate:"omitempty,gt=0"`  // of loaf & bundt pans, which are compared by volume
 }
 
 type CreateUser struct {
@@ -42,8 +73,14 @@ type CreateRecipe struct {
 	Info             CreateRecipeInfo   `json:"info,omitempty"`
 	ShortDescription *string            `json:"shortDescription,omitempty"`
 	LongDescription  *string            `json:"longDescription,omitempty"`
+	Tags             []string           `json:"tags,omitempty"`
 	Ingredients      []RecipeIngredient `json:"ingredients,omitempty"`
 	Steps            []RecipeStep       `json:"steps,omitempty"`
+	// the recipe this is a variation of, its image will be copied
+	OriginID   *uuid.UUID `json:"originId,omitempty"`
+	SourceName *string    `json:"sourceName,omitempty" validate:"omitempty,max=100"`
+	SourceURL  *string    `json:"sourceUrl,omitempty" validate:"omitempty,len=0|url"`
+	Equipment  []string   `json:"equipment,omitempty"`
 }
 
 func (r *CreateRecipe) IntoRecipe(ownerID uuid.UUID, imageID *uuid.UUID) Recipe {
@@ -53,20 +90,54 @@ func (r *CreateRecipe) IntoRecipe(ownerID uuid.UUID, imageID *uuid.UUID) Recipe
 		Info:             RecipeInfo(r.Info),
 		ShortDescription: r.ShortDescription,
 		LongDescription:  r.LongDescription,
-		ImageID:          imageID,
+		Tags: func() *datatypes.JSONType[[]string] {
+			if r.Tags == nil {
+				return nil
+			}
+			return &datatypes.JSONType[[]string]{Data: r.Tags}
+		}(),
+		Ingredients: func() *datatypes.JSONType[[]RecipeIngredient] {
+			if r.Ingredients == nil {
+				return nil
+			}
+			return &datatypes.JSONType[[]RecipeIngredient]{Data: r.Ingredients}
+		}(),
+		Steps: func() *datatypes.JSONType[[]RecipeStep] {
+			if r.Steps == nil {
+				return nil
+			}
+			return &datatypes.JSONType[[]RecipeStep]{Data: r.Steps}
+		}(),
+		ImageID:    imageID,
+		OriginID:   r.OriginID,
+		SourceName: r.SourceName,
+		SourceURL:  r.SourceURL,
+		Equipment: func() *datatypes.JSONType[[]string] {
+			if r.Equipment == nil {
+				return nil
+			}
+			return &datatypes.JSONType[[]string]{Data: r.Equipment}
+		}(),
 	}
 }
 
 type UpdateIngredient struct {
-	Name        string  `json:"name,omitempty"`
-	Amount      float32 `json:"amount,omitempty"`
-	UnitType    string  `json:"unitType,omitempty"`
-	Description *string `json:"description,omitempty"`
+	ID          string     `json:"id,omitempty"`
+	Name        string     `json:"name,omitempty"`
+	Amount      *float32   `json:"amount,omitempty" validate:"omitempty,gt=0"`
+	AmountMax   *float32   `json:"amountMax,omitempty" validate:"omitempty,gt=0"`
+	UnitType    string     `json:"unitType,omitempty"`
+	Optional    bool       `json:"optional,omitempty"`
+	Description *string    `json:"description,omitempty"`
+	RecipeID    *uuid.UUID `json:"recipeId,omitempty"`
+	Section     *string    `json:"section,omitempty"`
 }
 
 type UpdateStep struct {
-	Title       *string `json:"title,omitempty"`
-	Description string  `json:"description,omitempty"`
+	Title       *string  `json:"title,omitempty"`
+	Description string   `json:"description,omitempty"`
+	Section     *string  `json:"section,omitempty"`
+	Ingredients []string `json:"ingredients,omitempty"`
 }
 
 type UpdateRecipeInfo RecipeInfo
@@ -76,17 +147,88 @@ type UpdateRecipe struct {
 	Info             UpdateRecipeInfo    `json:"info,omitempty"`
 	ShortDescription *string             `json:"shortDescription,omitempty"`
 	LongDescription  *string             `json:"longDescription,omitempty"`
+	Tags             *[]string           `json:"tags,omitempty"`
 	Ingredients      *[]UpdateIngredient `json:"ingredients,omitempty"`
 	Steps            *[]UpdateStep       `json:"steps,omitempty"`
+	Favourite        *bool               `json:"favourite,omitempty"`
+	Rating           *uint               `json:"rating,omitempty" validate:"omitempty,lte=5"`
+	Review           *string             `json:"review,omitempty" validate:"omitempty,max=280"`
+	SourceName       *string             `json:"sourceName,omitempty" validate:"omitempty,max=100"`
+	SourceURL        *string             `json:"sourceUrl,omitempty" validate:"omitempty,len=0|url"`
+	Equipment        *[]string           `json:"equipment,omitempty"`
 	ImageID          *uuid.UUID          `json:"-"`
 }
 
+// Updates clearing the times & pan given as zero and the details given as empty,
+// which would otherwise be stored as taking no time or being blank.
+func (r *UpdateRecipe) ClearedUpdates() map[string]any {
+	updates := make(map[string]any)
+	times := map[string]*uint{
+		"info_prep_time":  r.Info.PrepTime,
+		"info_cook_time":  r.Info.CookTime,
+		"info_rest_time":  r.Info.RestTime,
+		"info_total_time": r.Info.TotalTime,
+	}
+	for column, minutes := range times {
+		if minutes != nil && *minutes == 0 {
+			updates[column] = nil
+		}
+	}
+	if r.Info.Pan != nil && r.Info.Pan.Data.Width == 0 {
+		updates["info_pan"] = nil
+	}
+	details := map[string]*string{
+		"info_cuisine":    r.Info.Cuisine,
+		"info_course":     r.Info.Course,
+		"info_difficulty": r.Info.Difficulty,
+		"source_name":     r.SourceName,
+		"source_url":      r.SourceURL,
+	}
+	for column, value := range details {
+		if value != nil && *value == "" {
+			updates[column] = nil
+		}
+	}
+	return updates
+}
+
+// Updates for the favourite, rating & review fields,
+// which are zero to clear them (so cannot be updated from a struct).
+// A zero rating or empty review will remove it.
+func (r *UpdateRecipe) RatingUpdates() map[string]any {
+	updates := make(map[string]any)
+	if r.Favourite != nil {
+		updates["favourite"] = *r.Favourite
+	}
+	if r.Rating != nil {
+		if *r.Rating == 0 {
+			updates["rating"] = nil
+		} else {
+			updates["rating"] = *r.Rating
+		}
+	}
+	if r.Review != nil {
+		if *r.Review == "" {
+			updates["review"] = nil
+		} else {
+			updates["review"] = *r.Review
+		}
+	}
+	return updates
+}
+
 func (r *UpdateRecipe) IntoRecipe() Recipe {
 	return Recipe{
 		Title:            r.Title,
 		Info:             RecipeInfo(r.Info),
 		ShortDescription: r.ShortDescription,
 		LongDescription:  r.LongDescription,
+		Tags: func() *datatypes.JSONType[[]string] {
+			if r.Tags == nil {
+				return nil
+			}
+			return &datatypes.JSONType[[]string]{Data: *r.Tags}
+		}(),
 		Ingredients: func() *datatypes.JSONType[[]RecipeIngredient] {
 			if r.Ingredients == nil {
 				return nil
@@ -107,6 +249,91 @@ func (r *UpdateRecipe) IntoRecipe() Recipe {
 			}
 			return &datatypes.JSONType[[]RecipeStep]{Data: steps}
 		}(),
+		SourceName: r.SourceName,
+		SourceURL:  r.SourceURL,
+		Equipment: func() *datatypes.JSONType[[]string] {
+			if r.Equipment == nil {
+				return nil
+			}
+			return &datatypes.JSONType[[]string]{Data: *r.Equipment}
+		}(),
 		ImageID: r.ImageID,
 	}
 }
+
+type CreateCookLog struct {
+	CookedAt time.Time `json:"cookedAt" validate:"required"`
+	Scale    float32   `json:"scale" validate:"required,gt=0"`
+	Rating   *uint     `json:"rating,omitempty" validate:"omitempty,gte=1,lte=5"`
+	Notes    *string   `json:"notes,omitempty" validate:"omitempty,max=280"`
+}
+
+func (l *CreateCookLog) IntoCookLog(ownerID uuid.UUID, recipeID uuid.UUID) CookLog {
+	return CookLog{
+		RecipeID: recipeID,
+		OwnerID:  ownerID,
+		CookedAt: l.CookedAt,
+		Scale:    l.Scale,
+		Rating:   l.Rating,
+		Notes:    l.Notes,
+	}
+}
+
+type CreateBook struct {
+	Title       string  `json:"title" validate:"required"`
+	Description *string `json:"description,omitempty"`
+}
+
+func (b *CreateBook) IntoBook(ownerID uuid.UUID) Book {
+	return Book{
+		OwnerID:     ownerID,
+		Title:       b.Title,
+		Description: b.Description,
+		RecipeIDs:   datatypes.JSONType[[]uuid.UUID]{Data: []uuid.UUID{}},
+	}
+}
+
+type UpdateBook struct {
+	Title       string       `json:"title,omitempty"`
+	Description *string      `json:"description,omitempty"`
+	RecipeIDs   *[]uuid.UUID `json:"recipeIds,omitempty"`
+}
+
+type AddBookRecipe struct {
+	RecipeID uuid.UUID `json:"recipeId" validate:"required"`
+}
+
+type CreatePantryItem struct {
+	Name      string     `json:"name" validate:"required,max=100"`
+	Quantity  *float32   `json:"quantity,omitempty" validate:"omitempty,gt=0"`
+	UnitType  *string    `json:"unitType,omitempty" validate:"omitempty,max=30"`
+	ExpiresAt *time.Time `json:"expiresAt,omitempty"`
+}
+
+func (p *CreatePantryItem) IntoPantryItem(ownerID uuid.UUID) PantryItem {
+	return PantryItem{
+		OwnerID:   ownerID,
+		Name:      p.Name,
+		Quantity:  p.Quantity,
+		UnitType:  p.UnitType,
+		ExpiresAt: p.ExpiresAt,
+	}
+}
+
+// The whole of a pantry item is always given,
+// so a missing quantity, unit or expiry removes it
+type UpdatePantryItem struct {
+	Name      string     `json:"name" validate:"required,max=100"`
+	Quantity  *float32   `json:"quantity,omitempty" validate:"omitempty,gt=0"`
+	UnitType  *string    `json:"unitType,omitempty" validate:"omitempty,max=30"`
+	ExpiresAt *time.Time `json:"expiresAt,omitempty"`
+}
+
+func (p *UpdatePantryItem) Updates() map[string]any {
+	return map[string]any{
+		"name":       p.Name,
+		"quantity":   p.Quantity,
+		"unit_type":  p.UnitType,
+		"expires_at": p.ExpiresAt,
+	}
+}
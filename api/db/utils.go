@@ -45,5 +45,9 @@ func InitDB(conf config.DBConfig) error {
 	return DB.AutoMigrate(
 		&User{},
 		&Recipe{},
+		&Book{},
+		&CookLog{},
+		&RecipeRevision{},
+		&PantryItem{},
 	)
 }
@@ -49,6 +49,7 @@ func main() {
 		log.Fatalln(err)
 	}
 	os.MkdirAll(path.Join(appConfig.DataPath, core.RecipeImagesOriginalPath), os.ModePerm)
+	os.MkdirAll(path.Join(appConfig.DataPath, core.BookImagesOriginalPath), os.ModePerm)
 	// Connect to database
 	if err := db.InitDB(appConfig.DB); err != nil {
 		log.Fatalln(err)
@@ -0,0 +1,286 @@
+package routes
+
+import (
+	"net/http"
+	"os"
+	"path"
+
+	"github.com/enchant97/my-cooking-codex/api/config"
+	"github.com/enchant97/my-cooking-codex/api/core"
+	"github.com/enchant97/my-cooking-codex/api/db"
+	"github.com/enchant97/my-cooking-codex/api/db/crud"
+	"github.com/google/uuid"
+	"github.com/labstack/echo/v4"
+)
+
+func postCreateBook(ctx echo.Context) error {
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	var bookData db.CreateBook
+	if err := ctx.Bind(&bookData); err != nil {
+		return ctx.NoContent(http.StatusBadRequest)
+	}
+	if err := ctx.Validate(bookData); err != nil {
+		return err
+	}
+
+	book, err := crud.CreateBook(bookData.IntoBook(authenticatedUser.UserID))
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusCreated, book)
+}
+
+func getBooks(ctx echo.Context) error {
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	books, err := crud.GetBooksByUserID(authenticatedUser.UserID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, books)
+}
+
+func getBook(ctx echo.Context) error {
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	book, err := crud.GetBookById(bookID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, book)
+}
+
+func getBookRecipes(ctx echo.Context) error {
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	book, err := crud.GetBookById(bookID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	recipes, err := crud.GetRecipesByIDs(authenticatedUser.UserID, book.RecipeIDs.Data)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, recipes)
+}
+
+func patchBook(ctx echo.Context) error {
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	var bookData db.UpdateBook
+	if err := ctx.Bind(&bookData); err != nil {
+		return ctx.NoContent(http.StatusBadRequest)
+	}
+	if err := ctx.Validate(bookData); err != nil {
+		return err
+	}
+
+	if bookData.RecipeIDs != nil {
+		// a recipe can only be in a book once
+		seenIDs := make(map[uuid.UUID]bool, len(*bookData.RecipeIDs))
+		for _, recipeID := range *bookData.RecipeIDs {
+			if seenIDs[recipeID] {
+				return ctx.NoContent(http.StatusBadRequest)
+			}
+			seenIDs[recipeID] = true
+		}
+		// only allow the user's own recipes to be placed in the book
+		recipes, err := crud.GetRecipesByIDs(authenticatedUser.UserID, *bookData.RecipeIDs)
+		if err != nil {
+			ctx.Logger().Error(err)
+			return ctx.NoContent(http.StatusInternalServerError)
+		} else if len(recipes) != len(*bookData.RecipeIDs) {
+			return ctx.NoContent(http.StatusBadRequest)
+		}
+	}
+
+	if err := crud.UpdateBook(bookID, bookData); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	return ctx.NoContent(http.StatusNoContent)
+}
+
+func deleteBook(ctx echo.Context) error {
+	appConfig := ctx.Get("AppConfig").(config.AppConfig)
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	book, err := crud.GetBookById(bookID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	if err := crud.DeleteBook(bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	if book.ImageID != nil {
+		os.Remove(path.Join(appConfig.DataPath, core.BookImagesOriginalPath, book.ImageID.String()+".jpg"))
+	}
+
+	return ctx.NoContent(http.StatusNoContent)
+}
+
+func postAddBookRecipe(ctx echo.Context) error {
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	var recipeData db.AddBookRecipe
+	if err := ctx.Bind(&recipeData); err != nil {
+		return ctx.NoContent(http.StatusBadRequest)
+	}
+	if err := ctx.Validate(recipeData); err != nil {
+		return err
+	}
+
+	if hasAccess, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeData.RecipeID); err != nil || !hasAccess {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	if err := crud.AddRecipeToBook(bookID, recipeData.RecipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	return ctx.NoContent(http.StatusNoContent)
+}
+
+func deleteBookRecipe(ctx echo.Context) error {
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+	recipeID := uuid.MustParse(ctx.Param("recipeId"))
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	if err := crud.RemoveRecipeFromBook(bookID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	return ctx.NoContent(http.StatusNoContent)
+}
+
+func postSetBookImage(ctx echo.Context) error {
+	appConfig := ctx.Get("AppConfig").(config.AppConfig)
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	book, err := crud.GetBookById(bookID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	content, err := readImageUpload(ctx)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	imageID := uuid.New()
+
+	if err := os.WriteFile(path.Join(appConfig.DataPath, core.BookImagesOriginalPath, imageID.String()+".jpg"), content, 0644); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	if err := crud.UpdateBookImage(bookID, &imageID); err != nil {
+		ctx.Logger().Error(err)
+	}
+
+	// Remove old image if one was set
+	if book.ImageID != nil {
+		os.Remove(path.Join(appConfig.DataPath, core.BookImagesOriginalPath, book.ImageID.String()+".jpg"))
+	}
+
+	return ctx.JSON(http.StatusCreated, imageID.String())
+}
+
+func deleteBookImage(ctx echo.Context) error {
+	appConfig := ctx.Get("AppConfig").(config.AppConfig)
+	bookID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnBook(authenticatedUser.UserID, bookID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	book, err := crud.GetBookById(bookID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+
+	if book.ImageID != nil {
+		os.Remove(path.Join(appConfig.DataPath, core.BookImagesOriginalPath, book.ImageID.String()+".jpg"))
+	}
+
+	if err := crud.UpdateBookImage(bookID, nil); err != nil {
+		ctx.Logger().Error(err)
+	}
+
+	return ctx.NoContent(http.StatusNoContent)
+}
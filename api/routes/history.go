@@ -0,0 +1,82 @@
+package routes
+
+import (
+	"net/http"
+
+	"github.com/enchant97/my-cooking-codex/api/db"
+	"github.com/enchant97/my-cooking-codex/api/db/crud"
+	"github.com/google/uuid"
+	"github.com/labstack/echo/v4"
+)
+
+func getRecipeHistory(ctx echo.Context) error {
+	recipeID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	cookLogs, err := crud.GetCookLogsByRecipeID(recipeID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, cookLogs)
+}
+
+func postRecipeHistory(ctx echo.Context) error {
+	recipeID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	var cookLogData db.CreateCookLog
+	if err := ctx.Bind(&cookLogData); err != nil {
+		return ctx.NoContent(http.StatusBadRequest)
+	}
+	if err := ctx.Validate(cookLogData); err != nil {
+		return err
+	}
+
+	cookLog, err := crud.CreateCookLog(cookLogData.IntoCookLog(authenticatedUser.UserID, recipeID))
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusCreated, cookLog)
+}
+
+func deleteRecipeHistory(ctx echo.Context) error {
+	recipeID := uuid.MustParse(ctx.Param("id"))
+	cookLogID := uuid.MustParse(ctx.Param("logId"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	if exists, err := crud.DoesRecipeHaveCookLog(recipeID, cookLogID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !exists {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	if err := crud.DeleteCookLog(recipeID, cookLogID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.NoContent(http.StatusNoContent)
+}
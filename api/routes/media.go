@@ -15,3 +15,10 @@ func getRecipeImageContent(ctx echo.Context) error {
 
 	return ctx.File(path.Join(appConfig.DataPath, core.RecipeImagesOriginalPath, uuid.MustParse(imageID).String()+".jpg"))
 }
+
+func getBookImageContent(ctx echo.Context) error {
+	appConfig := ctx.Get("AppConfig").(config.AppConfig)
+	imageID := ctx.Param("id")
+
+	return ctx.File(path.Join(appConfig.DataPath, core.BookImagesOriginalPath, uuid.MustParse(imageID).String()+".jpg"))
+}
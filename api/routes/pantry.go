@@ -0,0 +1,84 @@
+package routes
+
+import (
+	"net/http"
+
+	"github.com/enchant97/my-cooking-codex/api/db"
+	"github.com/enchant97/my-cooking-codex/api/db/crud"
+	"github.com/google/uuid"
+	"github.com/labstack/echo/v4"
+)
+
+func getPantryItems(ctx echo.Context) error {
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	items, err := crud.GetPantryItemsByUserID(authenticatedUser.UserID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, items)
+}
+
+func postCreatePantryItem(ctx echo.Context) error {
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	var itemData db.CreatePantryItem
+	if err := ctx.Bind(&itemData); err != nil {
+		return ctx.NoContent(http.StatusBadRequest)
+	}
+	if err := ctx.Validate(itemData); err != nil {
+		return err
+	}
+
+	item, err := crud.CreatePantryItem(itemData.IntoPantryItem(authenticatedUser.UserID))
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusCreated, item)
+}
+
+func patchPantryItem(ctx echo.Context) error {
+	itemID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnPantryItem(authenticatedUser.UserID, itemID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	var itemData db.UpdatePantryItem
+	if err := ctx.Bind(&itemData); err != nil {
+		return ctx.NoContent(http.StatusBadRequest)
+	}
+	if err := ctx.Validate(itemData); err != nil {
+		return err
+	}
+
+	if err := crud.UpdatePantryItem(itemID, itemData); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.NoContent(http.StatusNoContent)
+}
+
+func deletePantryItem(ctx echo.Context) error {
+	itemID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnPantryItem(authenticatedUser.UserID, itemID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	if err := crud.DeletePantryItem(itemID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.NoContent(http.StatusNoContent)
+}
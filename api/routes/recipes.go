@@ -1,8 +1,6 @@
 package routes
 
 import (
-	"bytes"
-	"io"
 	"net/http"
 	"os"
 	"path"
@@ -25,8 +23,46 @@ func postCreateRecipe(ctx echo.Context) error {
 	if err := ctx.Validate(recipeData); err != nil {
 		return err
 	}
+	// a new recipe cannot be used by others yet, so only ownership needs checking
+	if status, err := checkSubRecipes(authenticatedUser.UserID, uuid.Nil, recipeData.Ingredients); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if status != 0 {
+		return ctx.NoContent(status)
+	}
+
+	var imageID *uuid.UUID
+	if recipeData.OriginID != nil {
+		if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, *recipeData.OriginID); err != nil {
+			ctx.Logger().Error(err)
+			return ctx.NoContent(http.StatusInternalServerError)
+		} else if !isOwner {
+			return ctx.NoContent(http.StatusBadRequest)
+		}
+		origin, err := crud.GetRecipeById(*recipeData.OriginID)
+		if err != nil {
+			ctx.Logger().Error(err)
+			return ctx.NoContent(http.StatusInternalServerError)
+		}
+		// the variation gets its own copy, so either image can be changed separately
+		if origin.ImageID != nil {
+			appConfig := ctx.Get("AppConfig").(config.AppConfig)
+			imagesPath := path.Join(appConfig.DataPath, core.RecipeImagesOriginalPath)
+			content, err := os.ReadFile(path.Join(imagesPath, origin.ImageID.String()+".jpg"))
+			if err != nil {
+				ctx.Logger().Error(err)
+				return ctx.NoContent(http.StatusInternalServerError)
+			}
+			newImageID := uuid.New()
+			if err := os.WriteFile(path.Join(imagesPath, newImageID.String()+".jpg"), content, 0644); err != nil {
+				ctx.Logger().Error(err)
+				return ctx.NoContent(http.StatusInternalServerError)
+			}
+			imageID = &newImageID
+		}
+	}
 
-	recipe, err := crud.CreateRecipe(recipeData.IntoRecipe(authenticatedUser.UserID, nil))
+	recipe, err := crud.CreateRecipe(recipeData.IntoRecipe(authenticatedUser.UserID, imageID))
 	if err != nil {
 		ctx.Logger().Error(err)
 		return ctx.NoContent(http.StatusInternalServerError)
@@ -34,6 +70,25 @@ func postCreateRecipe(ctx echo.Context) error {
 	return ctx.JSON(http.StatusCreated, recipe)
 }
 
+func getRecipeVariations(ctx echo.Context) error {
+	recipeID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	recipes, err := crud.GetRecipeVariations(recipeID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, recipes)
+}
+
 func getRecipes(ctx echo.Context) error {
 	authenticatedUser := getAuthenticatedUser(ctx)
 
@@ -47,12 +102,12 @@ func getRecipes(ctx echo.Context) error {
     // convert human page number into database offset
     rowOffset := (filterParams.Page - 1) * filterParams.PerPage
 
-	recipes, err := crud.GetRecipesByUserID(authenticatedUser.UserID, rowOffset, filterParams.PerPage)
+	recipes, count, err := crud.GetRecipesByUserID(authenticatedUser.UserID, rowOffset, filterParams.PerPage, filterParams)
 	if err != nil {
 		ctx.Logger().Error(err)
 		return ctx.NoContent(http.StatusInternalServerError)
 	}
-	return ctx.JSON(http.StatusOK, recipes)
+	return ctx.JSON(http.StatusOK, core.NewPage(recipes, count, filterParams.Page, filterParams.PerPage))
 }
 
 func getRecipe(ctx echo.Context) error {
@@ -74,6 +129,27 @@ func getRecipe(ctx echo.Context) error {
 	return ctx.JSON(http.StatusOK, recipe)
 }
 
+// Status code to reject the recipes used as ingredients with, zero when they are valid.
+// They must belong to the user and not lead back to the recipe they are used in.
+func checkSubRecipes(userID uuid.UUID, recipeID uuid.UUID, ingredients []db.RecipeIngredient) (int, error) {
+	subRecipeIDs := db.SubRecipeIDs(ingredients)
+	if len(subRecipeIDs) == 0 {
+		return 0, nil
+	}
+	ownedRecipes, err := crud.GetRecipesByIDs(userID, subRecipeIDs)
+	if err != nil {
+		return 0, err
+	} else if len(ownedRecipes) != len(subRecipeIDs) {
+		return http.StatusBadRequest, nil
+	}
+	if isCycle, err := crud.DoSubRecipesCreateCycle(recipeID, subRecipeIDs); err != nil {
+		return 0, err
+	} else if isCycle {
+		return http.StatusConflict, nil
+	}
+	return 0, nil
+}
+
 func patchRecipe(ctx echo.Context) error {
 	recipeID := ctx.Param("id")
 	authenticatedUser := getAuthenticatedUser(ctx)
@@ -94,8 +170,16 @@ func patchRecipe(ctx echo.Context) error {
 	if err := ctx.Validate(recipeData); err != nil {
 		return err
 	}
+	if updatedRecipe := recipeData.IntoRecipe(); updatedRecipe.Ingredients != nil {
+		if status, err := checkSubRecipes(authenticatedUser.UserID, uuid.MustParse(recipeID), updatedRecipe.Ingredients.Data); err != nil {
+			ctx.Logger().Error(err)
+			return ctx.NoContent(http.StatusInternalServerError)
+		} else if status != 0 {
+			return ctx.NoContent(status)
+		}
+	}
 
-	if _, err := crud.UpdateRecipe(uuid.MustParse(recipeID), recipeData); err != nil {
+	if _, err := crud.UpdateRecipe(uuid.MustParse(recipeID), authenticatedUser.UserID, recipeData); err != nil {
 		ctx.Logger().Error(err)
 		return ctx.NoContent(http.StatusInternalServerError)
 	}
@@ -153,14 +237,8 @@ func postSetRecipeImage(ctx echo.Context) error {
 		return ctx.NoContent(http.StatusInternalServerError)
 	}
 
-	var content = make([]byte, ctx.Request().ContentLength)
-	// TODO handle errors
-	var b = bytes.Buffer{}
-	io.Copy(&b, ctx.Request().Body)
-	b.Read(content)
-	if optimisedContent, err := core.OptimiseImageToJPEG(content, 2000); err == nil {
-		content = optimisedContent
-	} else {
+	content, err := readImageUpload(ctx)
+	if err != nil {
 		ctx.Logger().Error(err)
 		return ctx.NoContent(http.StatusInternalServerError)
 	}
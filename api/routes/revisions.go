@@ -0,0 +1,62 @@
+package routes
+
+import (
+	"net/http"
+
+	"github.com/enchant97/my-cooking-codex/api/db/crud"
+	"github.com/google/uuid"
+	"github.com/labstack/echo/v4"
+)
+
+func getRecipeRevisions(ctx echo.Context) error {
+	recipeID := uuid.MustParse(ctx.Param("id"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	revisions, err := crud.GetRecipeRevisions(recipeID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, revisions)
+}
+
+func postRestoreRecipeRevision(ctx echo.Context) error {
+	recipeID := uuid.MustParse(ctx.Param("id"))
+	revisionID := uuid.MustParse(ctx.Param("revisionId"))
+	authenticatedUser := getAuthenticatedUser(ctx)
+
+	if isOwner, err := crud.DoesUserOwnRecipe(authenticatedUser.UserID, recipeID); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if !isOwner {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+
+	revision, err := crud.GetRecipeRevisionById(revisionID)
+	if err != nil {
+		return ctx.NoContent(http.StatusNotFound)
+	} else if revision.RecipeID != recipeID {
+		return ctx.NoContent(http.StatusNotFound)
+	}
+	// the sub-recipes may have changed since the revision was made
+	if status, err := checkSubRecipes(authenticatedUser.UserID, recipeID, revision.Snapshot.Data.Ingredients); err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	} else if status != 0 {
+		return ctx.NoContent(status)
+	}
+
+	recipe, err := crud.RestoreRecipeRevision(recipeID, revision, authenticatedUser.UserID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
+	return ctx.JSON(http.StatusOK, recipe)
+}
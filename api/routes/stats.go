@@ -10,6 +10,7 @@ import (
 type accountStats struct {
 	UserCount   int64 `json:"userCount"`
 	RecipeCount int64 `json:"recipeCount"`
+	BookCount   int64 `json:"bookCount"`
 }
 
 func getAccountStats(ctx echo.Context) error {
@@ -20,6 +21,11 @@ func getAccountStats(ctx echo.Context) error {
 		ctx.Logger().Error(err)
 		return ctx.NoContent(http.StatusInternalServerError)
 	}
+	bookCount, err := crud.GetBooksByUserIDCount(authenticatedUser.UserID)
+	if err != nil {
+		ctx.Logger().Error(err)
+		return ctx.NoContent(http.StatusInternalServerError)
+	}
 	userCount, err := crud.GetUserCount()
 	if err != nil {
 		ctx.Logger().Error(err)
@@ -28,5 +34,6 @@ func getAccountStats(ctx echo.Context) error {
 	return ctx.JSON(http.StatusOK, accountStats{
 		UserCount:   userCount,
 		RecipeCount: recipeCount,
+		BookCount:   bookCount,
 	})
 }
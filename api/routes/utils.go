@@ -1,6 +1,7 @@
 package routes
 
 import (
+	"io"
 	"net/http"
 
 	"github.com/enchant97/my-cooking-codex/api/config"
@@ -33,6 +34,15 @@ func getAuthenticatedUser(ctx echo.Context) core.AuthenticatedUser {
 	return ctx.Get(AuthenticatedUserKey).(core.AuthenticatedUser)
 }
 
+// Read an uploaded image from the request body, optimised for storing as a JPEG.
+func readImageUpload(ctx echo.Context) ([]byte, error) {
+	content, err := io.ReadAll(ctx.Request().Body)
+	if err != nil {
+		return nil, err
+	}
+	return core.OptimiseImageToJPEG(content, 2000)
+}
+
 func InitRoutes(e *echo.Echo, appConfig config.AppConfig) {
 	e.POST("/api/users/", postCreateUser)
 	e.POST("/api/login/", postLogin)
@@ -56,11 +66,32 @@ func InitRoutes(e *echo.Echo, appConfig config.AppConfig) {
 		apiRoutes.DELETE("recipes/:id/", deleteRecipe)
 		apiRoutes.POST("recipes/:id/image/", postSetRecipeImage, middleware.BodyLimit("4M"))
 		apiRoutes.DELETE("recipes/:id/image/", deleteRecipeImage)
+		apiRoutes.GET("recipes/:id/variations/", getRecipeVariations)
+		apiRoutes.GET("recipes/:id/revisions/", getRecipeRevisions)
+		apiRoutes.POST("recipes/:id/revisions/:revisionId/restore/", postRestoreRecipeRevision)
+		apiRoutes.GET("recipes/:id/history/", getRecipeHistory)
+		apiRoutes.POST("recipes/:id/history/", postRecipeHistory)
+		apiRoutes.DELETE("recipes/:id/history/:logId/", deleteRecipeHistory)
+		apiRoutes.POST("books/", postCreateBook)
+		apiRoutes.GET("books/", getBooks)
+		apiRoutes.GET("books/:id/", getBook)
+		apiRoutes.PATCH("books/:id/", patchBook)
+		apiRoutes.DELETE("books/:id/", deleteBook)
+		apiRoutes.GET("books/:id/recipes/", getBookRecipes)
+		apiRoutes.POST("books/:id/recipes/", postAddBookRecipe)
+		apiRoutes.DELETE("books/:id/recipes/:recipeId/", deleteBookRecipe)
+		apiRoutes.POST("books/:id/image/", postSetBookImage, middleware.BodyLimit("4M"))
+		apiRoutes.DELETE("books/:id/image/", deleteBookImage)
+		apiRoutes.GET("pantry/", getPantryItems)
+		apiRoutes.POST("pantry/", postCreatePantryItem)
+		apiRoutes.PATCH("pantry/:id/", patchPantryItem)
+		apiRoutes.DELETE("pantry/:id/", deletePantryItem)
 		apiRoutes.GET("stats/me/", getAccountStats)
 	}
 
 	mediaRoutes := e.Group("/media/")
 	{
 		mediaRoutes.GET("recipe-image/:id", getRecipeImageContent)
+		mediaRoutes.GET("book-image/:id", getBookImageContent)
 	}
 }
@@ -4,10 +4,23 @@ pub mod thumbnail_link_grid;
 pub mod header;
 pub mod input;
 pub mod loading;
+pub mod markdown;
+pub mod rating;
+/// Components used for the pantry page
+pub mod pantry;
+/// Components used for the book page
+pub mod book;
 /// Components used for the recipe page
 pub mod recipe;
+pub mod recipe_list;
+pub mod recipe_table;
+pub mod reorder;
+pub mod revisions;
+pub mod saved_searches;
 pub mod stats;
+pub mod step_text;
 pub mod toasts;
+pub mod variations;
 
 pub use header::Header;
 pub use toasts::Toasts;
@@ -0,0 +1,265 @@
+use yew::prelude::*;
+use yew_router::prelude::{use_navigator, Link};
+
+use crate::{
+    contexts::prelude::{push_toast, use_login, use_toasts},
+    core::{
+        handlers::{api_error_to_toast, logout_on_401},
+        types::{
+            book::{Book, UpdateBook},
+            recipe::Recipe,
+        },
+    },
+    modals::{self, ModalController},
+    Route,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct BookContentProps {
+    pub book: Book,
+    /// the book's recipes, in order
+    pub recipes: Vec<Recipe>,
+    pub media_url: AttrValue,
+}
+
+#[function_component(BookContent)]
+pub fn book_content(props: &BookContentProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let navigator = use_navigator().unwrap();
+    let modal_html_state: UseStateHandle<Option<Html>> = use_state(Option::default);
+    let book_state = use_state(|| props.book.clone());
+    let recipes_state = use_state(|| props.recipes.clone());
+
+    let details_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let book_state = book_state.clone();
+        Callback::from(move |details: Option<(String, String)>| {
+            modal_html_state.set(None);
+            if let Some((title, description)) = details {
+                let mut book = (*book_state).clone();
+                book.title = title;
+                book.description = Some(description);
+                book_state.set(book);
+            }
+        })
+    };
+
+    let image_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let book_state = book_state.clone();
+        Callback::from(move |image_id: Option<Option<String>>| {
+            modal_html_state.set(None);
+            if let Some(image_id) = image_id {
+                let mut book = (*book_state).clone();
+                book.image_id = image_id;
+                book_state.set(book);
+            }
+        })
+    };
+
+    let on_edit_details_click = {
+        let modal_html_state = modal_html_state.clone();
+        let book = (*book_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::book::EditDetails
+                    id={book.id.clone()}
+                    title={book.title.clone()}
+                    description={book.description.clone()}
+                    onclose={details_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let on_edit_image_click = {
+        let modal_html_state = modal_html_state.clone();
+        let book = (*book_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::book::SetImage
+                    id={book.id.clone()}
+                    image_id={book.image_id.clone()}
+                    onclose={image_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    // save a new order, restoring the previous one if saving fails
+    let set_recipes_order = {
+        let book_id = book_state.id.clone();
+        let recipes_state = recipes_state.clone();
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        Callback::from(move |recipes: Vec<Recipe>| {
+            let previous_recipes = (*recipes_state).clone();
+            let recipes_state = recipes_state.clone();
+            let book_id = book_id.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let update = UpdateBook {
+                recipe_ids: Some(recipes.iter().map(|recipe| recipe.id.clone()).collect()),
+                ..Default::default()
+            };
+            recipes_state.set(recipes);
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = api.patch_update_book(&book_id, &update).await {
+                    recipes_state.set(previous_recipes);
+                    push_toast(&toasts_ctx, api_error_to_toast(&e, "reordering book"));
+                    logout_on_401(&e, &login_ctx);
+                }
+            });
+        })
+    };
+
+    let on_move_click = {
+        let recipes_state = recipes_state.clone();
+        Callback::from(move |(index, new_index): (usize, usize)| {
+            let mut recipes = (*recipes_state).clone();
+            if new_index < recipes.len() {
+                recipes.swap(index, new_index);
+                set_recipes_order.emit(recipes);
+            }
+        })
+    };
+
+    let on_remove_click = {
+        let book_id = book_state.id.clone();
+        let recipes_state = recipes_state.clone();
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        Callback::from(move |recipe_id: String| {
+            let recipes_state = recipes_state.clone();
+            let book_id = book_id.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.delete_book_recipe(&book_id, &recipe_id).await {
+                    Ok(_) => {
+                        let mut recipes = (*recipes_state).clone();
+                        recipes.retain(|recipe| recipe.id != recipe_id);
+                        recipes_state.set(recipes);
+                    }
+                    Err(e) => {
+                        push_toast(
+                            &toasts_ctx,
+                            api_error_to_toast(&e, "removing recipe from book"),
+                        );
+                        logout_on_401(&e, &login_ctx);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_delete_click = {
+        let book_id = book_state.id.clone();
+        Callback::from(move |_: MouseEvent| {
+            let book_id = book_id.clone();
+            let navigator = navigator.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let login_ctx = login_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.delete_book(&book_id).await {
+                    Ok(_) => {
+                        navigator.push(&Route::Books);
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "deleting book"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let recipe_count = recipes_state.len();
+
+    html! {
+        <>
+        <ModalController modal={(*modal_html_state).clone()}/>
+        <div class="mb-4 relative h-48">
+            if let Some(image_id) = &book_state.image_id {
+                <img
+                    class="object-cover w-full h-full rounded"
+                    src={format!("{}/book-image/{}", props.media_url, image_id)}
+                />
+            } else {
+                <div class="w-full h-full bg-neutral rounded"></div>
+            }
+            <div class="flex items-center absolute bottom-0 left-0 p-2 w-full bg-[#000000cc] rounded-b">
+                <h1 class="mr-auto text-2xl font-bold text-slate-300 whitespace-nowrap overflow-hidden text-ellipsis">{book_state.title.clone()}</h1>
+                <button class="btn" onclick={on_edit_details_click}>{"Edit"}</button>
+                <button class="btn" onclick={on_edit_image_click}>{"Edit Cover"}</button>
+            </div>
+        </div>
+        <div class="mb-4 p-4 rounded bg-base-200 flex items-center">
+            <p class="mr-auto">{book_state.description.clone().unwrap_or_default()}</p>
+            <div class="dropdown dropdown-bottom dropdown-end">
+                <label tabindex="0" class="btn m-1">{"Remove"}</label>
+                <div class="dropdown-content menu bg-base-200 rounded">
+                    <button
+                        tabindex="0"
+                        class="btn btn-outline btn-error"
+                        onclick={on_delete_click}
+                        aria-label={"Confirm Deletion"}>
+                        {"Confirm"}
+                    </button>
+                </div>
+            </div>
+        </div>
+        <div class="p-4 rounded bg-base-200">
+            <h2 class="text-xl font-bold mb-2">{format!("Recipes ({recipe_count})")}</h2>
+            if recipes_state.is_empty() {
+                <p>{"No recipes yet, add them from a recipe's page."}</p>
+            } else {
+                <ol class="flex flex-col gap-2">
+                    { for recipes_state.iter().enumerate().map(|(i, recipe)| {
+                        let recipe_id = recipe.id.clone();
+                        html!{
+                            <li key={recipe.id.clone()} class="flex gap-2 items-center p-2 rounded-md bg-base-100">
+                                if let Some(image_id) = &recipe.image_id {
+                                    <img
+                                        class="object-cover w-12 h-12 rounded-md flex-none"
+                                        src={format!("{}/recipe-image/{}", props.media_url, image_id)}
+                                        loading="lazy"
+                                    />
+                                } else {
+                                    <div class="w-12 h-12 rounded-md flex-none bg-base-300"></div>
+                                }
+                                <Link<Route> to={Route::Recipe { id: recipe.id.clone() }} classes="mr-auto link link-hover font-bold">
+                                    {recipe.title.clone()}
+                                </Link<Route>>
+                                <div class="btn-group">
+                                    <button
+                                        class="btn btn-sm"
+                                        disabled={i == 0}
+                                        onclick={on_move_click.reform(move |_| (i, i.saturating_sub(1)))}
+                                        aria-label="Move Up"
+                                    >{"▲"}</button>
+                                    <button
+                                        class="btn btn-sm"
+                                        disabled={i + 1 == recipe_count}
+                                        onclick={on_move_click.reform(move |_| (i, i + 1))}
+                                        aria-label="Move Down"
+                                    >{"▼"}</button>
+                                    <button
+                                        class="btn btn-sm btn-outline btn-error"
+                                        onclick={on_remove_click.reform(move |_| recipe_id.clone())}
+                                        aria-label="Remove From Book"
+                                    >{"Remove"}</button>
+                                </div>
+                            </li>
+                        }
+                    })}
+                </ol>
+            }
+        </div>
+        </>
+    }
+}
@@ -1,7 +1,65 @@
-use yew::prelude::{classes, function_component, html, Html};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
 use yew_router::prelude::Link;
 
-use crate::{contexts::prelude::use_login, Route, core::APP_TITLE};
+use crate::{
+    contexts::prelude::use_login,
+    core::{
+        search::{self, use_search_index},
+        APP_TITLE,
+    },
+    Route,
+};
+
+const JUMP_RESULTS_LIMIT: usize = 8;
+
+/// Search box for quickly navigating to a recipe by name
+#[function_component(JumpToRecipe)]
+fn jump_to_recipe() -> Html {
+    let is_ready = use_search_index();
+    let query_state = use_state(AttrValue::default);
+
+    let on_query_input = {
+        let query_state = query_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query_state.set(input.value().into());
+        })
+    };
+
+    let on_result_click = {
+        let query_state = query_state.clone();
+        Callback::from(move |_: MouseEvent| query_state.set(AttrValue::default()))
+    };
+
+    let results = match is_ready {
+        true => search::search(&query_state, JUMP_RESULTS_LIMIT),
+        false => Vec::new(),
+    };
+
+    html! {
+        <div class="dropdown dropdown-end">
+            <input
+                class="input input-sm input-bordered w-40 md:w-64 text-base-content"
+                oninput={on_query_input}
+                value={(*query_state).clone()}
+                type="search"
+                placeholder={if is_ready {"Jump to recipe..."} else {"Loading recipes..."}}
+                disabled={!is_ready}
+                aria-label="Jump to recipe"
+            />
+            if !results.is_empty() {
+                <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 text-base-content rounded-box w-64 z-[1]">
+                    { for results.iter().map(|result| html!{
+                        <li key={result.id.clone()} onclick={on_result_click.clone()}>
+                            <Link<Route> to={Route::Recipe { id: result.id.clone() }}>{result.title.clone()}</Link<Route>>
+                        </li>
+                    })}
+                </ul>
+            }
+        </div>
+    }
+}
 
 #[function_component(Header)]
 pub fn header() -> Html {
@@ -17,10 +75,11 @@ pub fn header() -> Html {
         <div class="flex-1">
             <span class="text-xl p-2">{APP_TITLE}</span>
         </div>
-        <div class="flex-none">
+        <div class="flex-none gap-2">
             if current_login.is_none() {
                 <Link<Route> to={Route::Login} classes={classes!("btn", "btn-ghost")}>{"Login"}</Link<Route>>
             } else{
+                <JumpToRecipe/>
                 <Link<Route> to={Route::Logout} classes={classes!("btn", "btn-ghost")}>{"Logout"}</Link<Route>>
             }
         </div>
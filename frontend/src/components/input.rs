@@ -117,35 +117,46 @@ pub fn base_url_selector(props: &BaseUrlSelectorProps) -> Html {
 pub struct FractionalNumberInputProps {
     #[prop_or_default]
     pub classes: Classes,
-    pub value: f32,
-    pub oninput: Callback<f32>,
+    /// none when empty
+    pub value: Option<f32>,
+    /// emitted with the number when the text is one, or none when it is empty
+    pub oninput: Callback<Option<f32>>,
     #[prop_or_default]
     pub placeholder: &'static str,
+    /// whether being empty is invalid
     #[prop_or_default]
     pub required: bool,
 }
 
 #[function_component(FractionalNumberInput)]
 pub fn fractional_input(props: &FractionalNumberInputProps) -> Html {
-    let actual_state = use_state(f32::default);
+    let actual_state = use_state(Option::<f32>::default);
     let input_state = use_state(AttrValue::default);
     let is_valid_state = use_state(bool::default);
 
     {
         let initial_value = props.value;
+        let is_required = props.required;
         let actual_state = actual_state.clone();
         let input_state = input_state.clone();
         let is_valid_state = is_valid_state.clone();
         use_effect_with_deps(
             move |_| {
-                actual_state.set(initial_value);
-                if initial_value == 0.0 {
-                    input_state.set("".into());
-                    is_valid_state.set(false);
+                // the text is kept when it is already the value, so "1/2" is not shown as "0.5"
+                if *actual_state == initial_value && !input_state.is_empty() {
                     return;
                 }
-                is_valid_state.set(true);
-                input_state.set(initial_value.to_string().into());
+                actual_state.set(initial_value);
+                match initial_value {
+                    Some(value) if value != 0.0 => {
+                        is_valid_state.set(true);
+                        input_state.set(value.to_string().into());
+                    }
+                    _ => {
+                        is_valid_state.set(!is_required);
+                        input_state.set("".into());
+                    }
+                }
             },
             props.value,
         );
@@ -153,13 +164,20 @@ pub fn fractional_input(props: &FractionalNumberInputProps) -> Html {
 
     let on_input = {
         let on_input_callback = props.oninput.clone();
+        let is_required = props.required;
+        let actual_state = actual_state.clone();
         let input_state = input_state.clone();
         let is_valid_state = is_valid_state.clone();
         Callback::from(move |e: InputEvent| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
             if let Some(input) = input {
-                if Regex::new(VALID_FRACTIONAL_INPUT_REGEX)
+                if input.value().trim().is_empty() {
+                    input_state.set("".into());
+                    actual_state.set(None);
+                    is_valid_state.set(!is_required);
+                    on_input_callback.emit(None);
+                } else if Regex::new(VALID_FRACTIONAL_INPUT_REGEX)
                     .unwrap()
                     .is_match(&input.value())
                 {
@@ -176,14 +194,14 @@ pub fn fractional_input(props: &FractionalNumberInputProps) -> Html {
                             .parse::<Fraction>()
                             .expect("Failed to parse fraction")
                             .into();
-                        actual_state.set(parsed);
+                        actual_state.set(Some(parsed));
                     } else {
                         // otherwise, just parse it as a float
                         parsed = input.value().parse::<f32>().expect("Failed to parse float");
-                        actual_state.set(parsed);
+                        actual_state.set(Some(parsed));
                     }
                     is_valid_state.set(true);
-                    on_input_callback.emit(parsed);
+                    on_input_callback.emit(Some(parsed));
                 } else {
                     is_valid_state.set(false);
                     input_state.set(input.value().into());
@@ -0,0 +1,155 @@
+//! Markdown written in recipes, such as their notes and steps.
+//!
+//! Only part of CommonMark is shown as formatting: emphasis, lists, links, headings, quotes and code.
+//! Everything is built as elements rather than parsed HTML, so raw HTML is only ever shown as text.
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+use yew::prelude::*;
+
+/// Links to anything else, such as `javascript:` urls, are shown as their text
+pub fn is_safe_url(url: &str) -> bool {
+    let url = url.trim_start().to_ascii_lowercase();
+    ["https://", "http://", "mailto:"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+fn element(tag: Tag, children: Vec<Html>) -> Html {
+    let children = children.into_iter().collect::<Html>();
+    match tag {
+        Tag::Paragraph => html! {<p class="mb-2 last:mb-0">{children}</p>},
+        // the page already has the top level headings
+        Tag::Heading(level, _, _) => match level {
+            HeadingLevel::H1 => html! {<h3 class="text-xl font-bold mb-2">{children}</h3>},
+            HeadingLevel::H2 => html! {<h4 class="text-lg font-bold mb-2">{children}</h4>},
+            _ => html! {<h5 class="font-bold mb-1">{children}</h5>},
+        },
+        Tag::BlockQuote => {
+            html! {<blockquote class="border-l-4 border-base-300 pl-2 mb-2 italic">{children}</blockquote>}
+        }
+        Tag::CodeBlock(_) => {
+            html! {<pre class="bg-base-200 rounded p-2 mb-2 overflow-x-auto"><code>{children}</code></pre>}
+        }
+        Tag::List(Some(start)) => {
+            html! {<ol class="list-decimal ml-6 mb-2" start={start.to_string()}>{children}</ol>}
+        }
+        Tag::List(None) => html! {<ul class="list-disc ml-6 mb-2">{children}</ul>},
+        Tag::Item => html! {<li>{children}</li>},
+        Tag::Emphasis => html! {<em>{children}</em>},
+        Tag::Strong => html! {<strong>{children}</strong>},
+        Tag::Strikethrough => html! {<del>{children}</del>},
+        Tag::Link(_, url, title) if is_safe_url(&url) => html! {
+            <a
+                class="link"
+                href={url.to_string()}
+                title={Some(title.to_string()).filter(|v| !v.is_empty())}
+                target="_blank"
+                rel="noopener noreferrer nofollow"
+            >{children}</a>
+        },
+        // images are shown as their description, as they could be loaded from anywhere
+        _ => children,
+    }
+}
+
+/// Render markdown, with its text rendered by a function so it can be given meaning,
+/// such as references to ingredients
+pub fn render(text: &str, render_text: &dyn Fn(&str) -> Html) -> Html {
+    let mut stack: Vec<(Option<Tag>, Vec<Html>)> = vec![(None, Vec::new())];
+    // text can be split into pieces, so is joined before it is rendered
+    let mut text_buffer = String::new();
+    for event in Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH) {
+        match &event {
+            Event::Text(text) => {
+                text_buffer.push_str(text);
+                continue;
+            }
+            Event::SoftBreak => {
+                text_buffer.push(' ');
+                continue;
+            }
+            _ => {}
+        }
+        let (tag, children) = stack.last_mut().unwrap();
+        if !text_buffer.is_empty() {
+            match tag {
+                Some(Tag::CodeBlock(_)) => children.push(html! {text_buffer.clone()}),
+                _ => children.push(render_text(&text_buffer)),
+            }
+            text_buffer.clear();
+        }
+        match event {
+            Event::Start(tag) => stack.push((Some(tag), Vec::new())),
+            Event::End(_) => {
+                if let Some((Some(tag), children)) = stack.pop() {
+                    let html = element(tag, children);
+                    stack.last_mut().unwrap().1.push(html);
+                }
+            }
+            Event::Code(code) => children.push(
+                html! {<code class="font-mono bg-base-200 rounded px-1">{code.to_string()}</code>},
+            ),
+            Event::HardBreak => children.push(html! {<br/>}),
+            Event::Rule => children.push(html! {<hr class="my-2 border-base-300"/>}),
+            // raw HTML is shown as it was written
+            Event::Html(text) => children.push(html! {text.to_string()}),
+            Event::FootnoteReference(text) => children.push(html! {text.to_string()}),
+            _ => {}
+        }
+    }
+    let (_, mut children) = stack.pop().unwrap();
+    if !text_buffer.is_empty() {
+        children.push(render_text(&text_buffer));
+    }
+    children.into_iter().collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MarkdownProps {
+    pub text: AttrValue,
+}
+
+#[function_component(Markdown)]
+pub fn markdown(props: &MarkdownProps) -> Html {
+    render(&props.text, &|text| html! {text.to_owned()})
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PreviewTabsProps {
+    /// what the text being edited looks like
+    pub preview: Html,
+    pub children: Children,
+}
+
+/// Tabs to switch between editing text and previewing it
+#[function_component(PreviewTabs)]
+pub fn preview_tabs(props: &PreviewTabsProps) -> Html {
+    let is_preview_state = use_state(bool::default);
+
+    let on_tab_click = {
+        let is_preview_state = is_preview_state.clone();
+        Callback::from(move |is_preview: bool| is_preview_state.set(is_preview))
+    };
+
+    html! {
+        <>
+        <div class="tabs mb-2" role="tablist">
+            { for [("Write", false), ("Preview", true)].into_iter().map(|(label, is_preview)| html!{
+                <button
+                    type="button"
+                    role="tab"
+                    class={classes!("tab", "tab-bordered", (*is_preview_state == is_preview).then_some("tab-active"))}
+                    aria-selected={(*is_preview_state == is_preview).to_string()}
+                    onclick={on_tab_click.reform(move |_| is_preview)}
+                >{label}</button>
+            })}
+        </div>
+        // kept while previewing, so what is being edited keeps its state
+        <div class={classes!((*is_preview_state).then_some("hidden"))}>
+            { for props.children.iter() }
+        </div>
+        if *is_preview_state {
+            <div role="tabpanel">{props.preview.clone()}</div>
+        }
+        </>
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::Utc;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    components::loading::LoadingButton,
+    contexts::prelude::{push_toast, use_login, use_toasts},
+    core::{
+        handlers::{api_error_to_toast, logout_on_401},
+        search::{self, use_search_index},
+        types::pantry::PantryItem,
+    },
+    modals::{self, pantry::format_expiry, ModalController},
+    Route,
+};
+
+fn sort_items(items: &mut [PantryItem]) {
+    items.sort_by_key(|item| item.name.to_lowercase());
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CookableRecipesProps {
+    pub items: Vec<PantryItem>,
+    /// only show recipes missing a few ingredients
+    #[prop_or_default]
+    pub near_complete_only: bool,
+    #[prop_or_default]
+    pub limit: Option<usize>,
+}
+
+/// Recipes that can be cooked with what is in the pantry, the closest to being cookable first
+#[function_component(CookableRecipes)]
+pub fn cookable_recipes(props: &CookableRecipesProps) -> Html {
+    let index_ready = use_search_index();
+    if search::has_failed() {
+        return html! {
+            <div class="flex flex-col items-center gap-2">
+                <span>{"Failed To Load Recipes"}</span>
+                <button class="btn" onclick={Callback::from(|_| search::retry())}>{"Retry"}</button>
+            </div>
+        };
+    }
+    if !index_ready {
+        return html! {<LoadingButton classes="btn-block" r#type="button" />};
+    }
+    let matches: Vec<_> = search::pantry_matches(&props.items)
+        .into_iter()
+        .filter(|v| !props.near_complete_only || v.is_near_complete())
+        .take(props.limit.unwrap_or(usize::MAX))
+        .collect();
+    if matches.is_empty() {
+        return html! {<p>{"Nothing matches what is in your pantry yet"}</p>};
+    }
+    html! {
+        <ul class="flex flex-col gap-2">
+        { for matches.into_iter().map(|v| html!{
+            <li class="p-2 rounded bg-base-100">
+                <div class="flex flex-wrap items-baseline gap-2">
+                    <Link<Route> classes="link link-hover font-bold mr-auto" to={Route::Recipe { id: v.id.clone() }}>
+                        {v.title.clone()}
+                    </Link<Route>>
+                    <span class="text-sm opacity-70">{format!("{} of {} ingredients", v.on_hand(), v.needed)}</span>
+                </div>
+                <progress class="progress progress-primary w-full" value={v.on_hand().to_string()} max={v.needed.to_string()}></progress>
+                if v.missing.is_empty() {
+                    <p class="text-sm text-success">{"You have everything"}</p>
+                } else {
+                    <p class="text-sm">{format!("Missing: {}", v.missing.join(", "))}</p>
+                }
+            </li>
+        })}
+        </ul>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PantryContentProps {
+    pub items: Vec<PantryItem>,
+}
+
+#[function_component(PantryContent)]
+pub fn pantry_content(props: &PantryContentProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let modal_html_state: UseStateHandle<Option<Html>> = use_state(Option::default);
+    let items_state = use_state(|| props.items.clone());
+
+    let on_item_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let items_state = items_state.clone();
+        Callback::from(move |saved: Option<PantryItem>| {
+            modal_html_state.set(None);
+            if let Some(saved) = saved {
+                let mut items: Vec<_> = items_state
+                    .iter()
+                    .filter(|v| v.id != saved.id)
+                    .cloned()
+                    .collect();
+                items.push(saved);
+                sort_items(&mut items);
+                items_state.set(items);
+            }
+        })
+    };
+
+    let on_add_click = {
+        let modal_html_state = modal_html_state.clone();
+        let on_item_modal_closed = on_item_modal_closed.clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::pantry::EditPantryItem onclose={on_item_modal_closed.clone()} />
+            }));
+        })
+    };
+
+    let on_edit_click = {
+        let modal_html_state = modal_html_state.clone();
+        Callback::from(move |item: PantryItem| {
+            modal_html_state.set(Some(html! {
+                <modals::pantry::EditPantryItem {item} onclose={on_item_modal_closed.clone()} />
+            }));
+        })
+    };
+
+    let on_delete_click = {
+        let items_state = items_state.clone();
+        Callback::from(move |id: String| {
+            let items_state = items_state.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.delete_pantry_item(&id).await {
+                    Ok(_) => {
+                        items_state
+                            .set(items_state.iter().filter(|v| v.id != id).cloned().collect());
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "removing pantry item"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                }
+            });
+        })
+    };
+
+    let now = Utc::now();
+
+    html! {
+        <>
+        <ModalController modal={(*modal_html_state).clone()}/>
+        <div class="p-4 rounded bg-base-200 mb-4">
+            <div class="flex items-center mb-2">
+                <h1 class="text-3xl font-bold mr-auto">{"Pantry"}</h1>
+                <button class="btn" onclick={on_add_click}>{"Add"}</button>
+            </div>
+            if items_state.is_empty() {
+                <div class="text-center">{"Nothing In Your Pantry Yet"}</div>
+            } else {
+                <div class="overflow-x-auto">
+                    <table class="table table-compact w-full">
+                        <thead>
+                            <tr>
+                                <th>{"Name"}</th>
+                                <th>{"Quantity"}</th>
+                                <th>{"Expires"}</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                        { for items_state.iter().map(|item| {
+                            let edit_item = item.clone();
+                            let id = item.id.clone();
+                            html!{
+                                <tr>
+                                    <td class="whitespace-normal">{&item.name}</td>
+                                    <td>{item.quantity_summary()}</td>
+                                    <td>
+                                        if let Some(expires_at) = &item.expires_at {
+                                            {format_expiry(expires_at)}
+                                            if item.is_expired(now) {
+                                                <span class="badge badge-error badge-sm ml-2">{"expired"}</span>
+                                            } else if item.is_expiring_soon(now) {
+                                                <span class="badge badge-warning badge-sm ml-2">{"expiring soon"}</span>
+                                            }
+                                        }
+                                    </td>
+                                    <td class="text-right">
+                                        <div class="btn-group">
+                                            <button class="btn btn-sm" onclick={on_edit_click.reform(move |_| edit_item.clone())}>{"Edit"}</button>
+                                            <button class="btn btn-sm" onclick={on_delete_click.reform(move |_| id.clone())}>{"Remove"}</button>
+                                        </div>
+                                    </td>
+                                </tr>
+                            }
+                        })}
+                        </tbody>
+                    </table>
+                </div>
+            }
+        </div>
+        <div class="p-4 rounded bg-base-200">
+            <h2 class="text-2xl font-bold mb-2">{"What Can I Cook?"}</h2>
+            <CookableRecipes items={(*items_state).clone()} />
+        </div>
+        </>
+    }
+}
@@ -0,0 +1,60 @@
+use yew::prelude::*;
+
+pub const MAX_RATING: u8 = 5;
+
+#[derive(Properties, PartialEq)]
+pub struct RatingProps {
+    /// name shared by the radio inputs, must be unique on the page
+    pub name: AttrValue,
+    pub rating: Option<u8>,
+    /// emitted with the chosen rating, 0 when it is cleared,
+    /// when not given the rating is shown read-only
+    #[prop_or_default]
+    pub onchange: Option<Callback<u8>>,
+    #[prop_or_default]
+    pub classes: Classes,
+}
+
+/// A 1-5 star rating
+#[function_component(Rating)]
+pub fn rating(props: &RatingProps) -> Html {
+    let rating = props.rating.unwrap_or_default();
+
+    match &props.onchange {
+        Some(onchange) => html! {
+            <div class={classes!("rating", props.classes.clone())}>
+                <input
+                    type="radio"
+                    name={props.name.clone()}
+                    class="rating-hidden"
+                    checked={rating == 0}
+                    onchange={onchange.reform(|_| 0)}
+                    aria-label="No Rating"
+                />
+                { for (1..=MAX_RATING).map(|value| html!{
+                    <input
+                        type="radio"
+                        name={props.name.clone()}
+                        class="mask mask-star-2 bg-orange-400"
+                        checked={rating == value}
+                        onchange={onchange.reform(move |_| value)}
+                        aria-label={format!("{value} Stars")}
+                    />
+                })}
+            </div>
+        },
+        None => html! {
+            <div
+                class={classes!("rating", props.classes.clone())}
+                aria-label={format!("{rating} of {MAX_RATING} Stars")}
+            >
+                { for (1..=MAX_RATING).map(|value| html!{
+                    <div class={classes!(
+                        "mask", "mask-star-2", "bg-orange-400", "w-6", "h-6",
+                        (value > rating).then_some("opacity-20"),
+                    )}></div>
+                })}
+            </div>
+        },
+    }
+}
@@ -1,13 +1,25 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
-use yew_router::prelude::use_navigator;
+use yew_router::prelude::{use_navigator, Link};
 
 use crate::{
+    components::{
+        input::FractionalNumberInput,
+        markdown::{is_safe_url, Markdown},
+        rating::Rating,
+        revisions::Revisions,
+        step_text::StepText,
+        variations::Variations,
+    },
     contexts::{
         login::use_login,
-        prelude::{push_toast, use_toasts},
+        prelude::{push_toast, use_toasts, Toast},
     },
     core::{
+        bakers_percentage, duration,
         handlers::{api_error_to_toast, logout_on_401},
+        pan, schema_org, sections, storage,
+        temperature::{TemperatureDisplay, TemperatureUnits},
         types::{self, Fraction},
     },
     modals::{self, ModalController},
@@ -19,6 +31,9 @@ pub struct RecipeToolbarProps {
     #[prop_or_default]
     pub classes: Classes,
     pub recipe: types::recipe::Recipe,
+    /// emitted after logging that the recipe was cooked
+    #[prop_or_default]
+    pub oncooked: Callback<types::history::CookLog>,
 }
 
 #[function_component(RecipeToolbar)]
@@ -27,6 +42,60 @@ pub fn recipe_toolbar(props: &RecipeToolbarProps) -> Html {
     let navigator = use_navigator().unwrap();
     let toasts_ctx = use_toasts().unwrap();
     let login_ctx = use_login().unwrap();
+    let modal_html_state: UseStateHandle<Option<Html>> = use_state(Option::default);
+
+    let add_to_book_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        Callback::from(move |book_title: Option<String>| {
+            modal_html_state.set(None);
+            if let Some(book_title) = book_title {
+                push_toast(
+                    &toasts_ctx,
+                    Toast {
+                        message: format!("Added to \"{book_title}\""),
+                    },
+                );
+            }
+        })
+    };
+
+    let on_add_to_book_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe_id = (*recipe_state).clone().id;
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::book::AddRecipeToBook
+                    recipe_id={recipe_id.clone()}
+                    onclose={add_to_book_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let cooked_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let on_cooked = props.oncooked.clone();
+        Callback::from(move |cook_log: Option<types::history::CookLog>| {
+            modal_html_state.set(None);
+            if let Some(cook_log) = cook_log {
+                on_cooked.emit(cook_log);
+            }
+        })
+    };
+
+    let on_cooked_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe_id = (*recipe_state).clone().id;
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::recipe::LogCook
+                    id={recipe_id.clone()}
+                    onclose={cooked_modal_closed.clone()}
+                />
+            }));
+        })
+    };
 
     let on_print_click = {
         let recipe_id = (*recipe_state).clone().id;
@@ -43,6 +112,37 @@ pub fn recipe_toolbar(props: &RecipeToolbarProps) -> Html {
         })
     };
 
+    let on_duplicate_click = {
+        let recipe = props.recipe.clone();
+        let navigator = navigator.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        let login_ctx = login_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            let new_recipe = recipe.to_variation(format!("{} (Copy)", recipe.title));
+            let navigator = navigator.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let login_ctx = login_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.post_new_recipe(&new_recipe).await {
+                    Ok(recipe) => {
+                        push_toast(
+                            &toasts_ctx,
+                            Toast {
+                                message: "Duplicated recipe".to_owned(),
+                            },
+                        );
+                        navigator.push(&Route::Recipe { id: recipe.id });
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "duplicating recipe"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
     let on_delete_click = {
         let recipe_id = (*recipe_state).clone().id;
         Callback::from(move |_: MouseEvent| {
@@ -67,7 +167,11 @@ pub fn recipe_toolbar(props: &RecipeToolbarProps) -> Html {
 
     html! {
         <div class={classes!(props.classes.clone())}>
+            <ModalController modal={(*modal_html_state).clone()}/>
+            <button class="btn" onclick={on_cooked_click}>{"Cooked It"}</button>
             <button class="btn" onclick={on_print_click}>{"Print"}</button>
+            <button class="btn" onclick={on_add_to_book_click}>{"Add To Book"}</button>
+            <button class="btn" onclick={on_duplicate_click}>{"Duplicate"}</button>
         <div class="dropdown dropdown-bottom">
             <label tabindex="0" class="btn m-1">{"Remove"}</label>
             <div class="dropdown-content menu bg-base-200 rounded">
@@ -89,6 +193,9 @@ pub struct InfoProps {
     #[prop_or_default]
     pub classes: Classes,
     pub info: types::recipe::Info,
+    /// offers converting the recipe to another pan, when it has one
+    #[prop_or_default]
+    pub on_convert_pan: Option<Callback<MouseEvent>>,
 }
 
 #[function_component(Info)]
@@ -99,7 +206,7 @@ pub fn info(props: &InfoProps) -> Html {
                 if let Some(v) = &props.info.yields {
                     <tr>
                         <th>{v.unit_type.clone()}</th>
-                        <td>{v.value.to_string()}</td>
+                        <td>{v.value_text()}</td>
                     </tr>
                 } else {
                     <tr>
@@ -107,59 +214,345 @@ pub fn info(props: &InfoProps) -> Html {
                         <td>{"0"}</td>
                     </tr>
                 }
+                if let Some(pan) = &props.info.pan {
+                    <tr>
+                        <th>{"Pan"}</th>
+                        <td>
+                            {pan::summary(pan)}
+                            if let Some(on_convert_pan) = &props.on_convert_pan {
+                                <button class="btn btn-xs ml-2" onclick={on_convert_pan.clone()}>{"Convert"}</button>
+                            }
+                        </td>
+                    </tr>
+                }
+                { for props.info.times().into_iter().map(|(label, minutes)| html!{
+                    <tr>
+                        <th>{label}</th>
+                        <td>{duration::format(minutes)}</td>
+                    </tr>
+                })}
+                { for props.info.classifications().into_iter().map(|(label, value)| html!{
+                    <tr>
+                        <th>{label}</th>
+                        <td>{value}</td>
+                    </tr>
+                })}
             </tbody>
         </table>
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct SourceProps {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Where a recipe is from, linking to it when it has a url
+#[function_component(Source)]
+pub fn source(props: &SourceProps) -> Html {
+    let name = props.name.clone().or_else(|| props.url.clone());
+    match (name, &props.url) {
+        (Some(name), Some(url)) if is_safe_url(url) => html! {
+            <p>{"From "}<a class="link" href={url.clone()} target="_blank" rel="noopener noreferrer">{name}</a></p>
+        },
+        (Some(name), _) => html! { <p>{format!("From {name}")}</p> },
+        (None, _) => html! {},
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct IngredientsProps {
     pub items: Vec<types::recipe::Ingredient>,
+    /// steps of the recipe, to show which use each ingredient
+    #[prop_or_default]
+    pub steps: Vec<types::recipe::Step>,
+    /// emitted with what the amounts are multiplied by, when it changes
+    #[prop_or_default]
+    pub on_scale_change: Callback<f32>,
 }
 
 #[function_component(Ingredients)]
 pub fn ingredients(props: &IngredientsProps) -> Html {
+    // numbers of the steps using an ingredient
+    let used_in = |ingredient: &types::recipe::Ingredient| -> Vec<String> {
+        props
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| {
+                ingredient
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| step.ingredients.contains(id))
+            })
+            .map(|(i, _)| (i + 1).to_string())
+            .collect()
+    };
+    let has_used_in = props.steps.iter().any(|step| !step.ingredients.is_empty());
+
+    let is_bakers_state = use_state(bool::default);
+    // indexes of the ingredients making up the 100%, none until chosen
+    let flour_state = use_state(|| Option::<Vec<usize>>::None);
+    // grams of flour to recompute the amounts for
+    let target_flour_state = use_state(|| Option::<f32>::None);
+    {
+        let flour_state = flour_state.clone();
+        let target_flour_state = target_flour_state.clone();
+        use_effect_with_deps(
+            move |_| {
+                flour_state.set(None);
+                target_flour_state.set(None);
+            },
+            props.items.clone(),
+        );
+    }
+
+    let on_bakers_change = {
+        let is_bakers_state = is_bakers_state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            is_bakers_state.set(input.checked());
+        })
+    };
+
+    let flour = (*flour_state)
+        .clone()
+        .unwrap_or_else(|| bakers_percentage::default_flour(&props.items));
+    let flour_grams = bakers_percentage::flour_grams(&props.items, &flour);
+    let on_flour_change = {
+        let flour = flour.clone();
+        let flour_state = flour_state.clone();
+        Callback::from(move |(index, is_flour): (usize, bool)| {
+            let mut flour = flour.clone();
+            flour.retain(|v| *v != index);
+            if is_flour {
+                flour.push(index);
+            }
+            flour_state.set(Some(flour));
+        })
+    };
+
+    let on_target_flour_input = {
+        let target_flour_state = target_flour_state.clone();
+        Callback::from(move |grams: Option<f32>| target_flour_state.set(grams))
+    };
+
+    let is_bakers = *is_bakers_state;
+    let scale = match (is_bakers, *target_flour_state, flour_grams) {
+        (true, Some(target), Some(flour_grams)) => target / flour_grams,
+        _ => 1.0,
+    };
+    {
+        let on_scale_change = props.on_scale_change.clone();
+        use_effect_with_deps(move |scale| on_scale_change.emit(*scale), scale);
+    }
+    // sections with the index of their first ingredient
+    let mut start = 0;
+    let sections: Vec<_> = sections::group(&props.items)
+        .into_iter()
+        .map(|section| {
+            let section_start = start;
+            start += section.items.len();
+            (section_start, section)
+        })
+        .collect();
+    let column_count = 3 + usize::from(has_used_in) + 2 * usize::from(is_bakers);
+
     html! {
+        <>
+        <div class="flex flex-wrap items-center gap-2 mb-2">
+            <label class="label cursor-pointer gap-2">
+                <span class="label-text">{"Baker's %"}</span>
+                <input type="checkbox" class="toggle toggle-sm" checked={is_bakers} onchange={on_bakers_change} />
+            </label>
+            if is_bakers {
+                if flour_grams.is_some() {
+                    <label class="input-group input-group-sm w-auto">
+                        <span>{"Flour"}</span>
+                        <FractionalNumberInput
+                            classes="input-bordered input-sm w-24"
+                            oninput={on_target_flour_input}
+                            value={*target_flour_state}
+                            placeholder={"weight..."}
+                        />
+                        <span>{"g"}</span>
+                    </label>
+                } else {
+                    <span class="text-sm opacity-70">{"Tick the flour, measured by weight, to make it 100%"}</span>
+                }
+            }
+        </div>
         <table class="table table-compact table-zebra w-full">
             <thead>
                 <tr>
+                    if is_bakers {
+                        <th>{"Flour"}</th>
+                    }
                     <th>{"Amount"}</th>
+                    if is_bakers {
+                        <th>{"%"}</th>
+                    }
                     <th>{"Name"}</th>
                     <th>{"Notes"}</th>
+                    if has_used_in {
+                        <th>{"Step"}</th>
+                    }
                 </tr>
             </thead>
+            { for sections.into_iter().map(|(section_start, section)| html!{
             <tbody>
+                if let Some(name) = section.name {
+                    <tr>
+                        <th colspan={column_count.to_string()} class="text-base normal-case">{name}</th>
+                    </tr>
+                }
                 {
-                    for props.items.iter().map(|ingredient| {
+                    for section.items.iter().enumerate().map(|(i, ingredient)| {
+                        let index = section_start + i;
+                        let is_weighed = bakers_percentage::grams(ingredient).is_some();
+                        let is_flour = flour.contains(&index);
                         html!{
                             <tr>
-                                <td class="whitespace-normal">{format!("{} {}", Fraction::from(ingredient.amount), {&ingredient.unit_type})}</td>
-                                <td class="whitespace-normal">{&ingredient.name}</td>
+                                if is_bakers {
+                                    <td>
+                                        if is_weighed {
+                                            <input
+                                                type="checkbox"
+                                                class="checkbox checkbox-sm"
+                                                checked={is_flour}
+                                                onchange={on_flour_change.reform(move |e: Event| {
+                                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                                    (index, input.checked())
+                                                })}
+                                                aria-label="Flour"
+                                            />
+                                        }
+                                    </td>
+                                }
+                                <td class="whitespace-normal">{ingredient.scaled_quantity(scale)}</td>
+                                if is_bakers {
+                                    <td class="whitespace-normal">
+                                        {flour_grams.and_then(|grams| bakers_percentage::percentage(ingredient, grams)).unwrap_or_default()}
+                                    </td>
+                                }
+                                <td class="whitespace-normal">
+                                    if let Some(recipe_id) = &ingredient.recipe_id {
+                                        <Link<Route> classes="link" to={Route::Recipe { id: recipe_id.clone() }}>
+                                            {&ingredient.name}
+                                        </Link<Route>>
+                                    } else {
+                                        {&ingredient.name}
+                                    }
+                                    if ingredient.optional {
+                                        <span class="badge badge-outline badge-sm ml-2">{"optional"}</span>
+                                    }
+                                </td>
                                 <td class="whitespace-normal">{&ingredient.description.clone().unwrap_or_default()}</td>
+                                if has_used_in {
+                                    <td class="whitespace-normal">{used_in(ingredient).join(", ")}</td>
+                                }
                             </tr>
                         }
                     })
                 }
             </tbody>
+            })}
         </table>
+        </>
     }
 }
 
 #[derive(Properties, PartialEq)]
 pub struct StepsProps {
     pub items: Vec<types::recipe::Step>,
+    /// ingredients of the recipe, to show those used by each step
+    #[prop_or_default]
+    pub ingredients: Vec<types::recipe::Ingredient>,
+    /// how temperatures written in the steps are converted
+    #[prop_or_default]
+    pub temperatures: TemperatureDisplay,
+    /// what the amounts of the ingredients are multiplied by
+    #[prop_or(1.0)]
+    pub scale: f32,
 }
 
 #[function_component(Steps)]
 pub fn steps(props: &StepsProps) -> Html {
+    // steps are numbered across every section
+    let mut number = 0;
+    html! {
+        <>
+        { for sections::group(&props.items).into_iter().map(|section| html!{
+            <>
+            if let Some(name) = section.name {
+                <h3 class="text-lg font-bold mb-2 border-b border-base-300">{name}</h3>
+            }
+            <ul>
+            {
+                for section.items.iter().map(|step| {
+                    number += 1;
+                    html!{
+                        <li class="mb-2">
+                            <h2 class="text-l font-bold mb-2">{&step.title.clone().unwrap_or(format!("Step {number}"))}</h2>
+                            <div class="text-base">
+                                <StepText text={step.description.clone()} ingredients={props.ingredients.clone()} temperatures={props.temperatures} scale={props.scale}/>
+                            </div>
+                            if let used_ingredients @ [_, ..] = step.used_ingredients(&props.ingredients).as_slice() {
+                                <p class="mt-1 text-sm opacity-80">
+                                    {format!("You need: {}", used_ingredients.iter().map(|v| v.scaled_summary(props.scale)).collect::<Vec<_>>().join(", "))}
+                                </p>
+                            }
+                        </li>
+                    }
+                })
+            }
+            </ul>
+            </>
+        })}
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct HistoryProps {
+    pub items: Vec<types::history::CookLog>,
+    pub ondelete: Callback<String>,
+}
+
+/// Timeline of each time the recipe was cooked, most recent first
+#[function_component(History)]
+pub fn history(props: &HistoryProps) -> Html {
+    if props.items.is_empty() {
+        return html! {<p>{"Not cooked yet"}</p>};
+    }
     html! {
-        <ul>
+        <ul class="border-l-2 border-base-300 ml-2">
         {
-            for props.items.iter().enumerate().map(|(i, step)| {
+            for props.items.iter().map(|cook_log| {
+                let id = cook_log.id.clone();
                 html!{
-                    <li class="mb-2">
-                        <h2 class="text-l font-bold mb-2">{&step.title.clone().unwrap_or(format!("Step {}", i+1))}</h2>
-                        <pre class="whitespace-normal text-base font-sans">{&step.description}</pre>
+                    <li key={cook_log.id.clone()} class="relative pl-4 pb-4 last:pb-0">
+                        <span class="absolute -left-[7px] top-1.5 w-3 h-3 rounded-full bg-primary"></span>
+                        <div class="flex items-center gap-2 flex-wrap">
+                            <h3 class="font-bold">
+                                {cook_log.cooked_at.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string()}
+                            </h3>
+                            if cook_log.scale != 1.0 {
+                                <span class="badge badge-outline">{format!("× {}", Fraction::from(cook_log.scale))}</span>
+                            }
+                            if cook_log.rating.is_some() {
+                                <Rating name={format!("history-rating-{}", cook_log.id)} rating={cook_log.rating} classes="rating-sm" />
+                            }
+                            <button
+                                class="btn btn-ghost btn-xs ml-auto"
+                                onclick={props.ondelete.reform(move |_| id.clone())}
+                                aria-label="Delete From History"
+                            >{"Remove"}</button>
+                        </div>
+                        if let Some(notes) = &cook_log.notes {
+                            <p class="whitespace-pre-line">{notes.clone()}</p>
+                        }
                     </li>
                 }
             })
@@ -178,8 +571,138 @@ pub struct RecipeContentProps {
 
 #[function_component(RecipeContent)]
 pub fn recipe_content(props: &RecipeContentProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
     let modal_html_state: UseStateHandle<Option<Html>> = use_state(Option::default);
     let recipe_state = use_state(|| props.recipe.clone());
+    let history_state = use_state(Vec::<types::history::CookLog>::new);
+    // the chosen temperature display is remembered for each user
+    let user_id = login_ctx
+        .login
+        .as_ref()
+        .and_then(|login| login.token.subject())
+        .unwrap_or_default();
+    let temperatures_state = use_state(|| storage::read_temperature_display(&user_id));
+    // from the baker's percentage target flour, so steps show the same amounts as the ingredients
+    let ingredients_scale_state = use_state(|| 1.0_f32);
+
+    {
+        let history_state = history_state.clone();
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        use_effect_with_deps(
+            move |id| {
+                let id = id.clone();
+                let api = login_ctx.http_api.clone().unwrap();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.get_recipe_history(&id).await {
+                        Ok(history) => history_state.set(history),
+                        Err(e) => {
+                            push_toast(
+                                &toasts_ctx,
+                                api_error_to_toast(&e, "loading cooking history"),
+                            );
+                            logout_on_401(&e, &login_ctx);
+                        }
+                    }
+                });
+            },
+            props.recipe.id.clone(),
+        );
+    }
+
+    let on_cooked = {
+        let history_state = history_state.clone();
+        let recipe_state = recipe_state.clone();
+        Callback::from(move |cook_log: types::history::CookLog| {
+            let mut history = (*history_state).clone();
+            history.push(cook_log);
+            history.sort_by_key(|v| std::cmp::Reverse(v.cooked_at));
+            let mut recipe = (*recipe_state).clone();
+            recipe.times_cooked = history.len();
+            recipe.last_cooked_at = history.first().map(|v| v.cooked_at);
+            recipe_state.set(recipe);
+            history_state.set(history);
+        })
+    };
+
+    let on_history_delete = {
+        let history_state = history_state.clone();
+        let recipe_state = recipe_state.clone();
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        Callback::from(move |log_id: String| {
+            let history_state = history_state.clone();
+            let recipe_state = recipe_state.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let recipe_id = recipe_state.id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.delete_recipe_history(&recipe_id, &log_id).await {
+                    Ok(_) => {
+                        let history: Vec<_> = history_state
+                            .iter()
+                            .filter(|v| v.id != log_id)
+                            .cloned()
+                            .collect();
+                        let mut recipe = (*recipe_state).clone();
+                        recipe.times_cooked = history.len();
+                        recipe.last_cooked_at = history.first().map(|v| v.cooked_at);
+                        recipe_state.set(recipe);
+                        history_state.set(history);
+                    }
+                    Err(e) => {
+                        push_toast(
+                            &toasts_ctx,
+                            api_error_to_toast(&e, "removing from cooking history"),
+                        );
+                        logout_on_401(&e, &login_ctx);
+                    }
+                }
+            });
+        })
+    };
+
+    // favourite & rating changes are shown straight away, reverting if saving fails
+    let on_quick_update = {
+        let recipe_state = recipe_state.clone();
+        Callback::from(move |update: types::recipe::UpdateRecipe| {
+            let previous = (*recipe_state).clone();
+            let mut recipe = previous.clone();
+            if let Some(favourite) = update.favourite {
+                recipe.favourite = favourite;
+            }
+            if let Some(rating) = update.rating {
+                recipe.rating = (rating != 0).then_some(rating);
+            }
+            recipe_state.set(recipe);
+            let recipe_state = recipe_state.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = api.patch_update_recipe(previous.id.clone(), &update).await {
+                    recipe_state.set(previous);
+                    push_toast(&toasts_ctx, api_error_to_toast(&e, "saving recipe rating"));
+                    logout_on_401(&e, &login_ctx);
+                }
+            });
+        })
+    };
+
+    let on_favourite_click = {
+        let favourite = recipe_state.favourite;
+        on_quick_update.reform(move |_: MouseEvent| types::recipe::UpdateRecipe {
+            favourite: Some(!favourite),
+            ..Default::default()
+        })
+    };
+
+    let on_rating_change = on_quick_update.reform(|rating: u8| types::recipe::UpdateRecipe {
+        rating: Some(rating),
+        ..Default::default()
+    });
 
     let image_modal_closed = {
         let modal_html_state = modal_html_state.clone();
@@ -220,6 +743,35 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
         })
     };
 
+    let classification_modal_closed = info_modal_closed.clone();
+
+    let source_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe_state = recipe_state.clone();
+        Callback::from(move |new_source: Option<modals::recipe::source::Source>| {
+            modal_html_state.set(None);
+            if let Some((name, url)) = new_source {
+                let mut recipe = (*recipe_state).clone();
+                recipe.source_name = name;
+                recipe.source_url = url;
+                recipe_state.set(recipe)
+            }
+        })
+    };
+
+    let equipment_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe_state = recipe_state.clone();
+        Callback::from(move |new_equipment: Option<Vec<String>>| {
+            modal_html_state.set(None);
+            if let Some(equipment) = new_equipment {
+                let mut recipe = (*recipe_state).clone();
+                recipe.equipment = equipment;
+                recipe_state.set(recipe)
+            }
+        })
+    };
+
     let description_modal_closed = {
         let modal_html_state = modal_html_state.clone();
         let recipe_state = recipe_state.clone();
@@ -250,11 +802,12 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
         let modal_html_state = modal_html_state.clone();
         let recipe_state = recipe_state.clone();
         Callback::from(
-            move |new_ingredients: Option<Vec<types::recipe::Ingredient>>| {
+            move |result: Option<(Vec<types::recipe::Ingredient>, Vec<types::recipe::Step>)>| {
                 modal_html_state.set(None);
-                if let Some(ingredients) = new_ingredients {
+                if let Some((ingredients, steps)) = result {
                     let mut recipe = (*recipe_state).clone();
                     recipe.ingredients = ingredients;
+                    recipe.steps = steps;
                     recipe_state.set(recipe)
                 }
             },
@@ -264,16 +817,38 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
     let steps_modal_closed = {
         let modal_html_state = modal_html_state.clone();
         let recipe_state = recipe_state.clone();
-        Callback::from(move |new_steps: Option<Vec<types::recipe::Step>>| {
+        Callback::from(
+            move |updated: Option<(Vec<types::recipe::Step>, Vec<types::recipe::Ingredient>)>| {
+                modal_html_state.set(None);
+                if let Some((steps, ingredients)) = updated {
+                    let mut recipe = (*recipe_state).clone();
+                    recipe.steps = steps;
+                    recipe.ingredients = ingredients;
+                    recipe_state.set(recipe)
+                }
+            },
+        )
+    };
+
+    let review_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe_state = recipe_state.clone();
+        Callback::from(move |new_review: Option<modals::recipe::review::Review>| {
             modal_html_state.set(None);
-            if let Some(steps) = new_steps {
+            if let Some((rating, review)) = new_review {
                 let mut recipe = (*recipe_state).clone();
-                recipe.steps = steps;
+                recipe.rating = rating;
+                recipe.review = review;
                 recipe_state.set(recipe)
             }
         })
     };
 
+    let on_revision_restore = {
+        let recipe_state = recipe_state.clone();
+        Callback::from(move |recipe: types::recipe::Recipe| recipe_state.set(recipe))
+    };
+
     let on_edit_image_click = {
         let modal_html_state = modal_html_state.clone();
         let recipe = (*recipe_state).clone();
@@ -316,6 +891,66 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
         })
     };
 
+    let on_edit_classification_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe = (*recipe_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::recipe::EditClassification
+                    id={recipe.id.clone()}
+                    info={recipe.info.clone()}
+                    onclose={classification_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let on_edit_source_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe = (*recipe_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::recipe::EditSource
+                    id={recipe.id.clone()}
+                    name={recipe.source_name.clone()}
+                    url={recipe.source_url.clone()}
+                    onclose={source_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let on_edit_equipment_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe = (*recipe_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::recipe::EditEquipment
+                    id={recipe.id.clone()}
+                    equipment={recipe.equipment.clone()}
+                    onclose={equipment_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let on_convert_pan_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe = (*recipe_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            let modal_html_state_close = modal_html_state.clone();
+            if let Some(pan) = &recipe.info.pan {
+                modal_html_state.set(Some(html! {
+                    <modals::recipe::ConvertPan
+                        pan={pan.clone()}
+                        ingredients={recipe.ingredients.clone()}
+                        onclose={Callback::from(move |_| modal_html_state_close.set(None))}
+                    />
+                }));
+            }
+        })
+    };
+
     let on_edit_description_click = {
         let modal_html_state = modal_html_state.clone();
         let recipe = (*recipe_state).clone();
@@ -352,12 +987,62 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
                 <modals::recipe::EditIngredients
                     id={recipe.id.clone()}
                     ingredients={recipe.ingredients.clone()}
+                    steps={recipe.steps.clone()}
                     onclose={ingredients_modal_closed.clone()}
                 />
             }));
         })
     };
 
+    let on_edit_review_click = {
+        let modal_html_state = modal_html_state.clone();
+        let recipe = (*recipe_state).clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::recipe::EditReview
+                    id={recipe.id.clone()}
+                    rating={recipe.rating}
+                    review={recipe.review.clone()}
+                    onclose={review_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let on_temperature_units_change = {
+        let user_id = user_id.clone();
+        let temperatures_state = temperatures_state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            let display = TemperatureDisplay {
+                units: TemperatureUnits::ALL
+                    .get(input.selected_index() as usize)
+                    .copied()
+                    .unwrap_or_default(),
+                ..*temperatures_state
+            };
+            storage::set_temperature_display(&user_id, display);
+            temperatures_state.set(display);
+        })
+    };
+
+    let on_ingredients_scale_change = {
+        let ingredients_scale_state = ingredients_scale_state.clone();
+        Callback::from(move |scale: f32| ingredients_scale_state.set(scale))
+    };
+
+    let on_fan_oven_change = {
+        let temperatures_state = temperatures_state.clone();
+        Callback::from(move |_: Event| {
+            let display = TemperatureDisplay {
+                fan_oven: !temperatures_state.fan_oven,
+                ..*temperatures_state
+            };
+            storage::set_temperature_display(&user_id, display);
+            temperatures_state.set(display);
+        })
+    };
+
     let on_edit_steps_click = {
         let modal_html_state = modal_html_state.clone();
         let recipe = (*recipe_state).clone();
@@ -366,6 +1051,7 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
                 <modals::recipe::EditSteps
                     id={recipe.id.clone()}
                     steps={recipe.steps.clone()}
+                    ingredients={recipe.ingredients.clone()}
                     onclose={steps_modal_closed.clone()}
                 />
             }));
@@ -391,13 +1077,50 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
                     <button class="btn" onclick={on_edit_image_click}>{"Edit Image"}</button>
                 </div>
             </div>
-            <RecipeToolbar classes="mb-4 p-4 rounded bg-base-200" recipe={(*recipe_state).clone()} />
+            <RecipeToolbar classes="mb-4 p-4 rounded bg-base-200" recipe={(*recipe_state).clone()} oncooked={on_cooked} />
+            <Variations
+                classes="mb-4 p-4 rounded bg-base-200 flex flex-col gap-2"
+                recipe_id={recipe_state.id.clone()}
+                origin_id={recipe_state.origin_id.clone().map(AttrValue::from)}
+            />
             <div class="mb-4 p-4 rounded bg-base-200">
-                <div class="flex mb-2">
+                <div class="flex items-center gap-2 mb-2">
+                    <h2 class="text-xl font-bold mr-auto">{"Rating"}</h2>
+                    <button
+                        class={classes!("btn", recipe_state.favourite.then_some("btn-secondary"))}
+                        onclick={on_favourite_click}
+                        aria-pressed={recipe_state.favourite.to_string()}
+                    >{ if recipe_state.favourite { "♥ Favourite" } else { "♡ Favourite" } }</button>
+                    <button class="btn" onclick={on_edit_review_click}>{"Edit"}</button>
+                </div>
+                <Rating name="recipe-rating" rating={recipe_state.rating} onchange={on_rating_change} />
+                if let Some(review) = &recipe_state.review {
+                    <p class="mt-2 italic">{review.clone()}</p>
+                }
+            </div>
+            <div class="mb-4 p-4 rounded bg-base-200">
+                <div class="flex gap-2 mb-2">
                     <h2 class="text-xl font-bold mr-auto">{"Info"}</h2>
+                    <button class="btn" onclick={on_edit_classification_click}>{"Edit Classification"}</button>
                     <button class="btn" onclick={on_edit_info_click}>{"Edit"}</button>
                 </div>
-                <Info info={recipe_state.info.clone()} />
+                <Info info={recipe_state.info.clone()} on_convert_pan={on_convert_pan_click} />
+            </div>
+            <div class="mb-4 p-4 rounded bg-base-200">
+                <div class="flex mb-2">
+                    <h2 class="text-xl font-bold mr-auto">{"Source"}</h2>
+                    <button class="btn" onclick={on_edit_source_click}>{"Edit"}</button>
+                </div>
+                <Source name={recipe_state.source_name.clone()} url={recipe_state.source_url.clone()} />
+            </div>
+            <div class="mb-4 p-4 rounded bg-base-200">
+                <div class="flex mb-2">
+                    <h2 class="text-xl font-bold mr-auto">{"Equipment"}</h2>
+                    <button class="btn" onclick={on_edit_equipment_click}>{"Edit"}</button>
+                </div>
+                <ul class="list-disc list-inside">
+                    { for recipe_state.equipment.iter().map(|item| html!{<li>{item.clone()}</li>}) }
+                </ul>
             </div>
             <div class="mb-4 p-4 rounded bg-base-200">
                 <div class="flex mb-2">
@@ -411,7 +1134,7 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
                     <h2 class="text-xl font-bold mr-auto">{"Notes"}</h2>
                     <button class="btn" onclick={on_edit_long_description_click}>{"Edit"}</button>
                 </div>
-                <pre class="whitespace-normal text-base font-sans">{recipe_state.long_description.clone()}</pre>
+                <div class="text-base"><Markdown text={recipe_state.long_description.clone().unwrap_or_default()}/></div>
             </div>
             <div class="flex flex-col md:flex-row gap-4">
                 <div class="basis-full md:basis-3/4 lg:basis-11/12 p-4 rounded bg-base-200">
@@ -419,17 +1142,46 @@ pub fn recipe_content(props: &RecipeContentProps) -> Html {
                         <h2 class="text-xl font-bold mr-auto">{"Ingredients"}</h2>
                         <button class="btn" onclick={on_edit_ingredients_click}>{"Edit"}</button>
                     </div>
-                    <Ingredients items={recipe_state.ingredients.clone()}/>
+                    <Ingredients
+                        items={recipe_state.ingredients.clone()}
+                        steps={recipe_state.steps.clone()}
+                        on_scale_change={on_ingredients_scale_change}
+                    />
                 </div>
                 <div class="w-full p-4 rounded bg-base-200">
-                    <div class="flex mb-2">
+                    <div class="flex flex-wrap items-center gap-2 mb-2">
                         <h2 class="text-xl font-bold mr-auto">{"Steps"}</h2>
+                        <select class="select select-bordered select-sm" onchange={on_temperature_units_change} aria-label="Temperatures">
+                            { for TemperatureUnits::ALL.iter().map(|units| html!{
+                                <option selected={*units == temperatures_state.units}>{units.label()}</option>
+                            })}
+                        </select>
+                        <label class="label cursor-pointer gap-2">
+                            <span class="label-text">{"Fan Oven"}</span>
+                            <input type="checkbox" class="toggle toggle-sm" checked={temperatures_state.fan_oven} onchange={on_fan_oven_change} />
+                        </label>
                         <button class="btn" onclick={on_edit_steps_click}>{"Edit"}</button>
                     </div>
-                    <Steps items={recipe_state.steps.clone()}/>
+                    <Steps
+                        items={recipe_state.steps.clone()}
+                        ingredients={recipe_state.ingredients.clone()}
+                        temperatures={*temperatures_state}
+                        scale={*ingredients_scale_state}
+                    />
+                </div>
+            </div>
+            <div class="mt-4 p-4 rounded bg-base-200">
+                <div class="flex items-baseline gap-2 mb-2">
+                    <h2 class="text-xl font-bold mr-auto">{"History"}</h2>
+                    if let Some(summary) = recipe_state.cooked_summary() {
+                        <span class="text-sm opacity-70">{summary}</span>
+                    }
                 </div>
+                <History items={(*history_state).clone()} ondelete={on_history_delete} />
             </div>
+            <Revisions recipe_id={recipe_state.id.clone()} onrestore={on_revision_restore} />
         </div>
+        <script type="application/ld+json">{schema_org::recipe_json_ld(&recipe_state, *temperatures_state).to_string()}</script>
         </>
     }
 }
@@ -0,0 +1,72 @@
+use yew::{function_component, html, AttrValue, Html, Properties};
+use yew_router::prelude::*;
+
+use crate::Route;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ListItemProps {
+    pub navigate_to: Route,
+    pub title: AttrValue,
+    #[prop_or_default]
+    pub image_src: Option<AttrValue>,
+    #[prop_or_default]
+    pub description: Option<AttrValue>,
+    #[prop_or_default]
+    pub tags: Vec<AttrValue>,
+    /// summary of the cooking history
+    #[prop_or_default]
+    pub cooked: Option<AttrValue>,
+}
+
+#[function_component(ListItem)]
+pub fn list_item(props: &ListItemProps) -> Html {
+    html! {
+        <li>
+            <Link<Route> to={props.navigate_to.clone()} classes="flex gap-3 items-center p-2 rounded-md bg-base-100 hover:bg-base-300 hover:duration-200">
+                if props.image_src.is_some() {
+                    <img
+                        class="object-cover w-16 h-16 rounded-md flex-none"
+                        src={props.image_src.clone()}
+                        loading="lazy"
+                        decoding="async"
+                    />
+                } else {
+                    <div class="w-16 h-16 rounded-md flex-none bg-base-300"></div>
+                }
+                <div class="min-w-0">
+                    <h2 class="text-lg font-bold whitespace-nowrap overflow-hidden text-ellipsis">{props.title.clone()}</h2>
+                    if let Some(description) = &props.description {
+                        <p class="whitespace-nowrap overflow-hidden text-ellipsis">{description.clone()}</p>
+                    }
+                    if let Some(cooked) = &props.cooked {
+                        <p class="text-sm opacity-70">{cooked.clone()}</p>
+                    }
+                    if !props.tags.is_empty() {
+                        <div class="flex flex-wrap gap-1 mt-1">
+                            { for props.tags.iter().map(|tag| html!{
+                                <span class="badge badge-outline">{tag.clone()}</span>
+                            })}
+                        </div>
+                    }
+                </div>
+            </Link<Route>>
+        </li>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ListProps {
+    pub items: Vec<ListItemProps>,
+}
+
+/// A compact list of recipes, showing their description and tags
+#[function_component(List)]
+pub fn list(props: &ListProps) -> Html {
+    html! {
+        <ul class="flex flex-col gap-2 rounded p-3 bg-base-200">
+            { for props.items.iter().map(|item| html!{
+                <ListItem key={item.navigate_to.to_path()} ..item.clone() />
+            })}
+        </ul>
+    }
+}
@@ -0,0 +1,147 @@
+use std::cmp::Ordering;
+
+use yew::{function_component, html, use_state, Callback, Html, Properties};
+use yew_router::prelude::*;
+
+use crate::{
+    core::{duration, types::recipe::Recipe},
+    Route,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Title,
+    Yields,
+    Ingredients,
+    Steps,
+    TotalTime,
+    LastCooked,
+    TimesCooked,
+    Updated,
+}
+
+impl Column {
+    const ALL: [Self; 8] = [
+        Self::Title,
+        Self::Yields,
+        Self::Ingredients,
+        Self::Steps,
+        Self::TotalTime,
+        Self::LastCooked,
+        Self::TimesCooked,
+        Self::Updated,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Title => "Title",
+            Self::Yields => "Yields",
+            Self::Ingredients => "Ingredients",
+            Self::Steps => "Steps",
+            Self::TotalTime => "Total Time",
+            Self::LastCooked => "Last Cooked",
+            Self::TimesCooked => "Times Cooked",
+            Self::Updated => "Last Updated",
+        }
+    }
+
+    fn compare(&self, a: &Recipe, b: &Recipe) -> Ordering {
+        match self {
+            Self::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            Self::Yields => {
+                let yields = |recipe: &Recipe| recipe.info.yields.as_ref().map(|v| v.range());
+                yields(a).partial_cmp(&yields(b)).unwrap_or(Ordering::Equal)
+            }
+            Self::Ingredients => a.ingredients.len().cmp(&b.ingredients.len()),
+            Self::Steps => a.steps.len().cmp(&b.steps.len()),
+            Self::TotalTime => a.info.total_time().cmp(&b.info.total_time()),
+            Self::LastCooked => a.last_cooked_at.cmp(&b.last_cooked_at),
+            Self::TimesCooked => a.times_cooked.cmp(&b.times_cooked),
+            Self::Updated => a.updated_at.cmp(&b.updated_at),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TableProps {
+    pub recipes: Vec<Recipe>,
+}
+
+/// A table of recipes, sortable by clicking a column heading
+#[function_component(Table)]
+pub fn table(props: &TableProps) -> Html {
+    // column to sort by and whether it is ascending, none keeps the given order
+    let sort_state = use_state(|| Option::<(Column, bool)>::None);
+
+    let mut recipes: Vec<&Recipe> = props.recipes.iter().collect();
+    if let Some((column, is_ascending)) = *sort_state {
+        recipes.sort_by(|a, b| match is_ascending {
+            true => column.compare(a, b),
+            false => column.compare(b, a),
+        });
+    }
+
+    let on_sort_click = {
+        let sort_state = sort_state.clone();
+        Callback::from(move |column: Column| {
+            let is_ascending = match *sort_state {
+                Some((current, is_ascending)) if current == column => !is_ascending,
+                _ => true,
+            };
+            sort_state.set(Some((column, is_ascending)));
+        })
+    };
+
+    html! {
+        <div class="overflow-x-auto rounded p-3 bg-base-200">
+            <table class="table table-compact w-full">
+                <thead>
+                    <tr>
+                        { for Column::ALL.iter().map(|column| {
+                            let column = *column;
+                            let indicator = match *sort_state {
+                                Some((current, true)) if current == column => " ▲",
+                                Some((current, false)) if current == column => " ▼",
+                                _ => "",
+                            };
+                            html!{
+                                <th>
+                                    <button
+                                        class="uppercase font-bold"
+                                        onclick={on_sort_click.reform(move |_| column)}
+                                    >{format!("{}{}", column.label(), indicator)}</button>
+                                </th>
+                            }
+                        })}
+                    </tr>
+                </thead>
+                <tbody>
+                    { for recipes.iter().map(|recipe| html!{
+                        <tr key={recipe.id.clone()} class="hover">
+                            <td>
+                                <Link<Route> to={Route::Recipe { id: recipe.id.clone() }} classes="link link-hover font-bold">
+                                    {recipe.title.clone()}
+                                </Link<Route>>
+                            </td>
+                            <td>
+                                if let Some(yields) = &recipe.info.yields {
+                                    {yields.summary()}
+                                }
+                            </td>
+                            <td>{recipe.ingredients.len()}</td>
+                            <td>{recipe.steps.len()}</td>
+                            <td>{recipe.info.total_time().map(duration::format)}</td>
+                            <td>
+                                if let Some(last_cooked_at) = recipe.last_cooked_at {
+                                    {last_cooked_at.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string()}
+                                }
+                            </td>
+                            <td>{recipe.times_cooked}</td>
+                            <td>{recipe.updated_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                        </tr>
+                    })}
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,156 @@
+//! Reordering items by dragging their handle (with a mouse, pen or touch) or with the arrow keys.
+//!
+//! Elements that items can be dropped on are marked with their position as `data-drop-position`,
+//! dropping on the top half of one puts the item before it & the bottom half after it.
+use web_sys::{Element, HtmlElement};
+use yew::prelude::*;
+
+use crate::core::sections::ItemPosition;
+
+const DROP_POSITION_ATTRIBUTE: &str = "data-drop-position";
+
+/// Value for the `data-drop-position` attribute
+pub fn drop_position_attribute(position: ItemPosition) -> String {
+    format!("{}:{}", position.0, position.1)
+}
+
+/// Where an item would be dropped, if released at a point in the window
+fn drop_position_at(x: i32, y: i32) -> Option<ItemPosition> {
+    let element = gloo::utils::document().element_from_point(x as f32, y as f32)?;
+    let target = element
+        .closest(&format!("[{DROP_POSITION_ATTRIBUTE}]"))
+        .ok()??;
+    let (section, index) = target
+        .get_attribute(DROP_POSITION_ATTRIBUTE)?
+        .split_once(':')
+        .and_then(|(section, index)| Some((section.parse().ok()?, index.parse().ok()?)))?;
+    let rect = target.get_bounding_client_rect();
+    match (y as f64) > rect.top() + rect.height() / 2.0 {
+        true => Some((section, index + 1)),
+        false => Some((section, index)),
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DragHandleProps {
+    pub position: ItemPosition,
+    /// emitted with where the item would be dropped while dragging, none once finished
+    pub on_drag: Callback<Option<ItemPosition>>,
+    /// emitted with where the item was and where it was dropped
+    pub on_drop: Callback<(ItemPosition, ItemPosition)>,
+    /// emitted when moved with the arrow keys, with whether it was moved up
+    pub on_move: Callback<(ItemPosition, bool)>,
+}
+
+/// Handle to move an item by dragging or by focusing it and using the arrow keys
+#[function_component(DragHandle)]
+pub fn drag_handle(props: &DragHandleProps) -> Html {
+    let handle_ref = use_node_ref();
+    let is_dragging_ref = use_mut_ref(bool::default);
+    let target_ref = use_mut_ref(|| Option::<ItemPosition>::None);
+    let refocus_ref = use_mut_ref(bool::default);
+
+    // rows are moved in the page when reordered, which can lose focus
+    {
+        let handle_ref = handle_ref.clone();
+        let refocus_ref = refocus_ref.clone();
+        use_effect_with_deps(
+            move |_| {
+                if refocus_ref.replace(false) {
+                    if let Some(handle) = handle_ref.cast::<HtmlElement>() {
+                        let _ = handle.focus();
+                    }
+                }
+            },
+            props.position,
+        );
+    }
+
+    let on_pointer_down = {
+        let handle_ref = handle_ref.clone();
+        let is_dragging_ref = is_dragging_ref.clone();
+        Callback::from(move |e: PointerEvent| {
+            if !e.is_primary() {
+                return;
+            }
+            e.prevent_default();
+            // keep receiving pointer events when moved off the handle
+            if let Some(handle) = handle_ref.cast::<Element>() {
+                let _ = handle.set_pointer_capture(e.pointer_id());
+            }
+            *is_dragging_ref.borrow_mut() = true;
+        })
+    };
+
+    let on_pointer_move = {
+        let is_dragging_ref = is_dragging_ref.clone();
+        let target_ref = target_ref.clone();
+        let on_drag_callback = props.on_drag.clone();
+        Callback::from(move |e: PointerEvent| {
+            if !*is_dragging_ref.borrow() {
+                return;
+            }
+            let target = drop_position_at(e.client_x(), e.client_y());
+            if *target_ref.borrow() != target {
+                *target_ref.borrow_mut() = target;
+                on_drag_callback.emit(target);
+            }
+        })
+    };
+
+    let on_pointer_up = {
+        let is_dragging_ref = is_dragging_ref.clone();
+        let target_ref = target_ref.clone();
+        let on_drag_callback = props.on_drag.clone();
+        let on_drop_callback = props.on_drop.clone();
+        let position = props.position;
+        Callback::from(move |_: PointerEvent| {
+            if is_dragging_ref.replace(false) {
+                if let Some(target) = target_ref.take() {
+                    on_drop_callback.emit((position, target));
+                }
+                on_drag_callback.emit(None);
+            }
+        })
+    };
+
+    let on_pointer_cancel = {
+        let on_drag_callback = props.on_drag.clone();
+        Callback::from(move |_: PointerEvent| {
+            if is_dragging_ref.replace(false) {
+                target_ref.take();
+                on_drag_callback.emit(None);
+            }
+        })
+    };
+
+    let on_key_down = {
+        let on_move_callback = props.on_move.clone();
+        let position = props.position;
+        Callback::from(move |e: KeyboardEvent| {
+            let is_up = match e.key().as_str() {
+                "ArrowUp" => true,
+                "ArrowDown" => false,
+                _ => return,
+            };
+            e.prevent_default();
+            *refocus_ref.borrow_mut() = true;
+            on_move_callback.emit((position, is_up));
+        })
+    };
+
+    html! {
+        <button
+            ref={handle_ref}
+            type="button"
+            class="btn btn-ghost px-2 cursor-grab touch-none select-none"
+            title="Drag, or use the up and down arrow keys to move"
+            aria-label="Move, using the up and down arrow keys"
+            onpointerdown={on_pointer_down}
+            onpointermove={on_pointer_move}
+            onpointerup={on_pointer_up}
+            onpointercancel={on_pointer_cancel}
+            onkeydown={on_key_down}
+        >{"⠿"}</button>
+    }
+}
@@ -0,0 +1,342 @@
+use yew::prelude::*;
+
+use crate::{
+    contexts::prelude::{push_toast, use_login, use_toasts, Toast},
+    core::{
+        diff::{diff_list, diff_text, has_list_changes, ListChange, TextChange},
+        duration,
+        handlers::{api_error_to_toast, logout_on_401},
+        ingredient_references::strip_references,
+        pan,
+        types::{
+            recipe::{Ingredient, Recipe, Step},
+            revision::{RecipeRevision, RecipeSnapshot},
+        },
+    },
+};
+
+fn text_diff(old: &str, new: &str) -> Html {
+    html! {
+        <p class="whitespace-pre-line">
+            { for diff_text(old, new).into_iter().map(|change| match change {
+                TextChange::Equal(v) => html!{<span>{v}</span>},
+                TextChange::Removed(v) => html!{<del class="bg-error text-error-content">{v}</del>},
+                TextChange::Added(v) => html!{<ins class="bg-success text-success-content no-underline">{v}</ins>},
+            })}
+        </p>
+    }
+}
+
+fn ingredient_text(ingredient: &Ingredient) -> String {
+    let mut text = ingredient.summary();
+    if let Some(description) = &ingredient.description {
+        text.push_str(&format!(" ({description})"));
+    }
+    with_section(text, ingredient.section.as_deref())
+}
+
+fn step_text(step: &Step) -> String {
+    let text = match &step.title {
+        Some(title) => format!("{title}: {}", strip_references(&step.description)),
+        None => strip_references(&step.description),
+    };
+    with_section(text, step.section.as_deref())
+}
+
+fn with_section(text: String, section: Option<&str>) -> String {
+    match section {
+        Some(section) => format!("[{section}] {text}"),
+        None => text,
+    }
+}
+
+/// Show the changes of a list, only including the items that changed
+fn list_diff<T>(changes: &[ListChange<T>], to_text: impl Fn(&T) -> String) -> Html {
+    html! {
+        <ul class="flex flex-col gap-1">
+            { for changes.iter().filter_map(|change| match change {
+                ListChange::Unchanged(_) => None,
+                ListChange::Removed(v) => Some(html!{
+                    <li><del class="bg-error text-error-content">{format!("- {}", to_text(v))}</del></li>
+                }),
+                ListChange::Added(v) => Some(html!{
+                    <li><ins class="bg-success text-success-content no-underline">{format!("+ {}", to_text(v))}</ins></li>
+                }),
+                ListChange::Changed(old, new) => Some(html!{
+                    <li>{text_diff(&to_text(old), &to_text(new))}</li>
+                }),
+            })}
+        </ul>
+    }
+}
+
+fn diff_section(title: &'static str, content: Html) -> Html {
+    html! {
+        <div>
+            <h4 class="font-bold">{title}</h4>
+            {content}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RevisionDiffProps {
+    /// the revision before, none for the first revision
+    pub old: Option<RecipeSnapshot>,
+    pub new: RecipeSnapshot,
+}
+
+/// The changes made to a recipe by a revision
+#[function_component(RevisionDiff)]
+pub fn revision_diff(props: &RevisionDiffProps) -> Html {
+    let old = match &props.old {
+        Some(v) => v,
+        None => return html! {<p class="italic">{"First saved version"}</p>},
+    };
+    let new = &props.new;
+    let mut sections = Vec::new();
+
+    if old.title != new.title {
+        sections.push(diff_section("Title", text_diff(&old.title, &new.title)));
+    }
+    if old.info != new.info {
+        let info = |snapshot: &RecipeSnapshot| {
+            let info = &snapshot.info;
+            info.yields
+                .iter()
+                .map(|v| v.summary())
+                .chain(
+                    info.times()
+                        .into_iter()
+                        .map(|(label, minutes)| format!("{label} {}", duration::format(minutes))),
+                )
+                .chain(info.pan.iter().map(|v| format!("Pan {}", pan::summary(v))))
+                .chain(
+                    info.classifications()
+                        .into_iter()
+                        .map(|(label, value)| format!("{label} {value}")),
+                )
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        sections.push(diff_section("Info", text_diff(&info(old), &info(new))));
+    }
+    if old.short_description != new.short_description {
+        sections.push(diff_section(
+            "Description",
+            text_diff(
+                old.short_description.as_deref().unwrap_or_default(),
+                new.short_description.as_deref().unwrap_or_default(),
+            ),
+        ));
+    }
+    if old.long_description != new.long_description {
+        sections.push(diff_section(
+            "Notes",
+            text_diff(
+                old.long_description.as_deref().unwrap_or_default(),
+                new.long_description.as_deref().unwrap_or_default(),
+            ),
+        ));
+    }
+    if (&old.source_name, &old.source_url) != (&new.source_name, &new.source_url) {
+        let source = |snapshot: &RecipeSnapshot| {
+            [&snapshot.source_name, &snapshot.source_url]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        sections.push(diff_section(
+            "Source",
+            text_diff(&source(old), &source(new)),
+        ));
+    }
+    let tag_changes = diff_list(&old.tags, &new.tags, |_, _| false);
+    if has_list_changes(&tag_changes) {
+        sections.push(diff_section("Tags", list_diff(&tag_changes, String::clone)));
+    }
+    let equipment_changes = diff_list(&old.equipment, &new.equipment, |_, _| false);
+    if has_list_changes(&equipment_changes) {
+        sections.push(diff_section(
+            "Equipment",
+            list_diff(&equipment_changes, String::clone),
+        ));
+    }
+    let ingredient_changes = diff_list(&old.ingredients, &new.ingredients, |a, b| {
+        a.name.eq_ignore_ascii_case(&b.name)
+    });
+    if has_list_changes(&ingredient_changes) {
+        sections.push(diff_section(
+            "Ingredients",
+            list_diff(&ingredient_changes, ingredient_text),
+        ));
+    }
+    // steps replaced in the same place are treated as changed
+    let step_changes = diff_list(&old.steps, &new.steps, |_, _| true);
+    if has_list_changes(&step_changes) {
+        sections.push(diff_section("Steps", list_diff(&step_changes, step_text)));
+    }
+
+    if sections.is_empty() {
+        return html! {<p class="italic">{"No changes"}</p>};
+    }
+    html! {
+        <div class="flex flex-col gap-2">
+            { for sections }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RevisionsProps {
+    pub recipe_id: AttrValue,
+    /// emitted with the recipe after a revision is restored
+    pub onrestore: Callback<Recipe>,
+}
+
+/// Every saved version of a recipe, with their changes and the option to restore them
+#[function_component(Revisions)]
+pub fn revisions(props: &RevisionsProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let is_open_state = use_state(bool::default);
+    let revisions_state = use_state(|| Option::<Vec<RecipeRevision>>::None);
+    let expanded_state = use_state(|| Option::<String>::None);
+
+    let load_revisions = {
+        let recipe_id = props.recipe_id.clone();
+        let revisions_state = revisions_state.clone();
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        Callback::from(move |_| {
+            let recipe_id = recipe_id.clone();
+            let revisions_state = revisions_state.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            revisions_state.set(None);
+            wasm_bindgen_futures::spawn_local(async move {
+                match api.get_recipe_revisions(&recipe_id).await {
+                    Ok(revisions) => revisions_state.set(Some(revisions)),
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "loading revisions"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_toggle_click = {
+        let is_open_state = is_open_state.clone();
+        let load_revisions = load_revisions.clone();
+        Callback::from(move |_: MouseEvent| {
+            // reloaded each time, as the recipe may have been edited since
+            if !*is_open_state {
+                load_revisions.emit(());
+            }
+            is_open_state.set(!*is_open_state);
+        })
+    };
+
+    let on_changes_click = {
+        let expanded_state = expanded_state.clone();
+        Callback::from(move |id: String| {
+            match expanded_state.as_ref() == Some(&id) {
+                true => expanded_state.set(None),
+                false => expanded_state.set(Some(id)),
+            };
+        })
+    };
+
+    let on_restore_click = {
+        let recipe_id = props.recipe_id.clone();
+        let on_restore = props.onrestore.clone();
+        Callback::from(move |revision_id: String| {
+            let recipe_id = recipe_id.clone();
+            let on_restore = on_restore.clone();
+            let load_revisions = load_revisions.clone();
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            wasm_bindgen_futures::spawn_local(async move {
+                match api
+                    .post_restore_recipe_revision(&recipe_id, &revision_id)
+                    .await
+                {
+                    Ok(recipe) => {
+                        on_restore.emit(recipe);
+                        load_revisions.emit(());
+                        push_toast(
+                            &toasts_ctx,
+                            Toast {
+                                message: "Restored revision".to_owned(),
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "restoring revision"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="mt-4 p-4 rounded bg-base-200">
+            <div class="flex">
+                <h2 class="text-xl font-bold mr-auto">{"Revisions"}</h2>
+                <button class="btn" onclick={on_toggle_click}>
+                    { if *is_open_state { "Hide" } else { "Show" } }
+                </button>
+            </div>
+            if *is_open_state {
+                if let Some(revisions) = &*revisions_state {
+                    <ul class="flex flex-col gap-2 mt-2">
+                        { for revisions.iter().enumerate().map(|(index, revision)| {
+                            let is_expanded = expanded_state.as_ref() == Some(&revision.id);
+                            let id = revision.id.clone();
+                            let restore_id = revision.id.clone();
+                            html!{
+                                <li key={revision.id.clone()} class="p-2 rounded bg-base-100">
+                                    <div class="flex items-center gap-2 flex-wrap">
+                                        <span class="font-bold">
+                                            {revision.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()}
+                                        </span>
+                                        <span class="opacity-70">{format!("by {}", revision.author.username)}</span>
+                                        if index == 0 {
+                                            <span class="badge badge-primary">{"Current"}</span>
+                                        }
+                                        <button
+                                            class={classes!("btn", "btn-sm", "ml-auto", is_expanded.then_some("btn-active"))}
+                                            onclick={on_changes_click.reform(move |_| id.clone())}
+                                        >{"Changes"}</button>
+                                        if index != 0 {
+                                            <button
+                                                class="btn btn-sm"
+                                                onclick={on_restore_click.reform(move |_| restore_id.clone())}
+                                            >{"Restore"}</button>
+                                        }
+                                    </div>
+                                    if is_expanded {
+                                        <div class="mt-2">
+                                            <RevisionDiff
+                                                old={revisions.get(index + 1).map(|v| v.snapshot.clone())}
+                                                new={revision.snapshot.clone()}
+                                            />
+                                        </div>
+                                    }
+                                </li>
+                            }
+                        })}
+                    </ul>
+                } else {
+                    <p class="mt-2">{"Loading..."}</p>
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,94 @@
+use gloo::events::EventListener;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    contexts::prelude::use_login,
+    core::{
+        search::{self, use_search_index},
+        smart_query,
+        storage::{self, SAVED_SEARCHES_CHANGE_EVENT},
+        types::query::RecipesFilter,
+    },
+    Route,
+};
+
+/// Saved searches of the logged in user, shown as smart collections with live counts
+#[function_component(SavedSearches)]
+pub fn saved_searches() -> Html {
+    let login_ctx = use_login().unwrap();
+    let is_search_ready = use_search_index();
+    let user_id = login_ctx
+        .login
+        .as_ref()
+        .and_then(|login| login.token.subject())
+        .unwrap_or_default();
+    let saved_searches_state = use_state(|| storage::read_saved_searches(&user_id));
+
+    {
+        let saved_searches_state = saved_searches_state.clone();
+        use_effect_with_deps(
+            move |user_id| {
+                saved_searches_state.set(storage::read_saved_searches(user_id));
+                let user_id = user_id.clone();
+                let listener = EventListener::new(
+                    &gloo::utils::window(),
+                    SAVED_SEARCHES_CHANGE_EVENT,
+                    move |_| saved_searches_state.set(storage::read_saved_searches(&user_id)),
+                );
+                move || drop(listener)
+            },
+            user_id.clone(),
+        );
+    }
+
+    let on_delete_click = {
+        let saved_searches_state = saved_searches_state.clone();
+        Callback::from(move |index: usize| {
+            let mut saved_searches = (*saved_searches_state).clone();
+            saved_searches.remove(index);
+            storage::set_saved_searches(&user_id, saved_searches);
+        })
+    };
+
+    if saved_searches_state.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="flex flex-col items-stretch gap-1 p-0 hover:bg-transparent active:bg-transparent">
+            <span class="menu-title px-0">{"Saved Searches"}</span>
+            { for saved_searches_state.iter().enumerate().map(|(index, saved_search)| {
+                let count = match is_search_ready {
+                    true => smart_query::parse(&saved_search.query)
+                        .ok()
+                        .map(|query| search::count_matching(&query)),
+                    false => None,
+                };
+                let filters = RecipesFilter {
+                    query: Some(saved_search.query.clone()),
+                    ..Default::default()
+                };
+                html!{
+                    <div class="flex gap-1">
+                        <Link<Route, RecipesFilter>
+                            to={Route::Recipes}
+                            query={Some(filters)}
+                            classes="btn bg-base-100 flex-1 justify-between flex-nowrap normal-case"
+                        >
+                            <span class="truncate">{saved_search.name.clone()}</span>
+                            if let Some(count) = count {
+                                <span class="badge">{count}</span>
+                            }
+                        </Link<Route, RecipesFilter>>
+                        <button
+                            class="btn btn-square bg-base-100"
+                            onclick={on_delete_click.reform(move |_| index)}
+                            aria-label={format!("Delete {}", saved_search.name)}
+                        >{"✕"}</button>
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
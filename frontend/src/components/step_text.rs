@@ -0,0 +1,63 @@
+use yew::prelude::*;
+
+use crate::components::markdown;
+use crate::core::ingredient_references::{self, StepTextPart};
+use crate::core::temperature::{self, TemperatureDisplay, TemperaturePart};
+use crate::core::types::recipe::Ingredient;
+
+#[derive(Properties, PartialEq)]
+pub struct StepTextProps {
+    pub text: String,
+    /// ingredients of the recipe, which the text can refer to
+    pub ingredients: Vec<Ingredient>,
+    /// what the amounts of referred ingredients are multiplied by
+    #[prop_or(1.0)]
+    pub scale: f32,
+    /// how temperatures written in the text are converted
+    #[prop_or_default]
+    pub temperatures: TemperatureDisplay,
+}
+
+/// Markdown text of a step, with references to ingredients shown as their current amount,
+/// and temperatures followed by their conversions
+#[function_component(StepText)]
+pub fn step_text(props: &StepTextProps) -> Html {
+    let in_oven = temperature::mentions_oven(&props.text);
+    markdown::render(&props.text, &|text| {
+        html! {
+            <>
+            { for ingredient_references::parse(text).into_iter().map(|part| match part {
+                StepTextPart::Text(text) => html!{
+                    { for temperature::parse(text, props.temperatures, in_oven).into_iter().map(|part| match part {
+                        TemperaturePart::Text(text) => html!{text},
+                        TemperaturePart::Conversion(conversion) => html!{
+                            <span class="opacity-70">{format!(" ({conversion})")}</span>
+                        },
+                    })}
+                },
+                StepTextPart::Reference { name, id } => {
+                    match ingredient_references::find_ingredient(&props.ingredients, id) {
+                        Some(ingredient) => {
+                            let mut tip = ingredient.name.clone();
+                            if let Some(description) = &ingredient.description {
+                                tip.push_str(&format!(", {description}"));
+                            }
+                            if let Some(section) = &ingredient.section {
+                                tip.push_str(&format!(" ({section})"));
+                            }
+                            html!{
+                                <span
+                                    class="tooltip font-semibold text-primary underline decoration-dotted print:text-inherit"
+                                    data-tip={tip}
+                                >{ingredient.scaled_summary(props.scale)}</span>
+                            }
+                        }
+                        // the ingredient was removed, without the steps being updated
+                        None => html!{name},
+                    }
+                }
+            })}
+            </>
+        }
+    })
+}
@@ -1,14 +1,31 @@
-use yew::{function_component, html, AttrValue, Children, Html, Properties};
+use gloo::events::{EventListener, EventListenerOptions};
+use yew::{
+    function_component, html, use_effect_with_deps, use_mut_ref, use_node_ref, use_state,
+    AttrValue, Callback, Html, Properties,
+};
 use yew_router::prelude::*;
 
 use crate::Route;
 
-#[derive(Properties, PartialEq)]
+/// Padding around the grid items, in pixels (tailwind "p-3")
+const GRID_PADDING: f64 = 12.0;
+/// Gap between grid items, in pixels (tailwind "gap-4")
+const GRID_GAP: f64 = 16.0;
+/// Extra rows rendered above & below the visible ones, so fast scrolling stays smooth
+const OVERSCAN_ROWS: usize = 2;
+/// Rows rendered before the grid has been measured
+const INITIAL_ROWS: usize = 8;
+
+#[derive(Properties, PartialEq, Clone)]
 pub struct GridItemProps {
     pub navigate_to: Route,
     pub title: AttrValue,
     #[prop_or_default]
     pub image_src: Option<AttrValue>,
+    #[prop_or_default]
+    pub favourite: bool,
+    #[prop_or_default]
+    pub rating: Option<u8>,
 }
 
 #[function_component(GridItem)]
@@ -16,29 +33,191 @@ pub fn grid_item(props: &GridItemProps) -> Html {
     html! {
         <Link<Route> to={props.navigate_to.clone()} classes="bg-base-100 shadow-xl aspect-square w-full relative rounded-md">
             if props.image_src.is_some() {
-                <img class="object-cover w-full h-full rounded-lg hover:brightness-50 hover:duration-200" src={props.image_src.clone()} />
+                <img
+                    class="object-cover w-full h-full rounded-lg hover:brightness-50 hover:duration-200"
+                    src={props.image_src.clone()}
+                    loading="lazy"
+                    decoding="async"
+                />
             } else {
                 <div class="w-full h-full rounded-md hover:bg-neutral-focus hover:duration-200"></div>
             }
+            if props.favourite || props.rating.is_some() {
+                <div class="absolute top-2 right-2 flex gap-1">
+                    if props.favourite {
+                        <span class="badge badge-secondary" aria-label="Favourite">{"♥"}</span>
+                    }
+                    if let Some(rating) = props.rating {
+                        <span class="badge" aria-label={format!("{rating} Stars")}>{format!("★ {rating}")}</span>
+                    }
+                </div>
+            }
             <span
                 class="absolute bottom-0 left-0 p-1 w-full bg-[#000000cc] rounded-b-md whitespace-nowrap overflow-hidden text-ellipsis text-lg font-bold text-slate-300">{props.title.clone()}</span>
         </Link<Route>>
     }
 }
 
+/// Number of columns the grid currently has, matching its responsive classes
+fn column_count() -> usize {
+    let width = gloo::utils::window()
+        .inner_width()
+        .ok()
+        .and_then(|v| v.as_f64())
+        .unwrap_or_default();
+    match width {
+        w if w >= 1024.0 => 5,
+        w if w >= 768.0 => 3,
+        _ => 1,
+    }
+}
+
+/// Rows of the grid that are currently visible (start inclusive, end exclusive),
+/// along with the height of each row including its gap
+fn visible_rows(
+    element: &web_sys::Element,
+    columns: usize,
+    total_rows: usize,
+) -> (usize, usize, f64) {
+    let rect = element.get_bounding_client_rect();
+    let viewport_height = gloo::utils::window()
+        .inner_height()
+        .ok()
+        .and_then(|v| v.as_f64())
+        .unwrap_or_default();
+    let content_width = rect.width() - (GRID_PADDING * 2.0);
+    let item_size = (content_width - GRID_GAP * (columns - 1) as f64) / columns as f64;
+    let row_height = (item_size + GRID_GAP).max(1.0);
+    // offsets of the viewport, relative to the top of the first row
+    let viewport_top = -rect.top() - GRID_PADDING;
+    let viewport_bottom = viewport_top + viewport_height;
+    let start =
+        ((viewport_top / row_height).floor().max(0.0) as usize).saturating_sub(OVERSCAN_ROWS);
+    let end =
+        ((viewport_bottom / row_height).ceil().max(0.0) as usize + OVERSCAN_ROWS).min(total_rows);
+    (start.min(end), end, row_height)
+}
+
+/// Find the closest ancestor that scrolls its content
+fn scroll_parent(element: &web_sys::Element) -> Option<web_sys::Element> {
+    let mut parent = element.parent_element();
+    while let Some(element) = parent {
+        if element.scroll_height() > element.client_height() {
+            return Some(element);
+        }
+        parent = element.parent_element();
+    }
+    None
+}
+
 #[derive(Properties, PartialEq)]
-pub struct GridProps {
+pub struct WindowedGridProps {
+    pub items: Vec<GridItemProps>,
+    /// bring the item at this index into view, once it exists
     #[prop_or_default]
-    // HACK use enum to accept iterator?
-    // pub children: ChildrenWithProps<GridItem>,
-    pub children: Children,
+    pub scroll_to_item: Option<usize>,
+    /// emitted after scrolling to the requested item
+    #[prop_or_default]
+    pub on_scrolled: Callback<()>,
 }
 
-#[function_component(Grid)]
-pub fn grid(props: &GridProps) -> Html {
+/// A grid that only renders the rows in view,
+/// keeping large libraries fast by replacing the rest with padding
+#[function_component(WindowedGrid)]
+pub fn windowed_grid(props: &WindowedGridProps) -> Html {
+    let grid_ref = use_node_ref();
+    let window_state = use_state(|| (column_count(), 0, INITIAL_ROWS, 0.0));
+    // the window last set, as the state handle captured by the listeners goes stale
+    let current_window = use_mut_ref(|| *window_state);
+
+    {
+        let grid_ref = grid_ref.clone();
+        let window_state = window_state.clone();
+        let current_window = current_window.clone();
+        use_effect_with_deps(
+            move |item_count| {
+                let item_count = *item_count;
+                let update_window = move || {
+                    if let Some(element) = grid_ref.cast::<web_sys::Element>() {
+                        let columns = column_count();
+                        let total_rows = item_count.div_ceil(columns);
+                        let (start, end, row_height) = visible_rows(&element, columns, total_rows);
+                        let new_window = (columns, start, end, row_height);
+                        let mut current_window = current_window.borrow_mut();
+                        if *current_window != new_window {
+                            *current_window = new_window;
+                            window_state.set(new_window);
+                        }
+                    }
+                };
+                update_window();
+                // scrolling may happen on the window or the drawer content, so listen in the capture phase
+                let scroll_listener = {
+                    let update_window = update_window.clone();
+                    EventListener::new_with_options(
+                        &gloo::utils::document(),
+                        "scroll",
+                        EventListenerOptions::run_in_capture_phase(),
+                        move |_| update_window(),
+                    )
+                };
+                let resize_listener =
+                    EventListener::new(&gloo::utils::window(), "resize", move |_| update_window());
+                move || {
+                    drop(scroll_listener);
+                    drop(resize_listener);
+                }
+            },
+            props.items.len(),
+        );
+    }
+
+    {
+        let grid_ref = grid_ref.clone();
+        let on_scrolled = props.on_scrolled.clone();
+        use_effect_with_deps(
+            move |(scroll_to_item, item_count)| {
+                let element = grid_ref.cast::<web_sys::Element>();
+                if let (Some(index), Some(element)) = (scroll_to_item, element) {
+                    if index < item_count {
+                        let columns = column_count();
+                        let (_, _, row_height) =
+                            visible_rows(&element, columns, item_count.div_ceil(columns));
+                        let offset = element.get_bounding_client_rect().top()
+                            + (index / columns) as f64 * row_height;
+                        match scroll_parent(&element) {
+                            Some(parent) => parent.scroll_by_with_x_and_y(0.0, offset),
+                            None => gloo::utils::window().scroll_by_with_x_and_y(0.0, offset),
+                        }
+                        on_scrolled.emit(());
+                    }
+                }
+            },
+            (props.scroll_to_item, props.items.len()),
+        );
+    }
+
+    let (columns, start_row, end_row, row_height) = *window_state;
+    let total_rows = props.items.len().div_ceil(columns);
+    let end_row = end_row.min(total_rows);
+    let start_row = start_row.min(end_row);
+    let padding_top = GRID_PADDING + start_row as f64 * row_height;
+    let padding_bottom = GRID_PADDING + (total_rows - end_row) as f64 * row_height;
+    let visible_items = props
+        .items
+        .iter()
+        .skip(start_row * columns)
+        .take((end_row - start_row) * columns);
+
     html! {
-        <div class="grid gap-4 grid-cols-1 md:grid-cols-3 lg:grid-cols-5 rounded p-3 bg-base-200">
-            { for props.children.iter() }
+        <div
+            ref={grid_ref}
+            class="grid gap-4 grid-cols-1 md:grid-cols-3 lg:grid-cols-5 rounded px-3 bg-base-200"
+            style={format!("padding-top: {padding_top}px; padding-bottom: {padding_bottom}px;")}
+        >
+            { for visible_items.map(|item| html!{
+                <GridItem key={item.navigate_to.to_path()} ..item.clone() />
+            })}
         </div>
     }
 }
@@ -0,0 +1,85 @@
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    contexts::prelude::{push_toast, use_login, use_toasts},
+    core::{
+        handlers::{api_error_to_toast, logout_on_401},
+        types::recipe::Recipe,
+    },
+    Route,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct VariationsProps {
+    #[prop_or_default]
+    pub classes: Classes,
+    pub recipe_id: AttrValue,
+    #[prop_or_default]
+    pub origin_id: Option<AttrValue>,
+}
+
+/// The recipe this one was duplicated from and the recipes duplicated from it,
+/// nothing is shown when there are neither
+#[function_component(Variations)]
+pub fn variations(props: &VariationsProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let origin_state = use_state(|| Option::<Recipe>::None);
+    let variations_state = use_state(Vec::<Recipe>::default);
+
+    {
+        let origin_state = origin_state.clone();
+        let variations_state = variations_state.clone();
+        use_effect_with_deps(
+            move |(recipe_id, origin_id)| {
+                let recipe_id = recipe_id.clone();
+                let origin_id = origin_id.clone();
+                let api = login_ctx.http_api.clone().unwrap();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.get_recipe_variations(&recipe_id).await {
+                        Ok(variations) => variations_state.set(variations),
+                        Err(e) => {
+                            push_toast(&toasts_ctx, api_error_to_toast(&e, "loading variations"));
+                            logout_on_401(&e, &login_ctx);
+                        }
+                    }
+                    // the origin may have been removed since, so just skip it on error
+                    let origin = match origin_id {
+                        Some(origin_id) => api.get_recipe_by_id(origin_id.to_string()).await.ok(),
+                        None => None,
+                    };
+                    origin_state.set(origin);
+                });
+            },
+            (props.recipe_id.clone(), props.origin_id.clone()),
+        );
+    }
+
+    if origin_state.is_none() && variations_state.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class={props.classes.clone()}>
+            if let Some(origin) = &*origin_state {
+                <p>
+                    {"Variation of "}
+                    <Link<Route> classes="link" to={Route::Recipe { id: origin.id.clone() }}>
+                        {origin.title.clone()}
+                    </Link<Route>>
+                </p>
+            }
+            if !variations_state.is_empty() {
+                <div class="flex flex-wrap items-center gap-2">
+                    <span>{"Variations:"}</span>
+                    { for variations_state.iter().map(|variation| html!{
+                        <Link<Route> classes="badge badge-outline" to={Route::Recipe { id: variation.id.clone() }}>
+                            {variation.title.clone()}
+                        </Link<Route>>
+                    })}
+                </div>
+            }
+        </div>
+    }
+}
@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use yew::{Reducible, UseReducerHandle, use_context, hook};
 
-use crate::core::{api::Api, storage, types::StoredLogin};
+use crate::core::{api::Api, search, storage, types::StoredLogin};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct CurrentLogin {
@@ -25,6 +25,8 @@ impl Reducible for CurrentLogin {
     type Action = Option<StoredLogin>;
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        // the search index belongs to the previous login
+        search::clear();
         let http_api = match &action {
             Some(v) => {
                 storage::set_login_token(v.clone());
@@ -1,8 +1,20 @@
 pub mod api;
+pub mod bakers_percentage;
 pub mod constants;
+pub mod diff;
+pub mod duration;
 pub mod effects;
 pub mod handlers;
+pub mod ingredient_references;
+pub mod pan;
+pub mod pantry;
+pub mod schema_org;
+pub mod search;
+pub mod sections;
+pub mod smart_query;
 pub mod storage;
+pub mod sub_recipes;
+pub mod temperature;
 pub mod types;
 
 pub use constants::APP_TITLE;
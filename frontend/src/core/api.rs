@@ -1,4 +1,9 @@
-use super::types::{query::RecipesFilter, recipe, stats, user, Login, LoginToken, StoredLogin};
+use super::{search, storage};
+use super::types::{
+    book, history, pantry,
+    query::{Page, RecipesFilter},
+    recipe, revision, stats, user, Login, LoginToken, StoredLogin,
+};
 use gloo::net::http::Request;
 use serde::de::DeserializeOwned;
 use std::convert::From;
@@ -50,6 +55,16 @@ impl ApiError {
         }
     }
 
+    /// Handle responses that have no content
+    pub fn check_response_ok(response: gloo::net::http::Response) -> Result<(), Self> {
+        match response.ok() {
+            false => Err(ApiError::Response(ApiResponseError {
+                status_code: response.status(),
+            })),
+            true => Ok(()),
+        }
+    }
+
     /// Handle internal errors,
     /// validating the received JSON matches given type
     pub async fn check_json_response_ok<T>(response: gloo::net::http::Response) -> Result<T, Self>
@@ -112,7 +127,10 @@ impl Api {
         ApiError::check_json_response_ok::<user::User>(response).await
     }
 
-    pub async fn get_recipes(&self, filters: &RecipesFilter) -> Result<Vec<recipe::Recipe>, ApiError> {
+    pub async fn get_recipes(
+        &self,
+        filters: &RecipesFilter,
+    ) -> Result<Page<recipe::Recipe>, ApiError> {
         let req_url = format!(
             "{}/recipes/?{}",
             self.base_url.clone(),
@@ -124,7 +142,7 @@ impl Api {
                 .send()
                 .await,
         )?;
-        ApiError::check_json_response_ok::<Vec<recipe::Recipe>>(response).await
+        ApiError::check_json_response_ok::<Page<recipe::Recipe>>(response).await
     }
 
     pub async fn get_recipe_by_id(&self, id: String) -> Result<recipe::Recipe, ApiError> {
@@ -162,7 +180,10 @@ impl Api {
                 .send()
                 .await,
         )?;
-        ApiError::check_json_response_ok::<recipe::Recipe>(response).await
+        let recipe = ApiError::check_json_response_ok::<recipe::Recipe>(response).await?;
+        search::put_recipe(&recipe);
+        storage::remove_recipes_list_cache();
+        Ok(recipe)
     }
 
     pub async fn patch_update_recipe(
@@ -171,7 +192,7 @@ impl Api {
         updated_recipe: &recipe::UpdateRecipe,
     ) -> Result<(), ApiError> {
         let req_url = format!("{}/recipes/{}/", self.base_url.clone(), id);
-        ApiError::from_response_result(
+        let response = ApiError::from_response_result(
             Request::patch(&req_url)
                 .header("Authorization", &self.get_authorization_value().unwrap())
                 .json(updated_recipe)
@@ -179,17 +200,25 @@ impl Api {
                 .send()
                 .await,
         )?;
-        Ok(())
+        if response.ok() {
+            search::update_recipe(&id, updated_recipe);
+            storage::remove_recipes_list_cache();
+        }
+        ApiError::check_response_ok(response)
     }
 
     pub async fn delete_recipe(&self, id: &str) -> Result<(), ApiError> {
         let req_url = format!("{}/recipes/{}/", self.base_url.clone(), id);
-        ApiError::from_response_result(
+        let response = ApiError::from_response_result(
             Request::delete(&req_url)
                 .header("Authorization", &self.get_authorization_value().unwrap())
                 .send()
                 .await,
         )?;
+        if response.ok() {
+            search::remove_recipe(id);
+            storage::remove_recipes_list_cache();
+        }
         Ok(())
     }
 
@@ -206,19 +235,313 @@ impl Api {
                 .send()
                 .await,
         )?;
-        ApiError::check_json_response_ok::<String>(response).await
+        let image_id = ApiError::check_json_response_ok::<String>(response).await?;
+        search::update_recipe_image(&id, Some(image_id.clone()));
+        storage::remove_recipes_list_cache();
+        Ok(image_id)
     }
 
     pub async fn delete_recipe_image(&self, id: String) -> Result<(), ApiError> {
         let req_url = format!("{}/recipes/{}/image/", self.base_url.clone(), id);
-        ApiError::from_response_result(
+        let response = ApiError::from_response_result(
             Request::delete(&req_url)
                 .header("Authorization", &self.get_authorization_value().unwrap())
                 .send()
                 .await,
         )?;
+        if response.ok() {
+            search::update_recipe_image(&id, None);
+            storage::remove_recipes_list_cache();
+        }
         Ok(())
     }
+
+    pub async fn get_books(&self) -> Result<Vec<book::Book>, ApiError> {
+        let req_url = self.base_url.clone() + "/books/";
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<Vec<book::Book>>(response).await
+    }
+
+    pub async fn get_book_by_id(&self, id: &str) -> Result<book::Book, ApiError> {
+        let req_url = format!("{}/books/{}/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<book::Book>(response).await
+    }
+
+    /// Get the recipes in a book, in the book's order
+    pub async fn get_book_recipes(&self, id: &str) -> Result<Vec<recipe::Recipe>, ApiError> {
+        let req_url = format!("{}/books/{}/recipes/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<Vec<recipe::Recipe>>(response).await
+    }
+
+    pub async fn post_new_book(&self, new_book: &book::CreateBook) -> Result<book::Book, ApiError> {
+        let req_url = self.base_url.clone() + "/books/";
+        let response = ApiError::from_response_result(
+            Request::post(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .json(new_book)
+                .unwrap()
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<book::Book>(response).await
+    }
+
+    pub async fn patch_update_book(
+        &self,
+        id: &str,
+        updated_book: &book::UpdateBook,
+    ) -> Result<(), ApiError> {
+        let req_url = format!("{}/books/{}/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::patch(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .json(updated_book)
+                .unwrap()
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
+
+    pub async fn delete_book(&self, id: &str) -> Result<(), ApiError> {
+        let req_url = format!("{}/books/{}/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::delete(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
+
+    /// Get everything in the user's pantry, sorted by name
+    pub async fn get_pantry_items(&self) -> Result<Vec<pantry::PantryItem>, ApiError> {
+        let req_url = self.base_url.clone() + "/pantry/";
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<Vec<pantry::PantryItem>>(response).await
+    }
+
+    pub async fn post_new_pantry_item(
+        &self,
+        new_item: &pantry::UpdatePantryItem,
+    ) -> Result<pantry::PantryItem, ApiError> {
+        let req_url = self.base_url.clone() + "/pantry/";
+        let response = ApiError::from_response_result(
+            Request::post(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .json(new_item)
+                .unwrap()
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<pantry::PantryItem>(response).await
+    }
+
+    pub async fn patch_update_pantry_item(
+        &self,
+        id: &str,
+        updated_item: &pantry::UpdatePantryItem,
+    ) -> Result<(), ApiError> {
+        let req_url = format!("{}/pantry/{}/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::patch(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .json(updated_item)
+                .unwrap()
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
+
+    pub async fn delete_pantry_item(&self, id: &str) -> Result<(), ApiError> {
+        let req_url = format!("{}/pantry/{}/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::delete(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
+
+    /// Get every revision of a recipe, most recent first
+    pub async fn get_recipe_revisions(
+        &self,
+        id: &str,
+    ) -> Result<Vec<revision::RecipeRevision>, ApiError> {
+        let req_url = format!("{}/recipes/{}/revisions/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<Vec<revision::RecipeRevision>>(response).await
+    }
+
+    /// Replace the content of a recipe with a revision, returning the restored recipe
+    pub async fn post_restore_recipe_revision(
+        &self,
+        id: &str,
+        revision_id: &str,
+    ) -> Result<recipe::Recipe, ApiError> {
+        let req_url = format!(
+            "{}/recipes/{}/revisions/{}/restore/",
+            self.base_url.clone(),
+            id,
+            revision_id
+        );
+        let response = ApiError::from_response_result(
+            Request::post(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        let recipe = ApiError::check_json_response_ok::<recipe::Recipe>(response).await?;
+        search::put_recipe(&recipe);
+        storage::remove_recipes_list_cache();
+        Ok(recipe)
+    }
+
+    /// Get the variations made from a recipe
+    pub async fn get_recipe_variations(&self, id: &str) -> Result<Vec<recipe::Recipe>, ApiError> {
+        let req_url = format!("{}/recipes/{}/variations/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<Vec<recipe::Recipe>>(response).await
+    }
+
+    /// Get the cooking history of a recipe, most recent first
+    pub async fn get_recipe_history(&self, id: &str) -> Result<Vec<history::CookLog>, ApiError> {
+        let req_url = format!("{}/recipes/{}/history/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::get(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<Vec<history::CookLog>>(response).await
+    }
+
+    pub async fn post_recipe_history(
+        &self,
+        id: &str,
+        cook_log: &history::CreateCookLog,
+    ) -> Result<history::CookLog, ApiError> {
+        let req_url = format!("{}/recipes/{}/history/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::post(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .json(cook_log)
+                .unwrap()
+                .send()
+                .await,
+        )?;
+        let cook_log = ApiError::check_json_response_ok::<history::CookLog>(response).await?;
+        // the list shows when recipes were last cooked
+        storage::remove_recipes_list_cache();
+        Ok(cook_log)
+    }
+
+    pub async fn delete_recipe_history(&self, id: &str, log_id: &str) -> Result<(), ApiError> {
+        let req_url = format!(
+            "{}/recipes/{}/history/{}/",
+            self.base_url.clone(),
+            id,
+            log_id
+        );
+        let response = ApiError::from_response_result(
+            Request::delete(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)?;
+        storage::remove_recipes_list_cache();
+        Ok(())
+    }
+
+    /// Add a recipe to the end of a book, does nothing if it is already in the book
+    pub async fn post_add_book_recipe(&self, id: &str, recipe_id: &str) -> Result<(), ApiError> {
+        let req_url = format!("{}/books/{}/recipes/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::post(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .json(&book::AddBookRecipe {
+                    recipe_id: recipe_id.to_owned(),
+                })
+                .unwrap()
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
+
+    pub async fn delete_book_recipe(&self, id: &str, recipe_id: &str) -> Result<(), ApiError> {
+        let req_url = format!(
+            "{}/books/{}/recipes/{}/",
+            self.base_url.clone(),
+            id,
+            recipe_id
+        );
+        let response = ApiError::from_response_result(
+            Request::delete(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
+
+    pub async fn post_book_image(&self, id: &str, file: web_sys::File) -> Result<String, ApiError> {
+        let req_url = format!("{}/books/{}/image/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::post(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .body(file)
+                .send()
+                .await,
+        )?;
+        ApiError::check_json_response_ok::<String>(response).await
+    }
+
+    pub async fn delete_book_image(&self, id: &str) -> Result<(), ApiError> {
+        let req_url = format!("{}/books/{}/image/", self.base_url.clone(), id);
+        let response = ApiError::from_response_result(
+            Request::delete(&req_url)
+                .header("Authorization", &self.get_authorization_value().unwrap())
+                .send()
+                .await,
+        )?;
+        ApiError::check_response_ok(response)
+    }
 }
 
 impl From<StoredLogin> for Api {
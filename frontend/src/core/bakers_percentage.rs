@@ -0,0 +1,61 @@
+//! Baker's percentages, giving each ingredient's weight as a percentage of the flour's.
+//!
+//! Only ingredients measured by weight can be compared, so those measured by volume
+//! or as a count are left out. The flour is chosen by the cook, being one or more ingredients
+//! that together make 100%, starting as those named flour.
+use super::types::recipe::Ingredient;
+
+/// Grams in a unit of weight, such as "kg" or "oz"
+fn unit_grams(unit: &str) -> Option<f32> {
+    match unit.trim().trim_end_matches('.').to_lowercase().as_str() {
+        "mg" | "milligram" | "milligrams" => Some(0.001),
+        "g" | "gr" | "gram" | "grams" => Some(1.0),
+        "kg" | "kilo" | "kilos" | "kilogram" | "kilograms" => Some(1000.0),
+        "oz" | "ounce" | "ounces" => Some(28.349_523),
+        "lb" | "lbs" | "pound" | "pounds" => Some(453.592_37),
+        _ => None,
+    }
+}
+
+/// The weight of an ingredient in grams, as the least and most of a range,
+/// none when it is not measured by weight
+pub fn grams(ingredient: &Ingredient) -> Option<(f32, f32)> {
+    let unit = unit_grams(&ingredient.unit_type)?;
+    let amount = ingredient.amount?;
+    let max = ingredient.max_amount().unwrap_or(amount);
+    Some((amount * unit, max * unit))
+}
+
+/// Indexes of the ingredients named flour, which start off as the 100%
+pub fn default_flour(ingredients: &[Ingredient]) -> Vec<usize> {
+    ingredients
+        .iter()
+        .enumerate()
+        .filter(|(_, ingredient)| {
+            ingredient.name.to_lowercase().contains("flour") && grams(ingredient).is_some()
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The weight of the flour in grams, using the least of any range,
+/// none when none of it is measured by weight
+pub fn flour_grams(ingredients: &[Ingredient], flour: &[usize]) -> Option<f32> {
+    let total: f32 = flour
+        .iter()
+        .filter_map(|i| ingredients.get(*i))
+        .filter_map(grams)
+        .map(|(min, _)| min)
+        .sum();
+    (total > 0.0).then_some(total)
+}
+
+/// An ingredient's weight as a percentage of the flour's, such as "65%" or "60–65%"
+pub fn percentage(ingredient: &Ingredient, flour_grams: f32) -> Option<String> {
+    let (min, max) = grams(ingredient)?;
+    let percent = |grams: f32| (grams / flour_grams * 1000.0).round() / 10.0;
+    Some(match max > min {
+        true => format!("{}–{}%", percent(min), percent(max)),
+        false => format!("{}%", percent(min)),
+    })
+}
@@ -0,0 +1,133 @@
+//! Structured differences between two versions of recipe content.
+//!
+//! Both text and lists are compared using their longest common subsequence,
+//! changed list items are found by pairing removed & added items that represent the same thing.
+
+/// Largest comparison table allowed, above this everything is treated as replaced
+const MAX_TABLE_SIZE: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Operations turning `old` into `new`, in order
+fn lcs_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Op> {
+    let (n, m) = (old.len(), new.len());
+    if (n + 1) * (m + 1) > MAX_TABLE_SIZE {
+        return (0..n)
+            .map(Op::Delete)
+            .chain((0..m).map(Op::Insert))
+            .collect();
+    }
+    // lengths[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0_u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            ops.push(Op::Delete(i));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(j));
+            j += 1;
+        }
+    }
+    ops.extend((i..n).map(Op::Delete));
+    ops.extend((j..m).map(Op::Insert));
+    ops
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextChange {
+    Equal(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Compare text word by word, keeping whitespace so the text can be shown as-is
+pub fn diff_text(old: &str, new: &str) -> Vec<TextChange> {
+    let old_words: Vec<&str> = old.split_inclusive(char::is_whitespace).collect();
+    let new_words: Vec<&str> = new.split_inclusive(char::is_whitespace).collect();
+    let mut changes: Vec<TextChange> = Vec::new();
+    for op in lcs_ops(&old_words, &new_words) {
+        // merge runs of the same kind, so they are shown together
+        match (changes.last_mut(), op) {
+            (Some(TextChange::Equal(v)), Op::Equal(i, _))
+            | (Some(TextChange::Removed(v)), Op::Delete(i)) => v.push_str(old_words[i]),
+            (Some(TextChange::Added(v)), Op::Insert(j)) => v.push_str(new_words[j]),
+            (_, Op::Equal(i, _)) => changes.push(TextChange::Equal(old_words[i].to_owned())),
+            (_, Op::Delete(i)) => changes.push(TextChange::Removed(old_words[i].to_owned())),
+            (_, Op::Insert(j)) => changes.push(TextChange::Added(new_words[j].to_owned())),
+        }
+    }
+    changes
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListChange<'a, T> {
+    Unchanged(&'a T),
+    Removed(&'a T),
+    Added(&'a T),
+    Changed(&'a T, &'a T),
+}
+
+/// Compare two lists, pairing removed & added items accepted by `is_same_item` as changed
+pub fn diff_list<'a, T: PartialEq>(
+    old: &'a [T],
+    new: &'a [T],
+    is_same_item: impl Fn(&T, &T) -> bool,
+) -> Vec<ListChange<'a, T>> {
+    let mut changes = Vec::new();
+    let mut removed: Vec<&T> = Vec::new();
+    let mut added: Vec<&T> = Vec::new();
+    let flush = |removed: &mut Vec<&'a T>,
+                 added: &mut Vec<&'a T>,
+                 changes: &mut Vec<ListChange<'a, T>>| {
+        let mut paired = Vec::new();
+        for new_item in added.drain(..) {
+            match removed
+                .iter()
+                .position(|old_item| is_same_item(old_item, new_item))
+            {
+                Some(index) => paired.push(ListChange::Changed(removed.remove(index), new_item)),
+                None => paired.push(ListChange::Added(new_item)),
+            }
+        }
+        changes.extend(removed.drain(..).map(ListChange::Removed));
+        changes.extend(paired);
+    };
+    for op in lcs_ops(old, new) {
+        match op {
+            Op::Equal(i, _) => {
+                flush(&mut removed, &mut added, &mut changes);
+                changes.push(ListChange::Unchanged(&old[i]));
+            }
+            Op::Delete(i) => removed.push(&old[i]),
+            Op::Insert(j) => added.push(&new[j]),
+        }
+    }
+    flush(&mut removed, &mut added, &mut changes);
+    changes
+}
+
+/// Whether any of the changes are not unchanged
+pub fn has_list_changes<T>(changes: &[ListChange<T>]) -> bool {
+    changes
+        .iter()
+        .any(|change| !matches!(change, ListChange::Unchanged(_)))
+}
@@ -0,0 +1,117 @@
+//! Durations of recipes, such as how long they take to prepare, stored as whole minutes.
+//!
+//! They can be written how people tend to, such as "1h 30m", "90 min" or "1:30",
+//! or as ISO 8601 durations such as "PT1H30M", which is how schema.org recipes give them.
+
+const MINUTES_PER_HOUR: f64 = 60.0;
+const MINUTES_PER_DAY: f64 = 24.0 * MINUTES_PER_HOUR;
+
+/// Minutes in a unit, such as "h" or "mins"
+fn unit_minutes(unit: &str) -> Option<f64> {
+    match unit.to_lowercase().as_str() {
+        "d" | "day" | "days" => Some(MINUTES_PER_DAY),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(MINUTES_PER_HOUR),
+        "" | "m" | "min" | "mins" | "minute" | "minutes" => Some(1.0),
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1.0 / 60.0),
+        _ => None,
+    }
+}
+
+fn to_minutes(minutes: f64) -> Option<u32> {
+    (minutes.is_finite() && minutes > 0.0).then(|| minutes.round() as u32)
+}
+
+/// Parse an ISO 8601 duration, such as "PT1H30M" or "P1DT2H", into minutes
+pub fn from_iso8601(input: &str) -> Option<u32> {
+    let input = input.trim().to_uppercase();
+    let rest = input.strip_prefix('P')?;
+    let mut minutes = 0.0;
+    let mut is_time = false;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            'T' if !is_time && number.is_empty() => is_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            _ => {
+                let value: f64 = number.parse().ok()?;
+                number.clear();
+                minutes += value
+                    * match (c, is_time) {
+                        ('W', false) => 7.0 * MINUTES_PER_DAY,
+                        ('D', false) => MINUTES_PER_DAY,
+                        ('H', true) => MINUTES_PER_HOUR,
+                        ('M', true) => 1.0,
+                        ('S', true) => 1.0 / 60.0,
+                        // months & years vary in length, so are not a sensible recipe time
+                        _ => return None,
+                    };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    to_minutes(minutes)
+}
+
+/// Format minutes as an ISO 8601 duration, such as "PT1H30M"
+pub fn to_iso8601(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("PT{minutes}M"),
+        (hours, 0) => format!("PT{hours}H"),
+        (hours, minutes) => format!("PT{hours}H{minutes}M"),
+    }
+}
+
+/// Parse a duration into minutes, such as "1h 30m", "1.5 hours", "90 min", "90", "1:30" or "PT1H30M"
+pub fn parse(input: &str) -> Option<u32> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if let Some(minutes) = from_iso8601(input) {
+        return Some(minutes);
+    }
+    // hours and minutes, as on a clock
+    if let Some((hours, minutes)) = input.split_once(':') {
+        let hours: u32 = hours.trim().parse().ok()?;
+        let minutes: u32 = minutes.trim().parse().ok()?;
+        return (minutes < 60)
+            .then_some(hours * 60 + minutes)
+            .filter(|v| *v != 0);
+    }
+    // numbers each followed by an optional unit, no unit meaning minutes
+    let mut minutes = 0.0;
+    let mut chars = input.chars().peekable();
+    while chars.peek().is_some() {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut number = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.' || *c == ',') {
+            number.push(if c == ',' { '.' } else { c });
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut unit = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_alphabetic()) {
+            unit.push(c);
+        }
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        if number.is_empty() {
+            // allows "1 hour and 30 minutes"
+            if unit.eq_ignore_ascii_case("and") {
+                continue;
+            }
+            return None;
+        }
+        minutes += number.parse::<f64>().ok()? * unit_minutes(&unit)?;
+    }
+    to_minutes(minutes)
+}
+
+/// Format minutes to be read, such as "1 h 30 min"
+pub fn format(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes} min"),
+        (hours, 0) => format!("{hours} h"),
+        (hours, minutes) => format!("{hours} h {minutes} min"),
+    }
+}
@@ -1,4 +1,7 @@
-use yew::{hook, use_effect_with_deps};
+use js_sys::Array;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+use yew::{hook, use_effect_with_deps, Callback, NodeRef};
 use yew_router::prelude::use_navigator;
 
 use crate::{contexts::prelude::use_login, Route};
@@ -24,3 +27,41 @@ pub fn use_login_redirect_effect(require_state: LoginState, redirect_to: Route)
         login_ctx,
     );
 }
+
+/// emit when the referenced element scrolls into (or near to) view,
+/// re-checked whenever the callback changes
+#[hook]
+pub fn use_visible_effect(node_ref: NodeRef, on_visible: Callback<()>) {
+    use_effect_with_deps(
+        move |(node_ref, on_visible)| {
+            let observer = node_ref.cast::<web_sys::Element>().and_then(|element| {
+                let on_visible = on_visible.clone();
+                let on_intersect = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+                    let is_visible = entries.iter().any(|entry| {
+                        entry
+                            .unchecked_into::<IntersectionObserverEntry>()
+                            .is_intersecting()
+                    });
+                    if is_visible {
+                        on_visible.emit(());
+                    }
+                });
+                let options = IntersectionObserverInit::new();
+                options.set_root_margin("200px");
+                let observer = IntersectionObserver::new_with_options(
+                    on_intersect.as_ref().unchecked_ref(),
+                    &options,
+                )
+                .ok()?;
+                observer.observe(&element);
+                Some((observer, on_intersect))
+            });
+            move || {
+                if let Some((observer, _on_intersect)) = observer {
+                    observer.disconnect();
+                }
+            }
+        },
+        (node_ref, on_visible),
+    );
+}
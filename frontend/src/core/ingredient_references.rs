@@ -0,0 +1,111 @@
+//! References to ingredients written in the text of steps, shown as the ingredient's current amount.
+//!
+//! A reference is written as `{{name|id}}`, such as `{{flour|1a2b3c4d}}`.
+//! The id is what it refers to, so renaming the ingredient keeps it working,
+//! the name is only there so the text can still be read when editing it.
+use super::types::recipe::Ingredient;
+
+const START: &str = "{{";
+const END: &str = "}}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepTextPart<'a> {
+    Text(&'a str),
+    Reference { name: &'a str, id: &'a str },
+}
+
+/// Split step text into plain text and references
+pub fn parse(text: &str) -> Vec<StepTextPart<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    let mut text_start = 0;
+    while let Some(start) = rest[text_start..].find(START).map(|v| v + text_start) {
+        let inner_start = start + START.len();
+        let reference = rest[inner_start..].find(END).and_then(|end| {
+            let (name, id) = rest[inner_start..inner_start + end].rsplit_once('|')?;
+            let is_id = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric());
+            is_id.then_some((name, id, inner_start + end + END.len()))
+        });
+        match reference {
+            Some((name, id, end)) => {
+                if start != 0 {
+                    parts.push(StepTextPart::Text(&rest[..start]));
+                }
+                parts.push(StepTextPart::Reference { name, id });
+                rest = &rest[end..];
+                text_start = 0;
+            }
+            // not a reference, so carry on looking after it
+            None => text_start = inner_start,
+        }
+    }
+    if !rest.is_empty() {
+        parts.push(StepTextPart::Text(rest));
+    }
+    parts
+}
+
+/// The text to insert to refer to an ingredient, it must have an id
+pub fn reference(ingredient: &Ingredient) -> Option<String> {
+    let id = ingredient.id.as_ref()?;
+    // keep the name from ending the reference early
+    let name: String = ingredient
+        .name
+        .chars()
+        .filter(|c| !matches!(c, '|' | '{' | '}'))
+        .collect();
+    Some(format!("{START}{name}|{id}{END}"))
+}
+
+/// Ids of the ingredients referred to in the text
+pub fn referenced_ids(text: &str) -> Vec<&str> {
+    parse(text)
+        .into_iter()
+        .filter_map(|part| match part {
+            StepTextPart::Reference { id, .. } => Some(id),
+            StepTextPart::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Update the names in references to the current ones,
+/// references to ingredients no longer in the recipe become their name
+pub fn update_references(text: &str, ingredients: &[Ingredient]) -> String {
+    parse(text)
+        .into_iter()
+        .map(|part| match part {
+            StepTextPart::Text(text) => text.to_owned(),
+            StepTextPart::Reference { name, id } => find_ingredient(ingredients, id)
+                .and_then(reference)
+                .unwrap_or_else(|| name.to_owned()),
+        })
+        .collect()
+}
+
+/// Text with references replaced by the amounts of their ingredients, for showing it as plain text
+pub fn to_plain_text(text: &str, ingredients: &[Ingredient]) -> String {
+    parse(text)
+        .into_iter()
+        .map(|part| match part {
+            StepTextPart::Text(text) => text.to_owned(),
+            StepTextPart::Reference { name, id } => find_ingredient(ingredients, id)
+                .map(Ingredient::summary)
+                .unwrap_or_else(|| name.to_owned()),
+        })
+        .collect()
+}
+
+/// Text with references replaced by their names, for showing it without the ingredients
+pub fn strip_references(text: &str) -> String {
+    parse(text)
+        .into_iter()
+        .map(|part| match part {
+            StepTextPart::Text(text) => text,
+            StepTextPart::Reference { name, .. } => name,
+        })
+        .collect()
+}
+
+pub fn find_ingredient<'a>(ingredients: &'a [Ingredient], id: &str) -> Option<&'a Ingredient> {
+    ingredients.iter().find(|v| v.id.as_deref() == Some(id))
+}
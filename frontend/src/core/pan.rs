@@ -0,0 +1,72 @@
+//! Converting a recipe between the pans it can be made in, such as a 23 cm round tin
+//! rather than a 20 × 30 cm tray.
+//!
+//! Flat pans hold the same depth of mixture, so amounts scale with the area of the base.
+//! Loaf & bundt pans are filled to their depth, so amounts scale with their volume instead.
+use std::f32::consts::PI;
+
+use super::types::recipe::{Pan, PanShape};
+
+/// The tube of a bundt pan, as a part of its diameter
+const BUNDT_TUBE_RATIO: f32 = 0.25;
+
+/// Why two pans cannot be compared
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConversionError {
+    /// a pan has a width, length or depth of zero
+    MissingSize,
+    /// a loaf or bundt pan is involved, but a pan has no depth to compare volumes with
+    MissingDepth,
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingSize => write!(f, "Give both pans their size"),
+            Self::MissingDepth => write!(f, "Give both pans a depth to compare loaf & bundt pans"),
+        }
+    }
+}
+
+/// The area of the base of a pan in cm², none when it is missing a size
+pub fn area(pan: &Pan) -> Option<f32> {
+    let width = Some(pan.width).filter(|v| *v > 0.0)?;
+    let area = match pan.shape {
+        PanShape::Round => PI * (width / 2.0).powi(2),
+        PanShape::Bundt => PI * (1.0 - BUNDT_TUBE_RATIO.powi(2)) * (width / 2.0).powi(2),
+        PanShape::Square => width * width,
+        PanShape::Rectangle | PanShape::Loaf => width * pan.length.filter(|v| *v > 0.0)?,
+    };
+    Some(area)
+}
+
+/// The volume of a pan in cm³, none when it is missing a size or depth
+pub fn volume(pan: &Pan) -> Option<f32> {
+    Some(area(pan)? * pan.depth.filter(|v| *v > 0.0)?)
+}
+
+/// How much to multiply amounts by, to make a recipe for one pan in another
+pub fn scale(from: &Pan, to: &Pan) -> Result<f32, ConversionError> {
+    let size = |pan: &Pan| area(pan).ok_or(ConversionError::MissingSize);
+    let (from_size, to_size) = (size(from)?, size(to)?);
+    if !from.shape.has_depth() && !to.shape.has_depth() {
+        return Ok(to_size / from_size);
+    }
+    let volume = |pan: &Pan| volume(pan).ok_or(ConversionError::MissingDepth);
+    Ok(volume(to)? / volume(from)?)
+}
+
+/// A pan as it is described, such as "23 cm round" or "20 × 30 cm rectangle, 5 cm deep"
+pub fn summary(pan: &Pan) -> String {
+    let size = |v: f32| v.to_string();
+    let mut summary = match (pan.shape.has_length(), pan.length) {
+        (true, Some(length)) => format!("{} × {} cm", size(pan.width), size(length)),
+        _ => format!("{} cm", size(pan.width)),
+    };
+    summary.push(' ');
+    summary.push_str(&pan.shape.label().to_lowercase());
+    if let Some(depth) = pan.depth {
+        summary.push_str(&format!(", {} cm deep", size(depth)));
+    }
+    summary
+}
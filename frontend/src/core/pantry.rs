@@ -0,0 +1,244 @@
+//! Matching the ingredients of recipes to what is in the user's pantry,
+//! to find what can be cooked with what they have.
+//!
+//! Names are matched loosely, ignoring plurals, small typos and descriptions such as "chopped",
+//! with ingredients known by several names (e.g. "scallion" and "spring onion") treated the same.
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use chrono::Utc;
+
+use super::{search::index::IndexedRecipe, types::pantry::PantryItem};
+
+/// Names of the same ingredient, the first being the one the others are changed to
+const SYNONYMS: [&[&str]; 24] = [
+    &["spring onion", "scallion", "green onion", "salad onion"],
+    &["coriander", "cilantro"],
+    &["courgette", "zucchini"],
+    &["aubergine", "eggplant"],
+    &["rocket", "arugula"],
+    &["chickpea", "garbanzo bean", "garbanzo"],
+    &["icing sugar", "powdered sugar", "confectioners sugar"],
+    &["caster sugar", "superfine sugar"],
+    &["plain flour", "all purpose flour"],
+    &["self raising flour", "self rising flour"],
+    &["double cream", "heavy cream"],
+    &["single cream", "light cream"],
+    &["bicarbonate of soda", "baking soda", "bicarb"],
+    &["cornflour", "cornstarch", "corn starch"],
+    &["beef mince", "minced beef", "ground beef"],
+    &["prawn", "shrimp"],
+    &["bell pepper", "capsicum", "sweet pepper"],
+    &["beetroot", "beet"],
+    &["swede", "rutabaga"],
+    &["mangetout", "snow pea"],
+    &["chilli", "chili", "chile"],
+    &["yoghurt", "yogurt"],
+    &["stock", "broth"],
+    &["spring greens", "collard greens"],
+];
+
+/// Words which describe an ingredient, rather than being what it is.
+/// Colours and "ground" are kept, as "red pepper" is not "black pepper"
+/// and "ground ginger" is not fresh ginger.
+const DESCRIPTIONS: [&str; 37] = [
+    "a", "and", "of", "the", "fresh", "freshly", "dried", "chopped", "diced", "sliced", "minced",
+    "grated", "crushed", "large", "small", "medium", "ripe", "whole", "finely", "roughly",
+    "thinly", "peeled", "unsalted", "salted", "organic", "extra", "virgin", "tinned", "canned",
+    "frozen", "raw", "boneless", "skinless", "leaf", "sprig", "clove", "bunch",
+];
+
+/// Words of common ingredients, which are never taken as a typo of another,
+/// as many differ by only a letter (e.g. "butter" and "batter")
+const KNOWN_WORDS: [&str; 77] = [
+    "almond", "apple", "bacon", "basil", "batter", "black", "bread", "brown", "butter", "cabbage",
+    "caper", "carrot", "caster", "celery", "cheese", "cherry", "chicken", "chilli", "chive",
+    "cider", "cinnamon", "cocoa", "coconut", "cream", "cumin", "currant", "custard", "double",
+    "flour", "garlic", "ginger", "grape", "green", "ground", "honey", "icing", "lemon", "lentil",
+    "mango", "melon", "mince", "mustard", "noodle", "olive", "onion", "orange", "paper", "pasta",
+    "paste", "pastry", "peach", "pecan", "pepper", "plain", "potato", "raisin", "salmon", "sauce",
+    "sesame", "shallot", "single", "spinach", "stock", "sugar", "sultana", "syrup", "thyme",
+    "tomato", "vanilla", "vinegar", "walnut", "water", "white", "wine", "yeast", "yellow",
+    "yoghurt",
+];
+
+/// Ingredients every kitchen has, so are never missing
+const ALWAYS_ON_HAND: [&str; 1] = ["water"];
+
+/// Most ingredients a recipe can be missing to be shown as nearly ready to cook
+const NEAR_COMPLETE_MISSING: usize = 2;
+
+/// "tomatoes" -> "tomato", "berries" -> "berry", "leaves" -> "leaf"
+fn singular(word: &str) -> String {
+    if word.chars().count() <= 3 || word.ends_with("ss") || word.ends_with("us") {
+        return word.to_owned();
+    }
+    // unlike "olives" and "cloves"
+    if ["leaves", "loaves", "halves"].contains(&word) {
+        format!("{}f", &word[..word.len() - 3])
+    } else if let Some(root) = word.strip_suffix("ies") {
+        format!("{root}y")
+    } else if let Some(root) = word.strip_suffix("oes") {
+        format!("{root}o")
+    } else if let Some(root) = word.strip_suffix('s') {
+        root.to_owned()
+    } else {
+        word.to_owned()
+    }
+}
+
+/// Lowercase singular words of a name
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| singular(&word.to_lowercase()))
+        .collect()
+}
+
+/// The words an ingredient name is matched by,
+/// with synonyms changed to a single name and descriptions removed
+fn canonical(name: &str) -> Vec<String> {
+    // padded, so only whole words are replaced
+    let mut phrase = format!(" {} ", words(name).join(" "));
+    for names in SYNONYMS {
+        let replacement = format!(" {} ", words(names[0]).join(" "));
+        for synonym in &names[1..] {
+            phrase = phrase.replace(&format!(" {} ", words(synonym).join(" ")), &replacement);
+        }
+    }
+    let words: Vec<String> = phrase
+        .split_whitespace()
+        .filter(|word| !word.chars().all(|c| c.is_numeric()))
+        .map(str::to_owned)
+        .collect();
+    let described: Vec<String> = words
+        .iter()
+        .filter(|word| !DESCRIPTIONS.contains(&word.as_str()))
+        .cloned()
+        .collect();
+    // a name can be only a description, such as "cloves"
+    if described.is_empty() {
+        words
+    } else {
+        described
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Whether words are the same, allowing for a small typo in longer words,
+/// though two different known words are always different ingredients
+fn is_similar(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    if KNOWN_WORDS.contains(&a) && KNOWN_WORDS.contains(&b) {
+        return false;
+    }
+    let length = a.chars().count().max(b.chars().count());
+    let allowed = match length {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    };
+    allowed != 0 && edit_distance(a, b) <= allowed
+}
+
+/// Whether a pantry item is the ingredient,
+/// an item with more detail covers it, such as "plain flour" for "flour"
+fn is_match(ingredient: &[String], item: &[String]) -> bool {
+    !ingredient.is_empty()
+        && ingredient
+            .iter()
+            .all(|word| item.iter().any(|v| is_similar(word, v)))
+}
+
+/// How much of a recipe can be cooked from the pantry
+#[derive(Debug, Clone, PartialEq)]
+pub struct PantryMatch {
+    pub id: String,
+    pub title: String,
+    pub image_id: Option<String>,
+    /// number of different ingredients needed, not counting optional ones
+    pub needed: usize,
+    /// names of the needed ingredients which are not in the pantry
+    pub missing: Vec<String>,
+}
+
+impl PantryMatch {
+    /// Number of needed ingredients which are in the pantry
+    pub fn on_hand(&self) -> usize {
+        self.needed - self.missing.len()
+    }
+
+    fn fraction(&self) -> f32 {
+        self.on_hand() as f32 / self.needed as f32
+    }
+
+    /// Whether only a few ingredients are missing, with most of them on hand
+    pub fn is_near_complete(&self) -> bool {
+        self.missing.len() <= NEAR_COMPLETE_MISSING && self.on_hand() > self.missing.len()
+    }
+}
+
+/// Every recipe using something in the pantry, ranked by how much of it is on hand.
+/// Expired items are not counted as being on hand.
+pub fn rank<'a>(
+    recipes: impl Iterator<Item = &'a IndexedRecipe>,
+    items: &[PantryItem],
+) -> Vec<PantryMatch> {
+    let now = Utc::now();
+    let on_hand: Vec<Vec<String>> = items
+        .iter()
+        .filter(|item| !item.is_expired(now))
+        .map(|item| canonical(&item.name))
+        .chain(ALWAYS_ON_HAND.iter().map(|name| canonical(name)))
+        .collect();
+    let mut matches: Vec<PantryMatch> = recipes
+        .filter_map(|recipe| {
+            // the same ingredient can be used in several sections
+            let mut seen = HashSet::new();
+            let needed: Vec<&String> = recipe
+                .ingredients
+                .iter()
+                .filter(|name| !recipe.optional_ingredients.contains(name))
+                .filter(|name| seen.insert(canonical(name)))
+                .collect();
+            let missing: Vec<String> = needed
+                .iter()
+                .filter(|name| {
+                    let ingredient = canonical(name);
+                    !on_hand.iter().any(|item| is_match(&ingredient, item))
+                })
+                .map(|name| (*name).clone())
+                .collect();
+            (missing.len() < needed.len()).then(|| PantryMatch {
+                id: recipe.id.clone(),
+                title: recipe.title.clone(),
+                image_id: recipe.image_id.clone(),
+                needed: needed.len(),
+                missing,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.fraction()
+            .partial_cmp(&a.fraction())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.missing.len().cmp(&b.missing.len()))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    matches
+}
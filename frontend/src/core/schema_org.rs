@@ -0,0 +1,84 @@
+//! Recipes as schema.org structured data, which other recipe apps and search engines understand.
+use serde_json::{json, Map, Value};
+
+use super::{
+    duration, ingredient_references,
+    temperature::{self, TemperatureDisplay},
+    types::recipe::Recipe,
+};
+
+/// A recipe as a schema.org `Recipe`, to be given as JSON-LD,
+/// with temperatures in the steps converted as they are shown
+pub fn recipe_json_ld(recipe: &Recipe, temperatures: TemperatureDisplay) -> Value {
+    let mut value = Map::new();
+    value.insert("@context".to_owned(), json!("https://schema.org"));
+    value.insert("@type".to_owned(), json!("Recipe"));
+    value.insert("name".to_owned(), json!(recipe.title));
+    if let Some(description) = &recipe.short_description {
+        value.insert("description".to_owned(), json!(description));
+    }
+    if !recipe.tags.is_empty() {
+        value.insert("keywords".to_owned(), json!(recipe.tags.join(", ")));
+    }
+    if let Some(cuisine) = &recipe.info.cuisine {
+        value.insert("recipeCuisine".to_owned(), json!(cuisine));
+    }
+    if let Some(course) = &recipe.info.course {
+        value.insert("recipeCategory".to_owned(), json!(course));
+    }
+    if let Some(url) = &recipe.source_url {
+        value.insert("isBasedOn".to_owned(), json!(url));
+    }
+    if !recipe.equipment.is_empty() {
+        value.insert(
+            "tool".to_owned(),
+            recipe
+                .equipment
+                .iter()
+                .map(|name| json!({"@type": "HowToTool", "name": name}))
+                .collect(),
+        );
+    }
+    if let Some(yields) = &recipe.info.yields {
+        value.insert("recipeYield".to_owned(), json!(yields.summary()));
+    }
+    let times = [
+        ("prepTime", recipe.info.prep_time),
+        ("cookTime", recipe.info.cook_time),
+        ("totalTime", recipe.info.total_time()),
+    ];
+    for (key, minutes) in times {
+        if let Some(minutes) = minutes {
+            value.insert(key.to_owned(), json!(duration::to_iso8601(minutes)));
+        }
+    }
+    value.insert(
+        "recipeIngredient".to_owned(),
+        recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| json!(ingredient.summary()))
+            .collect(),
+    );
+    value.insert(
+        "recipeInstructions".to_owned(),
+        recipe
+            .steps
+            .iter()
+            .map(|step| {
+                let text =
+                    ingredient_references::to_plain_text(&step.description, &recipe.ingredients);
+                let in_oven = temperature::mentions_oven(&text);
+                let mut value = json!({
+                    "@type": "HowToStep",
+                    "text": temperature::annotate(&text, temperatures, in_oven),
+                });
+                if let Some(title) = &step.title {
+                    value["name"] = json!(title);
+                }
+                value
+            })
+            .collect(),
+    );
+    Value::Object(value)
+}
@@ -0,0 +1,291 @@
+//! Client-side full-text search over the user's recipe library.
+//!
+//! The index is held in memory and persisted to IndexedDB,
+//! it is kept up to date by [`Api`] as recipes are created, updated and deleted.
+pub mod index;
+mod store;
+
+use std::cell::{Cell, RefCell};
+
+use yew::{hook, use_effect_with_deps, use_force_update, Callback};
+
+use self::index::{IndexedRecipe, SearchIndex, SearchResult};
+use super::{
+    api::{Api, ApiError},
+    pantry::{self, PantryMatch},
+    smart_query::Query,
+    types::{
+        pantry::PantryItem,
+        query::RecipesFilter,
+        recipe::{Recipe, UpdateRecipe},
+    },
+};
+use crate::contexts::prelude::use_login;
+
+/// Largest page size the API allows, used when building the index
+const REBUILD_PER_PAGE: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IndexStatus {
+    Unloaded,
+    /// waiting for the stored index to be removed
+    Clearing,
+    Loading,
+    Ready,
+    /// building the index failed, it is not loaded again until retried
+    Failed,
+}
+
+thread_local! {
+    static INDEX: RefCell<SearchIndex> = RefCell::default();
+    static STATUS: Cell<IndexStatus> = const { Cell::new(IndexStatus::Unloaded) };
+    /// increased on every clear, so work started before it is thrown away
+    static GENERATION: Cell<u32> = const { Cell::new(0) };
+    static SUBSCRIBERS: RefCell<Vec<Callback<()>>> = RefCell::default();
+}
+
+fn notify_subscribers() {
+    let subscribers = SUBSCRIBERS.with(|v| v.borrow().clone());
+    subscribers.iter().for_each(|callback| callback.emit(()));
+}
+
+fn log_store_error(err: wasm_bindgen::JsValue, when: &str) {
+    gloo::console::error!(format!("search index storage failed, when {when}"), err);
+}
+
+fn set_status(status: IndexStatus) {
+    STATUS.with(|v| v.set(status));
+    notify_subscribers();
+}
+
+fn generation() -> u32 {
+    GENERATION.with(|v| v.get())
+}
+
+pub fn is_ready() -> bool {
+    STATUS.with(|v| v.get()) == IndexStatus::Ready
+}
+
+/// Whether the index could not be built, see [`retry`]
+pub fn has_failed() -> bool {
+    STATUS.with(|v| v.get()) == IndexStatus::Failed
+}
+
+/// Try loading the index again after it failed to build
+pub fn retry() {
+    if has_failed() {
+        set_status(IndexStatus::Unloaded);
+    }
+}
+
+/// Search the index, returning the best matches first
+pub fn search(query: &str, limit: usize) -> Vec<SearchResult> {
+    INDEX.with(|v| v.borrow().search(query, limit))
+}
+
+/// Search the index, only including recipes matching a smart query
+pub fn search_matching(query: &str, smart_query: &Query, limit: usize) -> Vec<SearchResult> {
+    INDEX.with(|v| {
+        v.borrow()
+            .search_where(query, limit, |document| smart_query.matches(document))
+    })
+}
+
+/// Every recipe matching a smart query, sorted by title
+pub fn matching(smart_query: &Query) -> Vec<SearchResult> {
+    INDEX.with(|v| v.borrow().filter(|document| smart_query.matches(document)))
+}
+
+/// Every recipe in the index, sorted by title
+pub fn all() -> Vec<SearchResult> {
+    INDEX.with(|v| v.borrow().filter(|_| true))
+}
+
+/// Number of recipes matching a smart query
+pub fn count_matching(smart_query: &Query) -> usize {
+    INDEX.with(|v| v.borrow().count(|document| smart_query.matches(document)))
+}
+
+/// Every tag used across the library
+pub fn tags() -> Vec<String> {
+    INDEX.with(|v| v.borrow().tags())
+}
+
+/// Every cuisine used across the library
+pub fn cuisines() -> Vec<String> {
+    INDEX.with(|v| v.borrow().cuisines())
+}
+
+/// Every course used across the library
+pub fn courses() -> Vec<String> {
+    INDEX.with(|v| v.borrow().courses())
+}
+
+/// Recipes with ingredients in the pantry, the closest to being cookable first
+pub fn pantry_matches(items: &[PantryItem]) -> Vec<PantryMatch> {
+    INDEX.with(|v| pantry::rank(v.borrow().documents(), items))
+}
+
+/// Load the index from storage, then refresh it from the API in the background,
+/// building it from the API when nothing has been stored yet
+fn ensure_loaded(api: Api) {
+    if STATUS.with(|v| v.get()) != IndexStatus::Unloaded {
+        return;
+    }
+    STATUS.with(|v| v.set(IndexStatus::Loading));
+    let generation = generation();
+    wasm_bindgen_futures::spawn_local(async move {
+        let stored = store::read_all().await.unwrap_or_else(|err| {
+            log_store_error(err, "loading");
+            Vec::new()
+        });
+        if generation != self::generation() {
+            // cleared while loading
+            return;
+        }
+        if stored.is_empty() {
+            let result = rebuild(&api).await;
+            if generation != self::generation() {
+                return;
+            }
+            match result {
+                Ok(_) => set_status(IndexStatus::Ready),
+                Err(err) => {
+                    gloo::console::error!(format!("failed to build search index: {:?}", err));
+                    set_status(IndexStatus::Failed);
+                }
+            }
+            return;
+        }
+        INDEX.with(|v| {
+            let mut index = v.borrow_mut();
+            stored
+                .into_iter()
+                .for_each(|document| index.insert(document));
+        });
+        set_status(IndexStatus::Ready);
+        // recipes may have changed elsewhere since they were stored,
+        // the stored index is still usable should this fail
+        if let Err(err) = rebuild(&api).await {
+            gloo::console::error!(format!("failed to refresh search index: {:?}", err));
+        }
+    });
+}
+
+/// Replace the whole index with every recipe from the API
+pub async fn rebuild(api: &Api) -> Result<(), ApiError> {
+    let generation = generation();
+    let mut documents = Vec::new();
+    let mut filters = RecipesFilter {
+        per_page: REBUILD_PER_PAGE,
+        ..Default::default()
+    };
+    loop {
+        let page = api.get_recipes(&filters).await?;
+        documents.extend(page.items.iter().map(IndexedRecipe::from));
+        match page.next_cursor {
+            Some(next_page) => filters.page = next_page,
+            None => break,
+        }
+    }
+    if generation != self::generation() {
+        // cleared while fetching, so the recipes belong to a previous login
+        return Ok(());
+    }
+    INDEX.with(|v| {
+        let mut index = v.borrow_mut();
+        index.clear();
+        documents
+            .iter()
+            .cloned()
+            .for_each(|document| index.insert(document));
+    });
+    notify_subscribers();
+    if let Err(err) = store::put_all(&documents).await {
+        log_store_error(err, "rebuilding");
+    }
+    Ok(())
+}
+
+fn put_document(document: IndexedRecipe) {
+    INDEX.with(|v| v.borrow_mut().insert(document.clone()));
+    notify_subscribers();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = store::put(&document).await {
+            log_store_error(err, "saving recipe");
+        }
+    });
+}
+
+/// Add or replace a recipe in the index
+pub fn put_recipe(recipe: &Recipe) {
+    put_document(IndexedRecipe::from(recipe));
+}
+
+/// Apply a partial recipe update to the index
+pub fn update_recipe(id: &str, update: &UpdateRecipe) {
+    if let Some(mut document) = INDEX.with(|v| v.borrow().get(id).cloned()) {
+        document.apply_update(update);
+        put_document(document);
+    }
+}
+
+/// Update the image of an indexed recipe
+pub fn update_recipe_image(id: &str, image_id: Option<String>) {
+    if let Some(mut document) = INDEX.with(|v| v.borrow().get(id).cloned()) {
+        document.image_id = image_id;
+        put_document(document);
+    }
+}
+
+pub fn remove_recipe(id: &str) {
+    INDEX.with(|v| v.borrow_mut().remove(id));
+    notify_subscribers();
+    let id = id.to_owned();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = store::delete(&id).await {
+            log_store_error(err, "removing recipe");
+        }
+    });
+}
+
+/// Remove everything from the index (e.g. on logout),
+/// it will be rebuilt the next time it is used
+pub fn clear() {
+    GENERATION.with(|v| v.set(v.get().wrapping_add(1)));
+    let generation = generation();
+    INDEX.with(|v| v.borrow_mut().clear());
+    set_status(IndexStatus::Clearing);
+    // not loaded again until the stored index is gone, so it is never read back
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = store::clear().await {
+            log_store_error(err, "clearing");
+        }
+        if generation == self::generation() {
+            set_status(IndexStatus::Unloaded);
+        }
+    });
+}
+
+/// Load the search index (if needed),
+/// re-rendering the component whenever the index changes.
+/// Returns whether the index is ready to be searched.
+#[hook]
+pub fn use_search_index() -> bool {
+    let login_ctx = use_login().unwrap();
+    let force_update = use_force_update();
+    // loaded again once the index has been cleared or retried
+    let status = STATUS.with(|v| v.get());
+    use_effect_with_deps(
+        move |(login_ctx, _)| {
+            let callback = Callback::from(move |_| force_update.force_update());
+            SUBSCRIBERS.with(|v| v.borrow_mut().push(callback.clone()));
+            if let Some(api) = login_ctx.http_api.clone() {
+                ensure_loaded(api);
+            }
+            move || SUBSCRIBERS.with(|v| v.borrow_mut().retain(|c| *c != callback))
+        },
+        (login_ctx, status),
+    );
+    is_ready()
+}
@@ -0,0 +1,452 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::recipe::{Info, Recipe, UpdateRecipe};
+
+const TITLE_WEIGHT: u32 = 8;
+const TAG_WEIGHT: u32 = 4;
+const INGREDIENT_WEIGHT: u32 = 2;
+const STEP_WEIGHT: u32 = 1;
+
+/// Words that are too common to be useful when searching
+const STOP_WORDS: [&str; 12] = [
+    "a", "an", "and", "at", "for", "in", "into", "of", "on", "the", "to", "with",
+];
+
+/// The searchable parts of a recipe, as stored in the index
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IndexedRecipe {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub image_id: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub ingredients: Vec<String>,
+    /// names of the ingredients that are not needed to cook it
+    #[serde(default)]
+    pub optional_ingredients: Vec<String>,
+    #[serde(default)]
+    pub steps: Vec<String>,
+    // only used by smart queries & filters, so are not indexed
+    #[serde(default)]
+    pub description: Option<String>,
+    /// the least made, with the most when it is a range
+    #[serde(default)]
+    pub yields: Option<f32>,
+    #[serde(default)]
+    pub yields_max: Option<f32>,
+    /// in minutes
+    #[serde(default)]
+    pub total_time: Option<u32>,
+    #[serde(default)]
+    pub cuisine: Option<String>,
+    #[serde(default)]
+    pub course: Option<String>,
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    #[serde(default)]
+    pub equipment: Vec<String>,
+    #[serde(default)]
+    pub favourite: bool,
+    #[serde(default)]
+    pub rating: Option<u8>,
+    #[serde(default)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl IndexedRecipe {
+    /// Merge a partial update into the indexed content
+    pub fn apply_update(&mut self, update: &UpdateRecipe) {
+        if let Some(title) = &update.title {
+            self.title = title.clone();
+        }
+        if let Some(tags) = &update.tags {
+            self.tags = tags.clone();
+        }
+        if let Some(description) = &update.short_description {
+            self.description = Some(description.clone());
+        }
+        if let Some(yields) = &update.info.yields {
+            self.yields = Some(yields.value);
+            self.yields_max = yields.value_max;
+        }
+        let info = &update.info;
+        if [
+            info.prep_time,
+            info.cook_time,
+            info.rest_time,
+            info.total_time,
+        ]
+        .iter()
+        .any(Option::is_some)
+        {
+            // zero clears a time
+            let without_zero = |time: Option<u32>| time.filter(|v| *v != 0);
+            self.total_time = Info {
+                prep_time: without_zero(info.prep_time),
+                cook_time: without_zero(info.cook_time),
+                rest_time: without_zero(info.rest_time),
+                total_time: without_zero(info.total_time),
+                ..Default::default()
+            }
+            .total_time();
+        }
+        // an empty value clears it
+        let non_empty = |value: &String| Some(value.clone()).filter(|v| !v.is_empty());
+        if let Some(cuisine) = &info.cuisine {
+            self.cuisine = non_empty(cuisine);
+        }
+        if let Some(course) = &info.course {
+            self.course = non_empty(course);
+        }
+        if let Some(difficulty) = &info.difficulty {
+            self.difficulty = non_empty(difficulty);
+        }
+        if let Some(equipment) = &update.equipment {
+            self.equipment = equipment.clone();
+        }
+        if let Some(favourite) = update.favourite {
+            self.favourite = favourite;
+        }
+        if let Some(rating) = update.rating {
+            self.rating = (rating != 0).then_some(rating);
+        }
+        if let Some(ingredients) = &update.ingredients {
+            self.ingredients = ingredients.iter().filter_map(|i| i.name.clone()).collect();
+            self.optional_ingredients = ingredients
+                .iter()
+                .filter(|i| i.optional)
+                .filter_map(|i| i.name.clone())
+                .collect();
+        }
+        if let Some(steps) = &update.steps {
+            self.steps = steps
+                .iter()
+                .map(|step| {
+                    let mut text = step.title.clone().unwrap_or_default();
+                    text.push(' ');
+                    text.push_str(&step.description.clone().unwrap_or_default());
+                    text
+                })
+                .collect();
+        }
+    }
+
+    /// Get every indexable term with its weight,
+    /// a term appearing multiple times will have its weights combined
+    fn terms(&self) -> HashMap<String, u32> {
+        let mut terms = HashMap::new();
+        let mut add_field = |text: &str, weight: u32| {
+            for token in tokenize(text) {
+                for term in token_terms(&token) {
+                    *terms.entry(term).or_insert(0) += weight;
+                }
+            }
+        };
+        add_field(&self.title, TITLE_WEIGHT);
+        self.tags.iter().for_each(|v| add_field(v, TAG_WEIGHT));
+        self.ingredients
+            .iter()
+            .for_each(|v| add_field(v, INGREDIENT_WEIGHT));
+        self.steps.iter().for_each(|v| add_field(v, STEP_WEIGHT));
+        terms
+    }
+}
+
+impl From<&Recipe> for IndexedRecipe {
+    fn from(recipe: &Recipe) -> Self {
+        Self {
+            id: recipe.id.clone(),
+            title: recipe.title.clone(),
+            image_id: recipe.image_id.clone(),
+            tags: recipe.tags.clone(),
+            ingredients: recipe.ingredients.iter().map(|i| i.name.clone()).collect(),
+            optional_ingredients: recipe
+                .ingredients
+                .iter()
+                .filter(|i| i.optional)
+                .map(|i| i.name.clone())
+                .collect(),
+            steps: recipe
+                .steps
+                .iter()
+                .map(|step| {
+                    format!(
+                        "{} {}",
+                        step.title.clone().unwrap_or_default(),
+                        step.description
+                    )
+                })
+                .collect(),
+            description: recipe.short_description.clone(),
+            yields: recipe.info.yields.as_ref().map(|v| v.value),
+            yields_max: recipe.info.yields.as_ref().and_then(|v| v.value_max),
+            total_time: recipe.info.total_time(),
+            cuisine: recipe.info.cuisine.clone(),
+            course: recipe.info.course.clone(),
+            difficulty: recipe.info.difficulty.clone(),
+            equipment: recipe.equipment.clone(),
+            favourite: recipe.favourite,
+            rating: recipe.rating,
+            created_at: recipe.created_at,
+        }
+    }
+}
+
+/// A matching recipe, returned from a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub id: String,
+    pub title: String,
+    pub image_id: Option<String>,
+    pub tags: Vec<String>,
+    pub favourite: bool,
+    pub rating: Option<u8>,
+    /// in minutes
+    pub total_time: Option<u32>,
+    pub cuisine: Option<String>,
+    pub course: Option<String>,
+    pub difficulty: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub score: u32,
+}
+
+impl SearchResult {
+    fn new(document: &IndexedRecipe, score: u32) -> Self {
+        Self {
+            id: document.id.clone(),
+            title: document.title.clone(),
+            image_id: document.image_id.clone(),
+            tags: document.tags.clone(),
+            favourite: document.favourite,
+            rating: document.rating,
+            total_time: document.total_time,
+            cuisine: document.cuisine.clone(),
+            course: document.course.clone(),
+            difficulty: document.difficulty.clone(),
+            created_at: document.created_at,
+            score,
+        }
+    }
+}
+
+/// An in-memory inverted index, mapping terms to the recipes containing them
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    documents: HashMap<String, IndexedRecipe>,
+    // sorted so prefix matches can be found with a range lookup
+    terms: BTreeMap<String, HashMap<String, u32>>,
+}
+
+impl SearchIndex {
+    pub fn get(&self, id: &str) -> Option<&IndexedRecipe> {
+        self.documents.get(id)
+    }
+
+    /// Add a recipe to the index, replacing any existing entry
+    pub fn insert(&mut self, document: IndexedRecipe) {
+        self.remove(&document.id);
+        for (term, weight) in document.terms() {
+            self.terms
+                .entry(term)
+                .or_default()
+                .insert(document.id.clone(), weight);
+        }
+        self.documents.insert(document.id.clone(), document);
+    }
+
+    /// Remove a recipe from the index, returning it if it existed
+    pub fn remove(&mut self, id: &str) -> Option<IndexedRecipe> {
+        let document = self.documents.remove(id)?;
+        for term in document.terms().keys() {
+            if let Some(postings) = self.terms.get_mut(term) {
+                postings.remove(id);
+                if postings.is_empty() {
+                    self.terms.remove(term);
+                }
+            }
+        }
+        Some(document)
+    }
+
+    /// Every tag used by an indexed recipe, sorted
+    pub fn tags(&self) -> Vec<String> {
+        let tags: BTreeSet<&String> = self.documents.values().flat_map(|d| &d.tags).collect();
+        tags.into_iter().cloned().collect()
+    }
+
+    /// Every cuisine given by an indexed recipe, sorted
+    pub fn cuisines(&self) -> Vec<String> {
+        let cuisines: BTreeSet<&String> = self
+            .documents
+            .values()
+            .filter_map(|d| d.cuisine.as_ref())
+            .collect();
+        cuisines.into_iter().cloned().collect()
+    }
+
+    /// Every course given by an indexed recipe, sorted
+    pub fn courses(&self) -> Vec<String> {
+        let courses: BTreeSet<&String> = self
+            .documents
+            .values()
+            .filter_map(|d| d.course.as_ref())
+            .collect();
+        courses.into_iter().cloned().collect()
+    }
+
+    /// Every indexed recipe, in no particular order
+    pub fn documents(&self) -> impl Iterator<Item = &IndexedRecipe> {
+        self.documents.values()
+    }
+
+    pub fn clear(&mut self) {
+        self.documents.clear();
+        self.terms.clear();
+    }
+
+    /// Find recipes matching every word of the query,
+    /// words match whole (stemmed) terms or the start of a term
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        self.search_where(query, limit, |_| true)
+    }
+
+    /// Search the index, only including recipes accepted by the filter
+    pub fn search_where(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: impl Fn(&IndexedRecipe) -> bool,
+    ) -> Vec<SearchResult> {
+        let tokens = tokenize(query);
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+        let mut scores: Option<HashMap<&str, u32>> = None;
+        for token in tokens {
+            let token_scores = self.token_scores(&token);
+            scores = Some(match scores {
+                None => token_scores,
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(id, score)| token_scores.get(id).map(|v| (id, score + v)))
+                    .collect(),
+            });
+        }
+        let mut results: Vec<SearchResult> = scores
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(id, score)| {
+                self.documents
+                    .get(id)
+                    .filter(|document| filter(document))
+                    .map(|document| SearchResult::new(document, score))
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        results.truncate(limit);
+        results
+    }
+
+    /// Every recipe accepted by the filter, sorted by title
+    pub fn filter(&self, filter: impl Fn(&IndexedRecipe) -> bool) -> Vec<SearchResult> {
+        let mut results: Vec<SearchResult> = self
+            .documents
+            .values()
+            .filter(|document| filter(document))
+            .map(|document| SearchResult::new(document, 0))
+            .collect();
+        results.sort_by(|a, b| a.title.cmp(&b.title));
+        results
+    }
+
+    /// Number of recipes accepted by the filter
+    pub fn count(&self, filter: impl Fn(&IndexedRecipe) -> bool) -> usize {
+        self.documents
+            .values()
+            .filter(|document| filter(document))
+            .count()
+    }
+
+    /// Score each recipe for a single query word,
+    /// exact stem matches score higher than prefix matches
+    fn token_scores(&self, token: &str) -> HashMap<&str, u32> {
+        let mut scores: HashMap<&str, u32> = HashMap::new();
+        if let Some(postings) = self.terms.get(&stem(token)) {
+            for (id, weight) in postings {
+                scores.insert(id, weight * 2);
+            }
+        }
+        for (_, postings) in self
+            .terms
+            .range(token.to_owned()..)
+            .take_while(|(term, _)| term.starts_with(token))
+        {
+            for (id, weight) in postings {
+                let score = scores.entry(id).or_insert(0);
+                *score = (*score).max(*weight);
+            }
+        }
+        scores
+    }
+}
+
+/// Split text into lowercase words, skipping stop words
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Terms stored for a single word: the word itself and its stem,
+/// the unstemmed word allows prefix matching while the user is still typing
+fn token_terms(token: &str) -> HashSet<String> {
+    HashSet::from([token.to_owned(), stem(token)])
+}
+
+/// A light English suffix stripping stemmer,
+/// so that words like "baking", "baked" and "bakes" all match each other
+fn stem(word: &str) -> String {
+    const SUFFIXES: [(&str, &str); 8] = [
+        ("ies", "y"),
+        ("ied", "y"),
+        ("ings", ""),
+        ("ing", ""),
+        ("ed", ""),
+        ("es", ""),
+        ("ly", ""),
+        ("s", ""),
+    ];
+    const MIN_STEM_LEN: usize = 3;
+
+    if word.chars().count() <= MIN_STEM_LEN || word.ends_with("ss") {
+        return word.to_owned();
+    }
+    let mut stemmed = word.to_owned();
+    for (suffix, replacement) in SUFFIXES {
+        if let Some(root) = word.strip_suffix(suffix) {
+            if root.chars().count() >= MIN_STEM_LEN {
+                stemmed = format!("{root}{replacement}");
+                break;
+            }
+        }
+    }
+    // "chopped" -> "chopp" -> "chop"
+    let chars: Vec<char> = stemmed.chars().collect();
+    if let [.., a, b] = chars[..] {
+        if a == b && !"aeioulsz".contains(b) && chars.len() > MIN_STEM_LEN {
+            stemmed.pop();
+        }
+    }
+    // "bake" and "bakes" -> "bak"
+    if stemmed.ends_with('e') && stemmed.chars().count() > MIN_STEM_LEN {
+        stemmed.pop();
+    }
+    stemmed
+}
@@ -0,0 +1,99 @@
+use js_sys::{Array, Promise};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Event, IdbDatabase, IdbObjectStore, IdbOpenDbRequest, IdbRequest, IdbTransactionMode,
+};
+
+use super::index::IndexedRecipe;
+
+const DB_NAME: &str = "my-cooking-codex";
+/// Increased whenever the stored documents change, so they are rebuilt
+const DB_VERSION: u32 = 6;
+const RECIPES_STORE: &str = "search-recipes";
+
+/// Wait for an IndexedDB request to finish, returning its result
+async fn wait_for_request(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::once_into_js(move |_: Event| {
+            resolve.call0(&JsValue::NULL).ok();
+        });
+        let on_error = Closure::once_into_js(move |_: Event| {
+            reject.call0(&JsValue::NULL).ok();
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await?;
+    request.result()
+}
+
+async fn open() -> Result<IdbDatabase, JsValue> {
+    let factory = gloo::utils::window()
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB is not supported"))?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+    let on_upgrade_needed = Closure::once_into_js(move |e: Event| {
+        let request: IdbOpenDbRequest = e.target().unwrap().unchecked_into();
+        let db: IdbDatabase = request.result().unwrap().unchecked_into();
+        // any stored documents are outdated, so start again with an empty store
+        if db.object_store_names().contains(RECIPES_STORE) {
+            db.delete_object_store(RECIPES_STORE).unwrap();
+        }
+        db.create_object_store(RECIPES_STORE).unwrap();
+    });
+    request.set_onupgradeneeded(Some(on_upgrade_needed.unchecked_ref()));
+    Ok(wait_for_request(&request).await?.unchecked_into())
+}
+
+async fn recipes_store(mode: IdbTransactionMode) -> Result<IdbObjectStore, JsValue> {
+    open()
+        .await?
+        .transaction_with_str_and_mode(RECIPES_STORE, mode)?
+        .object_store(RECIPES_STORE)
+}
+
+/// Read every stored recipe
+pub async fn read_all() -> Result<Vec<IndexedRecipe>, JsValue> {
+    let store = recipes_store(IdbTransactionMode::Readonly).await?;
+    let values: Array = wait_for_request(&store.get_all()?).await?.unchecked_into();
+    Ok(values
+        .iter()
+        .filter_map(|v| v.as_string())
+        .filter_map(|v| serde_json::from_str(&v).ok())
+        .collect())
+}
+
+/// Insert or replace a stored recipe
+pub async fn put(document: &IndexedRecipe) -> Result<(), JsValue> {
+    let value = serde_json::to_string(document).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let store = recipes_store(IdbTransactionMode::Readwrite).await?;
+    wait_for_request(&store.put_with_key(&value.into(), &document.id.as_str().into())?).await?;
+    Ok(())
+}
+
+/// Replace all stored recipes
+pub async fn put_all(documents: &[IndexedRecipe]) -> Result<(), JsValue> {
+    let store = recipes_store(IdbTransactionMode::Readwrite).await?;
+    // requests are queued without awaiting, so they all run in the same transaction
+    let mut last_request = store.clear()?;
+    for document in documents {
+        let value =
+            serde_json::to_string(document).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        last_request = store.put_with_key(&value.into(), &document.id.as_str().into())?;
+    }
+    wait_for_request(&last_request).await?;
+    Ok(())
+}
+
+pub async fn delete(id: &str) -> Result<(), JsValue> {
+    let store = recipes_store(IdbTransactionMode::Readwrite).await?;
+    wait_for_request(&store.delete(&id.into())?).await?;
+    Ok(())
+}
+
+pub async fn clear() -> Result<(), JsValue> {
+    let store = recipes_store(IdbTransactionMode::Readwrite).await?;
+    wait_for_request(&store.clear()?).await?;
+    Ok(())
+}
@@ -0,0 +1,190 @@
+//! Named parts of a recipe, such as "For the dough" and "For the filling".
+//!
+//! Ingredients and steps each store the name of the section they are in,
+//! a section being the items next to each other with the same name.
+//! Recipes from before sections existed have no names, so are shown as one unnamed section.
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::types::recipe::{Ingredient, Step};
+
+pub trait Sectioned {
+    fn section(&self) -> Option<&str>;
+    fn set_section(&mut self, section: Option<String>);
+}
+
+impl Sectioned for Ingredient {
+    fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    fn set_section(&mut self, section: Option<String>) {
+        self.section = section;
+    }
+}
+
+impl Sectioned for Step {
+    fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    fn set_section(&mut self, section: Option<String>) {
+        self.section = section;
+    }
+}
+
+static NEXT_KEY: AtomicUsize = AtomicUsize::new(0);
+
+/// An item being edited, with a key that stays the same while it is moved around
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyed<T> {
+    pub key: usize,
+    pub item: T,
+}
+
+impl<T> Keyed<T> {
+    pub fn new(item: T) -> Self {
+        Self {
+            key: NEXT_KEY.fetch_add(1, Ordering::Relaxed),
+            item,
+        }
+    }
+}
+
+impl<T: Sectioned> Sectioned for Keyed<T> {
+    fn section(&self) -> Option<&str> {
+        self.item.section()
+    }
+
+    fn set_section(&mut self, section: Option<String>) {
+        self.item.set_section(section);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section<T> {
+    pub name: Option<String>,
+    pub items: Vec<T>,
+}
+
+impl<T> Section<T> {
+    pub fn new(name: Option<String>) -> Self {
+        Self {
+            name,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Where an item is, as the index of its section and its index in that section
+pub type ItemPosition = (usize, usize);
+
+/// Group items into their sections, keeping their order
+pub fn group<T: Sectioned + Clone>(items: &[T]) -> Vec<Section<T>> {
+    let mut sections: Vec<Section<T>> = Vec::new();
+    for item in items {
+        match sections.last_mut() {
+            Some(section) if section.name.as_deref() == item.section() => {
+                section.items.push(item.clone())
+            }
+            _ => sections.push(Section {
+                name: item.section().map(str::to_owned),
+                items: vec![item.clone()],
+            }),
+        }
+    }
+    sections
+}
+
+/// The items of every section in order, with their section names set.
+/// Empty sections are dropped, as they have nothing to store their name on.
+pub fn flatten<T: Sectioned + Clone>(sections: &[Section<T>]) -> Vec<T> {
+    sections
+        .iter()
+        .flat_map(|section| {
+            section.items.iter().map(|item| {
+                let mut item = item.clone();
+                item.set_section(section.name.clone());
+                item
+            })
+        })
+        .collect()
+}
+
+/// Why sections would not be the same once saved and loaded again, none when they would be.
+/// Only items store the name of their section, so an empty named section would be lost,
+/// and sections next to each other with the same name would become one.
+pub fn check<T>(sections: &[Section<T>]) -> Result<(), String> {
+    if let Some(section) = sections
+        .iter()
+        .find(|section| section.items.is_empty() && section.name.is_some())
+    {
+        return Err(format!(
+            "The \"{}\" section is empty, add to it or remove it",
+            section.name.as_deref().unwrap_or_default()
+        ));
+    }
+    // empty sections are dropped, so do not separate the sections either side of them
+    let names: Vec<&Option<String>> = sections
+        .iter()
+        .filter(|section| !section.items.is_empty())
+        .map(|section| &section.name)
+        .collect();
+    match names.windows(2).find(|pair| pair[0] == pair[1]) {
+        Some([Some(name), _]) => Err(format!(
+            "Sections next to each other can't both be named \"{name}\""
+        )),
+        Some(_) => Err("Sections next to each other can't both be unnamed".to_owned()),
+        None => Ok(()),
+    }
+}
+
+/// Move an item to the end of another section
+pub fn move_item<T>(sections: &mut [Section<T>], from: ItemPosition, to_section: usize) {
+    if from.0 != to_section {
+        let item = sections[from.0].items.remove(from.1);
+        sections[to_section].items.push(item);
+    }
+}
+
+/// Move an item so it is at a position, which is where it would be before it was moved
+pub fn move_item_to<T>(sections: &mut [Section<T>], from: ItemPosition, to: ItemPosition) {
+    if from == to {
+        return;
+    }
+    let item = sections[from.0].items.remove(from.1);
+    // the item being taken out shifts the ones after it in the same section
+    let index = match from.0 == to.0 && from.1 < to.1 {
+        true => to.1 - 1,
+        false => to.1,
+    };
+    let items = &mut sections[to.0].items;
+    items.insert(index.min(items.len()), item);
+}
+
+/// Move an item one place up or down,
+/// going into the next section when at the start or end of its own
+pub fn move_item_by_one<T>(sections: &mut [Section<T>], from: ItemPosition, is_up: bool) {
+    let (section, index) = from;
+    match is_up {
+        true if index != 0 => move_item_to(sections, from, (section, index - 1)),
+        true if section != 0 => {
+            let to = (section - 1, sections[section - 1].items.len());
+            move_item_to(sections, from, to);
+        }
+        false if index + 1 < sections[section].items.len() => {
+            move_item_to(sections, from, (section, index + 2))
+        }
+        false if section + 1 < sections.len() => move_item_to(sections, from, (section + 1, 0)),
+        _ => {}
+    }
+}
+
+/// Remove a section's heading, its items joining the section before it
+pub fn remove_section<T>(sections: &mut Vec<Section<T>>, index: usize) {
+    if index == 0 {
+        sections[0].name = None;
+    } else {
+        let section = sections.remove(index);
+        sections[index - 1].items.extend(section.items);
+    }
+}
@@ -0,0 +1,707 @@
+//! A small query language for smart collections, evaluated client-side.
+//!
+//! Queries are made of terms combined with `AND`, `OR`, `NOT` and brackets,
+//! terms next to each other are treated as `AND`. For example:
+//! `tag:dessert AND NOT ingredient:nuts AND steps<=5`
+//!
+//! Supported terms:
+//! - `word` or `"some words"`, matching anywhere in the recipe
+//! - `title:`, `description:`, `tag:`, `ingredient:` & `step:` followed by a value
+//! - `cuisine:`, `course:` & `difficulty:`, matching the whole value, and `equipment:`
+//! - `is:favourite`, matching favourite recipes
+//! - `ingredients`, `steps`, `yields` & `rating` compared to a number with `<`, `<=`, `>`, `>=` or `=`
+//!   (a range of yields, such as "serves 4–6", matching when any of it does)
+//! - `time`, the total time, compared to a duration such as `time<=45` (minutes) or `time<1h30m`
+use std::borrow::Cow;
+use std::fmt;
+
+use super::duration;
+use super::search::index::IndexedRecipe;
+use super::types::recipe::{InfoYields, Recipe};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Comparison {
+    /// Whether any value in a range compares to another,
+    /// a single value being a range starting and ending at it
+    fn compare(&self, (min, max): (f32, f32), b: f32) -> bool {
+        match self {
+            Self::Lt => min < b,
+            Self::Le => min <= b,
+            Self::Gt => max > b,
+            Self::Ge => max >= b,
+            Self::Eq => min <= b && b <= max,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CountField {
+    Ingredients,
+    Steps,
+    Yields,
+    Rating,
+    /// total time in minutes
+    Time,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// matches any of the recipe's text
+    Text(String),
+    Title(String),
+    Description(String),
+    /// matches a whole tag
+    Tag(String),
+    Ingredient(String),
+    Step(String),
+    /// matches the whole cuisine, as do course & difficulty
+    Cuisine(String),
+    Course(String),
+    Difficulty(String),
+    Equipment(String),
+    Favourite,
+    Count(CountField, Comparison, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Term(Term),
+}
+
+/// Why a query could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    /// character the problem was found at, counting from zero
+    pub position: usize,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at character {}", self.message, self.position + 1)
+    }
+}
+
+/// The parts of a recipe a query can match against
+pub trait Queryable {
+    fn title(&self) -> &str;
+    fn description(&self) -> Option<&str>;
+    fn tags(&self) -> &[String];
+    fn ingredient_names(&self) -> Vec<&str>;
+    fn step_texts(&self) -> Vec<Cow<'_, str>>;
+    fn cuisine(&self) -> Option<&str>;
+    fn course(&self) -> Option<&str>;
+    fn difficulty(&self) -> Option<&str>;
+    fn equipment(&self) -> &[String];
+    /// the least & most made, being the same when it is not a range
+    fn yields(&self) -> Option<(f32, f32)>;
+    fn total_time(&self) -> Option<u32>;
+    fn favourite(&self) -> bool;
+    fn rating(&self) -> Option<u8>;
+}
+
+impl Queryable for Recipe {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.short_description.as_deref()
+    }
+
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn ingredient_names(&self) -> Vec<&str> {
+        self.ingredients.iter().map(|i| i.name.as_str()).collect()
+    }
+
+    fn step_texts(&self) -> Vec<Cow<'_, str>> {
+        self.steps
+            .iter()
+            .map(|step| match &step.title {
+                Some(title) => Cow::Owned(format!("{} {}", title, step.description)),
+                None => Cow::Borrowed(step.description.as_str()),
+            })
+            .collect()
+    }
+
+    fn cuisine(&self) -> Option<&str> {
+        self.info.cuisine.as_deref()
+    }
+
+    fn course(&self) -> Option<&str> {
+        self.info.course.as_deref()
+    }
+
+    fn difficulty(&self) -> Option<&str> {
+        self.info.difficulty.as_deref()
+    }
+
+    fn equipment(&self) -> &[String] {
+        &self.equipment
+    }
+
+    fn yields(&self) -> Option<(f32, f32)> {
+        self.info.yields.as_ref().map(InfoYields::range)
+    }
+
+    fn total_time(&self) -> Option<u32> {
+        self.info.total_time()
+    }
+
+    fn favourite(&self) -> bool {
+        self.favourite
+    }
+
+    fn rating(&self) -> Option<u8> {
+        self.rating
+    }
+}
+
+impl Queryable for IndexedRecipe {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn ingredient_names(&self) -> Vec<&str> {
+        self.ingredients.iter().map(String::as_str).collect()
+    }
+
+    fn step_texts(&self) -> Vec<Cow<'_, str>> {
+        self.steps
+            .iter()
+            .map(|v| Cow::Borrowed(v.as_str()))
+            .collect()
+    }
+
+    fn cuisine(&self) -> Option<&str> {
+        self.cuisine.as_deref()
+    }
+
+    fn course(&self) -> Option<&str> {
+        self.course.as_deref()
+    }
+
+    fn difficulty(&self) -> Option<&str> {
+        self.difficulty.as_deref()
+    }
+
+    fn equipment(&self) -> &[String] {
+        &self.equipment
+    }
+
+    fn yields(&self) -> Option<(f32, f32)> {
+        let min = self.yields?;
+        Some((min, self.yields_max.unwrap_or(min).max(min)))
+    }
+
+    fn total_time(&self) -> Option<u32> {
+        self.total_time
+    }
+
+    fn favourite(&self) -> bool {
+        self.favourite
+    }
+
+    fn rating(&self) -> Option<u8> {
+        self.rating
+    }
+}
+
+fn contains_ignore_case(text: &str, lowercase_value: &str) -> bool {
+    text.to_lowercase().contains(lowercase_value)
+}
+
+impl Term {
+    fn matches(&self, recipe: &impl Queryable) -> bool {
+        match self {
+            Self::Text(value) => {
+                contains_ignore_case(recipe.title(), value)
+                    || recipe
+                        .description()
+                        .is_some_and(|v| contains_ignore_case(v, value))
+                    || recipe.tags().iter().any(|v| contains_ignore_case(v, value))
+                    || recipe
+                        .ingredient_names()
+                        .iter()
+                        .any(|v| contains_ignore_case(v, value))
+                    || recipe
+                        .step_texts()
+                        .iter()
+                        .any(|v| contains_ignore_case(v, value))
+            }
+            Self::Title(value) => contains_ignore_case(recipe.title(), value),
+            Self::Description(value) => recipe
+                .description()
+                .is_some_and(|v| contains_ignore_case(v, value)),
+            Self::Tag(value) => recipe.tags().iter().any(|v| v.to_lowercase() == *value),
+            Self::Ingredient(value) => recipe
+                .ingredient_names()
+                .iter()
+                .any(|v| contains_ignore_case(v, value)),
+            Self::Step(value) => recipe
+                .step_texts()
+                .iter()
+                .any(|v| contains_ignore_case(v, value)),
+            Self::Cuisine(value) => recipe.cuisine().is_some_and(|v| v.to_lowercase() == *value),
+            Self::Course(value) => recipe.course().is_some_and(|v| v.to_lowercase() == *value),
+            Self::Difficulty(value) => recipe
+                .difficulty()
+                .is_some_and(|v| v.to_lowercase() == *value),
+            Self::Equipment(value) => recipe
+                .equipment()
+                .iter()
+                .any(|v| contains_ignore_case(v, value)),
+            Self::Favourite => recipe.favourite(),
+            Self::Count(field, comparison, value) => {
+                let single = |count: usize| (count as f32, count as f32);
+                let count = match field {
+                    CountField::Ingredients => Some(single(recipe.ingredient_names().len())),
+                    CountField::Steps => Some(single(recipe.step_texts().len())),
+                    CountField::Yields => recipe.yields(),
+                    CountField::Rating => recipe.rating().map(|v| single(v.into())),
+                    CountField::Time => recipe.total_time().map(|v| single(v as usize)),
+                };
+                count.is_some_and(|count| comparison.compare(count, *value as f32))
+            }
+        }
+    }
+}
+
+impl Query {
+    pub fn matches(&self, recipe: &impl Queryable) -> bool {
+        match self {
+            Self::And(a, b) => a.matches(recipe) && b.matches(recipe),
+            Self::Or(a, b) => a.matches(recipe) || b.matches(recipe),
+            Self::Not(query) => !query.matches(recipe),
+            Self::Term(term) => term.matches(recipe),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    OpenBracket,
+    CloseBracket,
+    And,
+    Or,
+    Not,
+    /// a term, with any quotes removed
+    Word(String),
+}
+
+/// Split a query into tokens, each with the character it starts at
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().enumerate().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push((Token::OpenBracket, start));
+            }
+            ')' => {
+                chars.next();
+                tokens.push((Token::CloseBracket, start));
+            }
+            _ => {
+                let mut word = String::new();
+                let mut is_quoted = false;
+                while let Some(&(position, c)) = chars.peek() {
+                    if c == '"' {
+                        chars.next();
+                        is_quoted = true;
+                        let mut is_closed = false;
+                        for (_, c) in chars.by_ref() {
+                            if c == '"' {
+                                is_closed = true;
+                                break;
+                            }
+                            word.push(c);
+                        }
+                        if !is_closed {
+                            return Err(ParseError::new("missing closing quote", position));
+                        }
+                    } else if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    } else {
+                        chars.next();
+                        word.push(c);
+                    }
+                }
+                let token = match word.as_str() {
+                    _ if is_quoted => Token::Word(word),
+                    v if v.eq_ignore_ascii_case("and") => Token::And,
+                    v if v.eq_ignore_ascii_case("or") => Token::Or,
+                    v if v.eq_ignore_ascii_case("not") => Token::Not,
+                    _ => Token::Word(word),
+                };
+                tokens.push((token, start));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse a single term starting at a character, such as `tag:dessert` or `steps<=5`
+fn parse_term(word: &str, position: usize) -> Result<Term, ParseError> {
+    let operator_start = match word.find([':', '<', '>', '=']) {
+        Some(v) if v != 0 => v,
+        _ => return Ok(Term::Text(word.to_lowercase())),
+    };
+    let field = word[..operator_start].to_lowercase();
+    let rest = &word[operator_start..];
+    let (comparison, value) = if let Some(v) = rest.strip_prefix("<=") {
+        (Comparison::Le, v)
+    } else if let Some(v) = rest.strip_prefix(">=") {
+        (Comparison::Ge, v)
+    } else if let Some(v) = rest.strip_prefix('<') {
+        (Comparison::Lt, v)
+    } else if let Some(v) = rest.strip_prefix('>') {
+        (Comparison::Gt, v)
+    } else if let Some(v) = rest.strip_prefix('=') {
+        (Comparison::Eq, v)
+    } else {
+        // a ':', which compares text or is equal to a number
+        (Comparison::Eq, &rest[1..])
+    };
+    if value.is_empty() {
+        return Err(ParseError::new(
+            format!("missing value for '{field}'"),
+            position,
+        ));
+    }
+    let is_text_comparison = rest.starts_with(':');
+    let value = value.to_lowercase();
+    let count = |field: CountField| -> Result<Term, ParseError> {
+        let number = value
+            .parse()
+            .map_err(|_| ParseError::new(format!("'{value}' is not a whole number"), position))?;
+        Ok(Term::Count(field, comparison, number))
+    };
+    match field.as_str() {
+        "title" if is_text_comparison => Ok(Term::Title(value)),
+        "description" if is_text_comparison => Ok(Term::Description(value)),
+        "tag" | "tags" if is_text_comparison => Ok(Term::Tag(value)),
+        "ingredient" if is_text_comparison => Ok(Term::Ingredient(value)),
+        "step" if is_text_comparison => Ok(Term::Step(value)),
+        "cuisine" if is_text_comparison => Ok(Term::Cuisine(value)),
+        "course" if is_text_comparison => Ok(Term::Course(value)),
+        "difficulty" if is_text_comparison => Ok(Term::Difficulty(value)),
+        "equipment" if is_text_comparison => Ok(Term::Equipment(value)),
+        "is" if is_text_comparison => match value.as_str() {
+            "favourite" | "favorite" => Ok(Term::Favourite),
+            _ => Err(ParseError::new(
+                format!("unknown value 'is:{value}'"),
+                position,
+            )),
+        },
+        "ingredients" => count(CountField::Ingredients),
+        "steps" => count(CountField::Steps),
+        "yields" => count(CountField::Yields),
+        "rating" => count(CountField::Rating),
+        "time" => match duration::parse(&value) {
+            Some(minutes) => Ok(Term::Count(CountField::Time, comparison, minutes as usize)),
+            None => Err(ParseError::new(
+                format!("'{value}' is not a duration"),
+                position,
+            )),
+        },
+        "title" | "description" | "tag" | "tags" | "ingredient" | "step" | "cuisine" | "course"
+        | "difficulty" | "equipment" | "is" => Err(ParseError::new(
+            format!("'{field}' can only be used with ':'"),
+            position,
+        )),
+        _ => Err(ParseError::new(
+            format!("unknown field '{field}'"),
+            position,
+        )),
+    }
+}
+
+/// Recursive descent parser, from lowest to highest precedence: OR, AND, NOT
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    /// number of characters in the query
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    /// Character the next token starts at, or the end of the query
+    fn peek_start(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(_, start)| *start)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Query, ParseError> {
+        let mut query = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            query = Query::Or(Box::new(query), Box::new(self.parse_and()?));
+        }
+        Ok(query)
+    }
+
+    fn parse_and(&mut self) -> Result<Query, ParseError> {
+        let mut query = self.parse_not()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                }
+                // terms next to each other are joined with AND
+                Some(Token::Not | Token::OpenBracket | Token::Word(_)) => (),
+                _ => break,
+            }
+            query = Query::And(Box::new(query), Box::new(self.parse_not()?));
+        }
+        Ok(query)
+    }
+
+    fn parse_not(&mut self) -> Result<Query, ParseError> {
+        let start = self.peek_start();
+        match self.next() {
+            Some(Token::Not) => Ok(Query::Not(Box::new(self.parse_not()?))),
+            Some(Token::OpenBracket) => {
+                let query = self.parse_or()?;
+                let end = self.peek_start();
+                match self.next() {
+                    Some(Token::CloseBracket) => Ok(query),
+                    _ => Err(ParseError::new("missing closing bracket", end)),
+                }
+            }
+            Some(Token::Word(word)) => Ok(Query::Term(parse_term(&word, start)?)),
+            Some(Token::CloseBracket) => Err(ParseError::new("unexpected closing bracket", start)),
+            Some(Token::And) => Err(ParseError::new("unexpected AND", start)),
+            Some(Token::Or) => Err(ParseError::new("unexpected OR", start)),
+            None => Err(ParseError::new("unexpected end of query", start)),
+        }
+    }
+}
+
+/// Parse a smart query, such as `tag:dessert AND NOT ingredient:nuts AND steps<=5`
+pub fn parse(input: &str) -> Result<Query, ParseError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(ParseError::new("query is empty", 0));
+    }
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        end: input.chars().count(),
+    };
+    let query = parser.parse_or()?;
+    let start = parser.peek_start();
+    match parser.peek() {
+        None => Ok(query),
+        Some(Token::CloseBracket) => Err(ParseError::new("unexpected closing bracket", start)),
+        Some(_) => Err(ParseError::new("unexpected text at end of query", start)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(title: &str, tags: &[&str], ingredients: &[&str]) -> IndexedRecipe {
+        serde_json::from_value(serde_json::json!({
+            "id": title,
+            "title": title,
+            "tags": tags,
+            "ingredients": ingredients,
+            "steps": ["mix", "bake"],
+            "total_time": 40,
+            "rating": 4,
+        }))
+        .unwrap()
+    }
+
+    fn text(value: &str) -> Query {
+        Query::Term(Term::Text(value.to_owned()))
+    }
+
+    fn and(a: Query, b: Query) -> Query {
+        Query::And(Box::new(a), Box::new(b))
+    }
+
+    fn or(a: Query, b: Query) -> Query {
+        Query::Or(Box::new(a), Box::new(b))
+    }
+
+    fn not(query: Query) -> Query {
+        Query::Not(Box::new(query))
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            parse("a OR b AND c").unwrap(),
+            or(text("a"), and(text("b"), text("c")))
+        );
+        assert_eq!(
+            parse("a AND b OR c").unwrap(),
+            or(and(text("a"), text("b")), text("c"))
+        );
+    }
+
+    #[test]
+    fn brackets_override_precedence() {
+        assert_eq!(
+            parse("(a OR b) AND c").unwrap(),
+            and(or(text("a"), text("b")), text("c"))
+        );
+    }
+
+    #[test]
+    fn adjacent_terms_are_joined_with_and() {
+        assert_eq!(parse("a b").unwrap(), and(text("a"), text("b")));
+        assert_eq!(parse("a NOT b").unwrap(), and(text("a"), not(text("b"))));
+    }
+
+    #[test]
+    fn not_applies_to_the_next_term_only() {
+        assert_eq!(
+            parse("NOT a AND b").unwrap(),
+            and(not(text("a")), text("b"))
+        );
+        assert_eq!(parse("NOT NOT a").unwrap(), not(not(text("a"))));
+        assert_eq!(
+            parse("NOT (a OR b)").unwrap(),
+            not(or(text("a"), text("b")))
+        );
+    }
+
+    #[test]
+    fn operators_ignore_case() {
+        assert_eq!(parse("a or not b").unwrap(), or(text("a"), not(text("b"))));
+    }
+
+    #[test]
+    fn quotes_keep_words_together() {
+        assert_eq!(parse("\"spring onion\"").unwrap(), text("spring onion"));
+        assert_eq!(
+            parse("ingredient:\"Spring Onion\"").unwrap(),
+            Query::Term(Term::Ingredient("spring onion".to_owned()))
+        );
+    }
+
+    #[test]
+    fn quoted_operators_are_text() {
+        assert_eq!(parse("\"and\"").unwrap(), text("and"));
+        assert_eq!(
+            parse("a \"OR\" b").unwrap(),
+            and(and(text("a"), text("or")), text("b"))
+        );
+    }
+
+    #[test]
+    fn comparison_operators() {
+        let count =
+            |comparison, value| Query::Term(Term::Count(CountField::Steps, comparison, value));
+        assert_eq!(parse("steps<5").unwrap(), count(Comparison::Lt, 5));
+        assert_eq!(parse("steps<=5").unwrap(), count(Comparison::Le, 5));
+        assert_eq!(parse("steps>5").unwrap(), count(Comparison::Gt, 5));
+        assert_eq!(parse("steps>=5").unwrap(), count(Comparison::Ge, 5));
+        assert_eq!(parse("steps=5").unwrap(), count(Comparison::Eq, 5));
+        assert_eq!(parse("steps:5").unwrap(), count(Comparison::Eq, 5));
+    }
+
+    #[test]
+    fn time_compares_durations() {
+        assert_eq!(
+            parse("time<1h30m").unwrap(),
+            Query::Term(Term::Count(CountField::Time, Comparison::Lt, 90))
+        );
+    }
+
+    #[test]
+    fn comparisons_match_recipes() {
+        let recipe = recipe("Bread", &["baking"], &["flour", "water", "yeast"]);
+        assert!(parse("ingredients=3").unwrap().matches(&recipe));
+        assert!(parse("ingredients>=3").unwrap().matches(&recipe));
+        assert!(!parse("ingredients>3").unwrap().matches(&recipe));
+        assert!(parse("steps<3").unwrap().matches(&recipe));
+        assert!(!parse("rating<4").unwrap().matches(&recipe));
+    }
+
+    #[test]
+    fn queries_match_recipes() {
+        let bread = recipe("Bread", &["baking"], &["flour", "water", "yeast"]);
+        let cake = recipe("Cake", &["baking", "dessert"], &["flour", "nuts"]);
+        let query = parse("tag:baking AND NOT ingredient:nuts").unwrap();
+        assert!(query.matches(&bread));
+        assert!(!query.matches(&cake));
+        let query = parse("tag:dessert OR title:bread").unwrap();
+        assert!(query.matches(&bread));
+        assert!(query.matches(&cake));
+    }
+
+    #[test]
+    fn errors_are_at_the_problem() {
+        let position = |input| parse(input).unwrap_err().position;
+        assert_eq!(position(""), 0);
+        assert_eq!(position("a AND"), 5);
+        assert_eq!(position("a OR OR b"), 5);
+        assert_eq!(position("(a OR b"), 7);
+        assert_eq!(position("a ) b"), 2);
+        assert_eq!(position("a \"b c"), 2);
+        assert_eq!(position("a colour:red"), 2);
+        assert_eq!(position("steps<many"), 0);
+        assert_eq!(position("a is:cheap"), 2);
+        assert_eq!(position("title<5"), 0);
+    }
+
+    #[test]
+    fn error_positions_count_characters() {
+        assert_eq!(parse("crème AND").unwrap_err().position, 9);
+        assert_eq!(
+            parse("a AND").unwrap_err().to_string(),
+            "unexpected end of query at character 6"
+        );
+    }
+}
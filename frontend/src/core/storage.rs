@@ -1,7 +1,18 @@
-use super::types::StoredLogin;
-use gloo::storage::{LocalStorage, Storage};
+use super::{
+    temperature::TemperatureDisplay,
+    types::{query::RecipesFilter, recipe::Recipe, StoredLogin},
+};
+use gloo::storage::{LocalStorage, SessionStorage, Storage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 const LOGIN_DETAILS_KEY: &str = "login-details";
+const RECIPES_LIST_CACHE_KEY: &str = "recipes-list-cache";
+const RECIPES_VIEW_KEY: &str = "recipes-view";
+const SAVED_SEARCHES_KEY: &str = "saved-searches";
+const TEMPERATURE_DISPLAY_KEY: &str = "temperature-display";
+/// Dispatched on the window whenever the saved searches change
+pub const SAVED_SEARCHES_CHANGE_EVENT: &str = "saved-searches-change";
 
 pub fn read_login_token() -> Option<StoredLogin> {
     LocalStorage::get::<StoredLogin>(LOGIN_DETAILS_KEY).ok()
@@ -12,5 +23,113 @@ pub fn set_login_token(login: StoredLogin) {
 }
 
 pub fn remove_login_token() {
-    LocalStorage::delete(LOGIN_DETAILS_KEY)
+    LocalStorage::delete(LOGIN_DETAILS_KEY);
+    remove_recipes_list_cache();
+}
+
+/// The loaded recipes list, kept so navigating back can restore it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecipesListCache {
+    /// filters used, the page being the last one loaded
+    pub filters: RecipesFilter,
+    pub recipes: Vec<Recipe>,
+    /// total recipes matching the filters
+    pub total_count: usize,
+    /// the next page to load, none when all have been loaded
+    pub next_cursor: Option<usize>,
+    /// scroll position of the window
+    pub window_scroll_y: f64,
+    /// scroll position of the drawer content (when it is the scrolling element)
+    pub content_scroll_top: i32,
+}
+
+pub fn read_recipes_list_cache() -> Option<RecipesListCache> {
+    SessionStorage::get::<RecipesListCache>(RECIPES_LIST_CACHE_KEY).ok()
+}
+
+pub fn set_recipes_list_cache(cache: &RecipesListCache) {
+    // the cache is only an optimisation, so ignore when storage is full
+    SessionStorage::set(RECIPES_LIST_CACHE_KEY, cache).ok();
+}
+
+pub fn remove_recipes_list_cache() {
+    SessionStorage::delete(RECIPES_LIST_CACHE_KEY)
+}
+
+/// Layout used to show the recipes list
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum RecipesView {
+    #[default]
+    Grid,
+    List,
+    Table,
+}
+
+impl RecipesView {
+    pub const ALL: [Self; 3] = [Self::Grid, Self::List, Self::Table];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Grid => "Grid",
+            Self::List => "List",
+            Self::Table => "Table",
+        }
+    }
+}
+
+/// Get the chosen recipes view of a user, views are kept for each user id
+pub fn read_recipes_view(user_id: &str) -> RecipesView {
+    LocalStorage::get::<HashMap<String, RecipesView>>(RECIPES_VIEW_KEY)
+        .ok()
+        .and_then(|views| views.get(user_id).copied())
+        .unwrap_or_default()
+}
+
+pub fn set_recipes_view(user_id: &str, view: RecipesView) {
+    let mut views =
+        LocalStorage::get::<HashMap<String, RecipesView>>(RECIPES_VIEW_KEY).unwrap_or_default();
+    views.insert(user_id.to_owned(), view);
+    LocalStorage::set(RECIPES_VIEW_KEY, views).ok();
+}
+
+/// Get how a user wants temperatures in steps to be shown, kept for each user id
+pub fn read_temperature_display(user_id: &str) -> TemperatureDisplay {
+    LocalStorage::get::<HashMap<String, TemperatureDisplay>>(TEMPERATURE_DISPLAY_KEY)
+        .ok()
+        .and_then(|displays| displays.get(user_id).copied())
+        .unwrap_or_default()
+}
+
+pub fn set_temperature_display(user_id: &str, display: TemperatureDisplay) {
+    let mut displays =
+        LocalStorage::get::<HashMap<String, TemperatureDisplay>>(TEMPERATURE_DISPLAY_KEY)
+            .unwrap_or_default();
+    displays.insert(user_id.to_owned(), display);
+    LocalStorage::set(TEMPERATURE_DISPLAY_KEY, displays).ok();
+}
+
+/// A smart query saved under a name, shown as a collection in the drawer
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+}
+
+/// Get the saved searches of a user, searches are kept for each user id
+pub fn read_saved_searches(user_id: &str) -> Vec<SavedSearch> {
+    LocalStorage::get::<HashMap<String, Vec<SavedSearch>>>(SAVED_SEARCHES_KEY)
+        .ok()
+        .and_then(|mut searches| searches.remove(user_id))
+        .unwrap_or_default()
+}
+
+pub fn set_saved_searches(user_id: &str, saved_searches: Vec<SavedSearch>) {
+    let mut searches = LocalStorage::get::<HashMap<String, Vec<SavedSearch>>>(SAVED_SEARCHES_KEY)
+        .unwrap_or_default();
+    searches.insert(user_id.to_owned(), saved_searches);
+    LocalStorage::set(SAVED_SEARCHES_KEY, searches).ok();
+    if let Ok(event) = web_sys::Event::new(SAVED_SEARCHES_CHANGE_EVENT) {
+        gloo::utils::window().dispatch_event(&event).ok();
+    }
 }
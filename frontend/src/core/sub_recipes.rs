@@ -0,0 +1,125 @@
+//! Recipes used as ingredients of other recipes.
+//!
+//! A sub-recipe ingredient's amount is how much of the sub-recipe is needed,
+//! either in the unit the sub-recipe yields (e.g. 500 ml of a sauce yielding 1000 ml)
+//! or otherwise as the number of batches.
+//! A range is made for its upper end, so there is enough, and no amount is a single batch.
+//! Likewise a sub-recipe yielding a range is taken to make the least of it.
+use std::collections::HashMap;
+
+use super::{
+    api::{Api, ApiError},
+    types::recipe::{Ingredient, Recipe},
+};
+
+/// How much a sub-recipe needs scaling by, to make the amount of an ingredient using it
+pub fn sub_recipe_scale(ingredient: &Ingredient, sub_recipe: &Recipe) -> f32 {
+    let amount = ingredient.max_amount().unwrap_or(1.0);
+    match &sub_recipe.info.yields {
+        Some(yields)
+            if yields.value > 0.0
+                && yields.unit_type.eq_ignore_ascii_case(&ingredient.unit_type) =>
+        {
+            amount / yields.value
+        }
+        _ => amount,
+    }
+}
+
+/// Fetch every sub-recipe used by the ingredients, including the sub-recipes of those.
+/// Sub-recipes that no longer exist are left out.
+pub async fn load_sub_recipes(
+    api: &Api,
+    ingredients: &[Ingredient],
+) -> Result<HashMap<String, Recipe>, ApiError> {
+    let mut sub_recipes: HashMap<String, Recipe> = HashMap::new();
+    let mut to_load: Vec<String> = ingredients
+        .iter()
+        .filter_map(|v| v.recipe_id.clone())
+        .collect();
+    let mut missing: Vec<String> = Vec::new();
+    while let Some(id) = to_load.pop() {
+        if sub_recipes.contains_key(&id) || missing.contains(&id) {
+            continue;
+        }
+        match api.get_recipe_by_id(id.clone()).await {
+            Ok(recipe) => {
+                to_load.extend(
+                    recipe
+                        .ingredients
+                        .iter()
+                        .filter_map(|v| v.recipe_id.clone()),
+                );
+                sub_recipes.insert(id, recipe);
+            }
+            Err(ApiError::Response(e)) if e.status_code == 404 => missing.push(id),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(sub_recipes)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandedIngredient {
+    /// the ingredient with its amount scaled
+    pub ingredient: Ingredient,
+    /// how deep in sub-recipes the ingredient is, zero when used directly
+    pub depth: usize,
+    /// whether it is a sub-recipe, which has its ingredients following it
+    pub is_sub_recipe: bool,
+}
+
+fn expand_into(
+    ingredients: &[Ingredient],
+    sub_recipes: &HashMap<String, Recipe>,
+    scale: f32,
+    path: &mut Vec<String>,
+    expanded: &mut Vec<ExpandedIngredient>,
+) {
+    for ingredient in ingredients {
+        let mut scaled = ingredient.clone();
+        scaled.scale(scale);
+        // cycles are rejected when saving, but are guarded against in case of older data
+        let sub_recipe = ingredient
+            .recipe_id
+            .as_ref()
+            .filter(|id| !path.contains(id))
+            .and_then(|id| sub_recipes.get(id));
+        expanded.push(ExpandedIngredient {
+            ingredient: scaled,
+            // the path starts with the recipe itself
+            depth: path.len() - 1,
+            is_sub_recipe: sub_recipe.is_some(),
+        });
+        if let Some(sub_recipe) = sub_recipe {
+            path.push(sub_recipe.id.clone());
+            expand_into(
+                &sub_recipe.ingredients,
+                sub_recipes,
+                scale * sub_recipe_scale(ingredient, sub_recipe),
+                path,
+                expanded,
+            );
+            path.pop();
+        }
+    }
+}
+
+/// The ingredients of a recipe with each sub-recipe followed by its own (scaled) ingredients
+pub fn expand_ingredients(
+    recipe_id: &str,
+    ingredients: &[Ingredient],
+    sub_recipes: &HashMap<String, Recipe>,
+    scale: f32,
+) -> Vec<ExpandedIngredient> {
+    let mut expanded = Vec::new();
+    expand_into(
+        ingredients,
+        sub_recipes,
+        scale,
+        // so a sub-recipe using the recipe itself is not expanded
+        &mut vec![recipe_id.to_owned()],
+        &mut expanded,
+    );
+    expanded
+}
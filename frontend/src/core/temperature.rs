@@ -0,0 +1,306 @@
+//! Temperatures written in step text, such as "180°C", "350 F", "gas mark 4" or "160°C fan",
+//! and showing them converted to the units a user prefers.
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+/// How much lower a fan oven is set than a conventional oven, in °C
+const FAN_OFFSET_CELSIUS: f32 = 20.0;
+
+const FAN_REGEX: &str = r"fan(?:[- ](?:forced|oven|assisted))?";
+
+/// A temperature, which is either a value with a unit or a gas mark,
+/// optionally marked as being for a fan oven before or after it.
+/// A bare C or F needs to be attached to the value (or be a capital letter),
+/// so amounts such as "12 c sugar" are not taken as temperatures.
+fn temperature_regex() -> Regex {
+    let value_unit = r"(?P<value>\d{2,3}(?:\.\d+)?)(?:\s*(?:°|º|˚|degrees?\b|deg\b\.?)\s*(?P<unit>celsius|centigrade|fahrenheit|c|f)|(?P<attached_unit>[cf])|(?-i:\s(?P<capital_unit>[CF]))|\s+(?P<word_unit>celsius|centigrade|fahrenheit))\b";
+    let gas_mark = r"gas(?:\s+mark)?\s*(?P<mark>1/2|1/4|½|¼|\d{1,2})";
+    Regex::new(&format!(
+        r"(?i)\b(?P<fan_before>{FAN_REGEX}\s+)?(?:{value_unit}|{gas_mark})(?P<fan_after>\s*\({FAN_REGEX}\)|\s+{FAN_REGEX}\b)?"
+    ))
+    .unwrap()
+}
+
+thread_local! {
+    // compiled once, as every step is parsed whenever a recipe is shown
+    static TEMPERATURE_REGEX: Regex = temperature_regex();
+}
+
+/// Which units temperatures are shown converted to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TemperatureUnits {
+    /// only as written
+    Off,
+    #[default]
+    All,
+    Celsius,
+    Fahrenheit,
+    GasMark,
+}
+
+impl TemperatureUnits {
+    pub const ALL: [Self; 5] = [
+        Self::Off,
+        Self::All,
+        Self::Celsius,
+        Self::Fahrenheit,
+        Self::GasMark,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Off => "As Written",
+            Self::All => "All Units",
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+            Self::GasMark => "Gas Mark",
+        }
+    }
+
+    fn shows(&self, unit: Unit) -> bool {
+        match self {
+            Self::Off => false,
+            Self::All => true,
+            Self::Celsius => unit == Unit::Celsius,
+            Self::Fahrenheit => unit == Unit::Fahrenheit,
+            Self::GasMark => unit == Unit::GasMark,
+        }
+    }
+}
+
+/// How a user wants temperatures in steps to be shown
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TemperatureDisplay {
+    pub units: TemperatureUnits,
+    /// oven temperatures are shown for a fan oven, rather than a conventional one
+    pub fan_oven: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+    GasMark,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Temperature {
+    unit: Unit,
+    /// it was written for a fan oven
+    is_fan: bool,
+    /// the temperature for a conventional oven
+    celsius: f32,
+}
+
+impl Temperature {
+    fn from_captures(captures: &Captures) -> Option<Self> {
+        let is_fan = captures.name("fan_before").is_some() || captures.name("fan_after").is_some();
+        if let Some(mark) = captures.name("mark") {
+            let mark = match mark.as_str() {
+                "1/2" | "½" => 0.5,
+                "1/4" | "¼" => 0.25,
+                mark => mark.parse().ok()?,
+            };
+            return Some(Self {
+                unit: Unit::GasMark,
+                // gas ovens don't have fans
+                is_fan: false,
+                celsius: fahrenheit_to_celsius(gas_mark_to_fahrenheit(mark)),
+            });
+        }
+        let value: f32 = captures.name("value")?.as_str().parse().ok()?;
+        let unit = ["unit", "attached_unit", "capital_unit", "word_unit"]
+            .iter()
+            .find_map(|name| captures.name(name))?
+            .as_str()
+            .to_lowercase();
+        let (unit, celsius) = if unit.starts_with('f') {
+            (Unit::Fahrenheit, fahrenheit_to_celsius(value))
+        } else {
+            (Unit::Celsius, value)
+        };
+        Some(Self {
+            unit,
+            is_fan,
+            celsius: if is_fan {
+                celsius + FAN_OFFSET_CELSIUS
+            } else {
+                celsius
+            },
+        })
+    }
+
+    /// Whether it is written as how it would be shown
+    fn is_shown_as(&self, unit: Unit, fan_oven: bool) -> bool {
+        self.unit == unit && (unit == Unit::GasMark || self.is_fan == fan_oven)
+    }
+}
+
+fn fahrenheit_to_celsius(fahrenheit: f32) -> f32 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// Gas mark 1 is 275 °F, going up by 25 °F for each mark, with ½ and ¼ below it
+fn gas_mark_to_fahrenheit(mark: f32) -> f32 {
+    if mark < 1.0 {
+        250.0 - (0.5 - mark) * 100.0
+    } else {
+        250.0 + mark * 25.0
+    }
+}
+
+/// The nearest gas mark, none when it is outside of what gas ovens have
+fn fahrenheit_to_gas_mark(fahrenheit: f32) -> Option<f32> {
+    if !(212.5..=512.5).contains(&fahrenheit) {
+        None
+    } else if fahrenheit < 237.5 {
+        Some(0.25)
+    } else if fahrenheit < 262.5 {
+        Some(0.5)
+    } else {
+        Some(((fahrenheit - 250.0) / 25.0).round().clamp(1.0, 10.0))
+    }
+}
+
+fn format_gas_mark(mark: f32) -> String {
+    if mark == 0.25 {
+        "¼".to_owned()
+    } else if mark == 0.5 {
+        "½".to_owned()
+    } else {
+        format!("{mark}")
+    }
+}
+
+/// Whether text is about an oven, which is when fan offsets and gas marks make sense,
+/// rather than for example the temperature of oil or sugar
+pub fn mentions_oven(text: &str) -> bool {
+    let text = text.to_lowercase();
+    ["oven", "bake", "baking", "roast", "gas mark", "fan"]
+        .iter()
+        .any(|word| text.contains(word))
+}
+
+/// The temperature converted to the units shown, leaving out those already written
+fn conversions(
+    temperature: &Temperature,
+    written: &[Temperature],
+    display: TemperatureDisplay,
+    in_oven: bool,
+) -> Vec<String> {
+    let in_oven = in_oven || temperature.is_fan || temperature.unit == Unit::GasMark;
+    let fan_oven = display.fan_oven && in_oven;
+    let celsius = if fan_oven {
+        temperature.celsius - FAN_OFFSET_CELSIUS
+    } else {
+        temperature.celsius
+    };
+    let oven = match (fan_oven, temperature.is_fan) {
+        (true, _) => " fan",
+        // make it clear it is not the fan temperature that was written
+        (false, true) => " conventional",
+        (false, false) => "",
+    };
+    let is_written = |unit| written.iter().any(|v| v.is_shown_as(unit, fan_oven));
+    let mut conversions = Vec::new();
+    if display.units.shows(Unit::Celsius) && !is_written(Unit::Celsius) {
+        conversions.push(format!("{:.0} °C{oven}", celsius));
+    }
+    if display.units.shows(Unit::Fahrenheit) && !is_written(Unit::Fahrenheit) {
+        conversions.push(format!("{:.0} °F{oven}", celsius_to_fahrenheit(celsius)));
+    }
+    if in_oven && display.units.shows(Unit::GasMark) && !is_written(Unit::GasMark) {
+        // gas marks are always for a conventional oven
+        if let Some(mark) = fahrenheit_to_gas_mark(celsius_to_fahrenheit(temperature.celsius)) {
+            conversions.push(format!("gas {}", format_gas_mark(mark)));
+        }
+    }
+    conversions
+}
+
+/// Part of text, with temperatures found in it
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperaturePart<'a> {
+    Text(&'a str),
+    /// what the temperature written before it is in the units shown, such as "356 °F / gas 4"
+    Conversion(String),
+}
+
+/// Whether text between two temperatures joins them as the same temperature
+/// in different units, such as "180°C / 350°F" or "180°C (gas 4)"
+fn is_joining(text: &str) -> bool {
+    matches!(
+        text.trim_matches(|c: char| c.is_whitespace() || c == '(' || c == ')')
+            .to_lowercase()
+            .as_str(),
+        "" | "/" | "," | "|" | "or"
+    )
+}
+
+/// Split text into the text and the conversions of temperatures written in it.
+/// Temperatures written together in several units are converted once, after the last of them.
+pub fn parse(text: &str, display: TemperatureDisplay, in_oven: bool) -> Vec<TemperaturePart<'_>> {
+    if display.units == TemperatureUnits::Off {
+        return vec![TemperaturePart::Text(text)];
+    }
+    let mut groups: Vec<(usize, usize, Vec<Temperature>)> = Vec::new();
+    TEMPERATURE_REGEX.with(|regex| {
+        for captures in regex.captures_iter(text) {
+            let matched = captures.get(0).unwrap();
+            let temperature = match Temperature::from_captures(&captures) {
+                Some(temperature) => temperature,
+                None => continue,
+            };
+            match groups.last_mut() {
+                Some((_, end, temperatures)) if is_joining(&text[*end..matched.start()]) => {
+                    *end = matched.end();
+                    temperatures.push(temperature);
+                }
+                _ => groups.push((matched.start(), matched.end(), vec![temperature])),
+            }
+        }
+    });
+
+    let mut parts = Vec::new();
+    let mut position = 0;
+    for (start, mut end, temperatures) in groups {
+        let conversions = conversions(&temperatures[0], &temperatures, display, in_oven);
+        if conversions.is_empty() {
+            continue;
+        }
+        // keep a bracket that was opened between the temperatures before the conversion
+        let group = &text[start..end];
+        if group.matches('(').count() > group.matches(')').count() {
+            if let Some(closing) = text[end..].find(|c: char| !c.is_whitespace()) {
+                if text[end + closing..].starts_with(')') {
+                    end += closing + 1;
+                }
+            }
+        }
+        parts.push(TemperaturePart::Text(&text[position..end]));
+        parts.push(TemperaturePart::Conversion(conversions.join(" / ")));
+        position = end;
+    }
+    if position < text.len() || parts.is_empty() {
+        parts.push(TemperaturePart::Text(&text[position..]));
+    }
+    parts
+}
+
+/// Text with the conversions of temperatures written after them in brackets,
+/// such as "180 °C (356 °F / gas 4)"
+pub fn annotate(text: &str, display: TemperatureDisplay, in_oven: bool) -> String {
+    parse(text, display, in_oven)
+        .into_iter()
+        .map(|part| match part {
+            TemperaturePart::Text(text) => text.to_owned(),
+            TemperaturePart::Conversion(conversion) => format!(" ({conversion})"),
+        })
+        .collect()
+}
@@ -1,8 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub mod book;
+pub mod history;
+pub mod pantry;
 pub mod query;
 pub mod recipe;
+pub mod revision;
 pub mod stats;
 pub mod user;
 
@@ -21,6 +25,22 @@ pub struct LoginToken {
     pub expiry: DateTime<Utc>,
 }
 
+impl LoginToken {
+    /// The subject claim of the token, identifying the logged in user.
+    /// The token is not verified, so this must only be used for client side state
+    pub fn subject(&self) -> Option<String> {
+        let payload = self.token.split('.').nth(1)?;
+        // convert from base64url, as expected by atob
+        let mut payload = payload.replace('-', "+").replace('_', "/");
+        while payload.len() % 4 != 0 {
+            payload.push('=');
+        }
+        let payload = gloo::utils::window().atob(&payload).ok()?;
+        let claims: serde_json::Value = serde_json::from_str(&payload).ok()?;
+        claims.get("sub")?.as_str().map(String::from)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct StoredLogin {
     pub api_url: String,
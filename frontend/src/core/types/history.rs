@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCookLog {
+    pub cooked_at: DateTime<Utc>,
+    pub scale: f32,
+    /// 1-5 stars
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// A single time a recipe was cooked
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CookLog {
+    pub id: String,
+    pub recipe_id: String,
+    pub owner_id: String,
+    pub cooked_at: DateTime<Utc>,
+    pub scale: f32,
+    #[serde(default)]
+    pub rating: Option<u8>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Fraction;
+
+/// Days before it expires that an item is shown as expiring soon
+const EXPIRING_SOON_DAYS: i64 = 3;
+
+/// A new pantry item, or the whole of an updated one,
+/// where a missing quantity, unit or expiry removes it
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePantryItem {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Something the user has in their kitchen
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PantryItem {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub quantity: Option<f32>,
+    #[serde(default)]
+    pub unit_type: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PantryItem {
+    /// Quantity and unit, such as "1/2 kg", empty when no quantity is given
+    pub fn quantity_summary(&self) -> String {
+        [
+            self.quantity
+                .map(|v| Fraction::from(v).to_string())
+                .unwrap_or_default(),
+            self.unit_type.clone().unwrap_or_default(),
+        ]
+        .into_iter()
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at < now)
+    }
+
+    /// Whether it has not expired, but will within a few days
+    pub fn is_expiring_soon(&self, now: DateTime<Utc>) -> bool {
+        !self.is_expired(now)
+            && self
+                .expires_at
+                .is_some_and(|expires_at| expires_at < now + Duration::days(EXPIRING_SOON_DAYS))
+    }
+}
+
+impl From<&PantryItem> for UpdatePantryItem {
+    fn from(item: &PantryItem) -> Self {
+        Self {
+            name: item.name.clone(),
+            quantity: item.quantity,
+            unit_type: item.unit_type.clone(),
+            expires_at: item.expires_at,
+        }
+    }
+}
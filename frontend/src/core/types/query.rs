@@ -1,10 +1,126 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecipesSort {
+    #[default]
+    Newest,
+    Oldest,
+    Title,
+    TitleDesc,
+    Rating,
+    /// most recently cooked first
+    Cooked,
+    MostCooked,
+    /// shortest total time first
+    Quickest,
+}
+
+impl RecipesSort {
+    pub const ALL: [Self; 8] = [
+        Self::Newest,
+        Self::Oldest,
+        Self::Title,
+        Self::TitleDesc,
+        Self::Rating,
+        Self::Cooked,
+        Self::MostCooked,
+        Self::Quickest,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Newest => "Newest",
+            Self::Oldest => "Oldest",
+            Self::Title => "Title (A-Z)",
+            Self::TitleDesc => "Title (Z-A)",
+            Self::Rating => "Rating",
+            Self::Cooked => "Last Cooked",
+            Self::MostCooked => "Most Cooked",
+            Self::Quickest => "Quickest",
+        }
+    }
+
+    /// Whether results from the search index can be sorted this way,
+    /// the index does not hold the cooking history
+    pub fn is_indexed(&self) -> bool {
+        !matches!(self, Self::Cooked | Self::MostCooked)
+    }
+}
+
+/// A single page of results, with the details needed to paginate accurately
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total_count: usize,
+    /// the next page to request, none when this is the last page
+    pub next_cursor: Option<usize>,
+}
+
+/// (De)serialize tags as a single comma separated value,
+/// as query strings cannot represent lists consistently
+mod comma_separated {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(tags: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&tags.join(","))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        Ok(String::deserialize(deserializer)?
+            .split(',')
+            .map(|tag| tag.trim())
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct RecipesFilter {
     pub page: usize,
     pub per_page: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(with = "comma_separated", skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub sort: RecipesSort,
+    /// only include favourite recipes
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub favourites: bool,
+    /// a smart query, evaluated against the search index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// only include recipes taking at most this many minutes in total
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_time: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cuisine: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub course: Option<String>,
+    /// one of `recipe::DIFFICULTIES`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+}
+
+impl RecipesFilter {
+    /// Whether both filters would return the same recipes, ignoring pagination
+    pub fn is_same_query(&self, other: &Self) -> bool {
+        self.per_page == other.per_page
+            && self.search == other.search
+            && self.tags == other.tags
+            && self.sort == other.sort
+            && self.favourites == other.favourites
+            && self.query == other.query
+            && self.max_total_time == other.max_total_time
+            && self.cuisine == other.cuisine
+            && self.course == other.course
+            && self.difficulty == other.difficulty
+    }
 }
 
 impl Default for RecipesFilter {
@@ -12,6 +128,15 @@ impl Default for RecipesFilter {
         Self {
             page: 1,
             per_page: 20,
+            search: None,
+            tags: Vec::new(),
+            sort: RecipesSort::default(),
+            favourites: false,
+            query: None,
+            max_total_time: None,
+            cuisine: None,
+            course: None,
+            difficulty: None,
         }
     }
 }
@@ -1,13 +1,33 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::Fraction;
+
 #[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateIngredient {
+    /// unique in the recipe, so steps can refer to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub name: String,
-    pub amount: f32,
+    /// none when it is not measured, such as salt "to taste"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f32>,
+    /// the upper end when the amount is a range, such as the 3 of "2–3"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_max: Option<f32>,
+    #[serde(default)]
     pub unit_type: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
     #[serde(default)]
     pub description: Option<String>,
+    /// another recipe used as the ingredient
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipe_id: Option<String>,
+    /// name of the part of the recipe it is for, e.g. "For the dough"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -16,6 +36,12 @@ pub struct CreateStep {
     #[serde(default)]
     pub title: Option<String>,
     pub description: String,
+    /// name of the part of the recipe it is for, e.g. "For the dough"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// ids of the ingredients used in the step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingredients: Vec<String>,
 }
 
 pub type CreateInfo = Info;
@@ -36,19 +62,43 @@ pub struct CreateRecipe {
     pub ingredients: Vec<CreateIngredient>,
     #[serde(default)]
     pub steps: Vec<CreateStep>,
+    /// the recipe this is a variation of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_id: Option<String>,
+    /// where the recipe is from, such as a book or website
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// what is needed to make it, such as "stand mixer"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equipment: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateIngredient {
+    /// unique in the recipe, so steps can refer to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
     #[serde(default)]
     pub amount: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_max: Option<f32>,
     #[serde(default)]
     pub unit_type: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
     #[serde(default)]
     pub description: Option<String>,
+    /// another recipe used as the ingredient
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipe_id: Option<String>,
+    /// name of the part of the recipe it is for, e.g. "For the dough"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -58,6 +108,12 @@ pub struct UpdateStep {
     pub title: Option<String>,
     #[serde(default)]
     pub description: Option<String>,
+    /// name of the part of the recipe it is for, e.g. "For the dough"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// ids of the ingredients used in the step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingredients: Vec<String>,
 }
 
 pub type UpdateInfo = Info;
@@ -79,16 +135,144 @@ pub struct UpdateRecipe {
     pub ingredients: Option<Vec<UpdateIngredient>>,
     #[serde(default)]
     pub steps: Option<Vec<UpdateStep>>,
+    #[serde(default)]
+    pub favourite: Option<bool>,
+    /// 1-5, or 0 to remove the rating
+    #[serde(default)]
+    pub rating: Option<u8>,
+    /// empty to remove the review
+    #[serde(default)]
+    pub review: Option<String>,
+    /// empty to remove it, as is the url
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equipment: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Ingredient {
+    /// unique in the recipe, so steps can refer to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub name: String,
-    pub amount: f32,
+    /// none when it is not measured, such as salt "to taste"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f32>,
+    /// the upper end when the amount is a range, such as the 3 of "2–3"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_max: Option<f32>,
+    #[serde(default)]
     pub unit_type: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
     #[serde(default)]
     pub description: Option<String>,
+    /// another recipe used as the ingredient
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipe_id: Option<String>,
+    /// name of the part of the recipe it is for, e.g. "For the dough"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+}
+
+impl Ingredient {
+    /// Amount, unit and name, e.g. "200 g flour" or "2–3 cloves garlic (optional)"
+    pub fn summary(&self) -> String {
+        self.scaled_summary(1.0)
+    }
+
+    /// Amount, unit and name with the amount multiplied by a scale
+    pub fn scaled_summary(&self, scale: f32) -> String {
+        let mut summary = [self.scaled_quantity(scale), self.name.clone()]
+            .into_iter()
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.optional {
+            summary.push_str(" (optional)");
+        }
+        summary
+    }
+
+    /// The amount multiplied by a scale, such as "½" or "2–3", none when it is not measured
+    pub fn scaled_amount(&self, scale: f32) -> Option<String> {
+        let amount = self.amount?;
+        Some(match self.amount_max {
+            Some(max) if max > amount => format!(
+                "{}–{}",
+                Fraction::from(amount * scale),
+                Fraction::from(max * scale)
+            ),
+            _ => Fraction::from(amount * scale).to_string(),
+        })
+    }
+
+    /// Amount and unit multiplied by a scale, such as "2–3 cloves", empty when neither is given
+    pub fn scaled_quantity(&self, scale: f32) -> String {
+        [
+            self.scaled_amount(scale).unwrap_or_default(),
+            self.unit_type.clone(),
+        ]
+        .into_iter()
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    /// Multiply the amount, and both ends of a range, by a scale
+    pub fn scale(&mut self, scale: f32) {
+        self.amount = self.amount.map(|v| v * scale);
+        self.amount_max = self.amount_max.map(|v| v * scale);
+    }
+
+    /// The most that is needed, being the upper end of a range
+    pub fn max_amount(&self) -> Option<f32> {
+        match (self.amount, self.amount_max) {
+            (Some(amount), Some(max)) => Some(amount.max(max)),
+            (amount, _) => amount,
+        }
+    }
+}
+
+impl From<&Ingredient> for UpdateIngredient {
+    fn from(ingredient: &Ingredient) -> Self {
+        Self {
+            id: ingredient.id.clone(),
+            name: Some(ingredient.name.clone()),
+            amount: ingredient.amount,
+            // a range must end above where it starts
+            amount_max: ingredient
+                .amount_max
+                .filter(|max| ingredient.amount.is_some_and(|amount| *max > amount)),
+            unit_type: Some(ingredient.unit_type.clone()),
+            optional: ingredient.optional,
+            description: ingredient.description.clone(),
+            recipe_id: ingredient.recipe_id.clone(),
+            section: ingredient.section.clone(),
+        }
+    }
+}
+
+/// Give every ingredient without an id a new one, returning whether any were given
+pub fn ensure_ingredient_ids(ingredients: &mut [Ingredient]) -> bool {
+    let mut ids: Vec<String> = ingredients.iter().filter_map(|v| v.id.clone()).collect();
+    let mut is_changed = false;
+    for ingredient in ingredients.iter_mut().filter(|v| v.id.is_none()) {
+        let id = loop {
+            let id = format!("{:08x}", (js_sys::Math::random() * u32::MAX as f64) as u32);
+            if !ids.contains(&id) {
+                break id;
+            }
+        };
+        ids.push(id.clone());
+        ingredient.id = Some(id);
+        is_changed = true;
+    }
+    is_changed
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -97,19 +281,213 @@ pub struct Step {
     #[serde(default)]
     pub title: Option<String>,
     pub description: String,
+    /// name of the part of the recipe it is for, e.g. "For the dough"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// ids of the ingredients used in the step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingredients: Vec<String>,
+}
+
+impl From<&Step> for UpdateStep {
+    fn from(step: &Step) -> Self {
+        Self {
+            title: step.title.clone(),
+            description: Some(step.description.clone()),
+            section: step.section.clone(),
+            ingredients: step.ingredients.clone(),
+        }
+    }
+}
+
+impl Step {
+    /// The ingredients used in the step, any no longer in the recipe are left out
+    pub fn used_ingredients<'a>(&self, ingredients: &'a [Ingredient]) -> Vec<&'a Ingredient> {
+        self.ingredients
+            .iter()
+            .filter_map(|id| ingredients.iter().find(|v| v.id.as_ref() == Some(id)))
+            .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct InfoYields {
-    pub value: usize,
+    /// can be fractional, such as 1.5 litres
+    pub value: f32,
+    /// the upper end when a range is made, such as the 6 of "serves 4–6"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_max: Option<f32>,
     pub unit_type: String,
 }
 
+impl InfoYields {
+    /// The value as written, such as "3/2" or "4–6"
+    pub fn value_text(&self) -> String {
+        match self.value_max {
+            Some(max) if max > self.value => {
+                format!("{}–{}", Fraction::from(self.value), Fraction::from(max))
+            }
+            _ => Fraction::from(self.value).to_string(),
+        }
+    }
+
+    /// The value and unit, such as "4–6 servings"
+    pub fn summary(&self) -> String {
+        format!("{} {}", self.value_text(), self.unit_type)
+    }
+
+    /// The least and most made, being the same when it is not a range
+    pub fn range(&self) -> (f32, f32) {
+        (
+            self.value,
+            self.value_max.unwrap_or(self.value).max(self.value),
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PanShape {
+    #[default]
+    Round,
+    Square,
+    Rectangle,
+    Loaf,
+    Bundt,
+}
+
+impl PanShape {
+    pub const ALL: [Self; 5] = [
+        Self::Round,
+        Self::Square,
+        Self::Rectangle,
+        Self::Loaf,
+        Self::Bundt,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Round => "Round",
+            Self::Square => "Square",
+            Self::Rectangle => "Rectangle",
+            Self::Loaf => "Loaf",
+            Self::Bundt => "Bundt",
+        }
+    }
+
+    /// What the width of the pan is for this shape
+    pub fn width_label(&self) -> &'static str {
+        match self {
+            Self::Round | Self::Bundt => "Diameter",
+            Self::Square => "Side",
+            Self::Rectangle | Self::Loaf => "Width",
+        }
+    }
+
+    /// Whether the pan's width and length are both needed, rather than only a diameter or side
+    pub fn has_length(&self) -> bool {
+        matches!(self, Self::Rectangle | Self::Loaf)
+    }
+
+    /// Whether the pan is deep enough that it is compared by volume rather than area
+    pub fn has_depth(&self) -> bool {
+        matches!(self, Self::Loaf | Self::Bundt)
+    }
+}
+
+/// The tin or tray a recipe is made in, measured in cm
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Pan {
+    pub shape: PanShape,
+    /// the diameter of round & bundt pans, or the side of square pans
+    pub width: f32,
+    /// of rectangle & loaf pans
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<f32>,
+    /// of loaf & bundt pans
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<f32>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Info {
     pub yields: Option<InfoYields>,
+    /// in minutes, as are the other times
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prep_time: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cook_time: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rest_time: Option<u32>,
+    /// when not given, the sum of the other times
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_time: Option<u32>,
+    /// such as "Italian"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cuisine: Option<String>,
+    /// such as "Dinner"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub course: Option<String>,
+    /// one of `DIFFICULTIES`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+    /// what it is made in, a width of zero removing it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pan: Option<Pan>,
+}
+
+pub const DIFFICULTIES: [&str; 3] = ["easy", "medium", "hard"];
+
+/// A difficulty as it is shown, such as "Easy"
+pub fn difficulty_label(difficulty: &str) -> String {
+    let mut chars = difficulty.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Info {
+    /// The total time given, or otherwise the sum of the other times
+    pub fn total_time(&self) -> Option<u32> {
+        self.total_time.or_else(|| {
+            [self.prep_time, self.cook_time, self.rest_time]
+                .into_iter()
+                .flatten()
+                .reduce(|a, b| a + b)
+        })
+    }
+
+    /// Each time with its label, leaving out those not given
+    pub fn times(&self) -> Vec<(&'static str, u32)> {
+        [
+            ("Prep Time", self.prep_time),
+            ("Cook Time", self.cook_time),
+            ("Rest Time", self.rest_time),
+            ("Total Time", self.total_time()),
+        ]
+        .into_iter()
+        .filter_map(|(label, minutes)| Some((label, minutes?)))
+        .collect()
+    }
+
+    /// Cuisine, course & difficulty with their labels, leaving out those not given
+    pub fn classifications(&self) -> Vec<(&'static str, String)> {
+        [
+            ("Cuisine", self.cuisine.clone()),
+            ("Course", self.course.clone()),
+            (
+                "Difficulty",
+                self.difficulty.as_deref().map(difficulty_label),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value?)))
+        .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -132,4 +510,86 @@ pub struct Recipe {
     pub steps: Vec<Step>,
     #[serde(default)]
     pub image_id: Option<String>,
+    #[serde(default)]
+    pub favourite: bool,
+    /// 1-5 stars
+    #[serde(default)]
+    pub rating: Option<u8>,
+    #[serde(default)]
+    pub review: Option<String>,
+    #[serde(default)]
+    pub last_cooked_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub times_cooked: usize,
+    /// the recipe this is a variation of
+    #[serde(default)]
+    pub origin_id: Option<String>,
+    /// where the recipe is from, such as a book or website
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub equipment: Vec<String>,
+    #[serde(default)]
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Recipe {
+    /// A copy of the recipe, linked to it as a variation
+    pub fn to_variation(&self, title: String) -> CreateRecipe {
+        CreateRecipe {
+            title,
+            info: self.info.clone(),
+            short_description: self.short_description.clone(),
+            long_description: self.long_description.clone(),
+            tags: self.tags.clone(),
+            ingredients: self
+                .ingredients
+                .iter()
+                .map(|v| CreateIngredient {
+                    id: v.id.clone(),
+                    name: v.name.clone(),
+                    amount: v.amount,
+                    amount_max: v.amount_max,
+                    unit_type: v.unit_type.clone(),
+                    optional: v.optional,
+                    description: v.description.clone(),
+                    recipe_id: v.recipe_id.clone(),
+                    section: v.section.clone(),
+                })
+                .collect(),
+            steps: self
+                .steps
+                .iter()
+                .map(|v| CreateStep {
+                    title: v.title.clone(),
+                    description: v.description.clone(),
+                    section: v.section.clone(),
+                    ingredients: v.ingredients.clone(),
+                })
+                .collect(),
+            origin_id: Some(self.id.clone()),
+            source_name: self.source_name.clone(),
+            source_url: self.source_url.clone(),
+            equipment: self.equipment.clone(),
+        }
+    }
+
+    /// How often & when the recipe was last cooked, none if never cooked
+    pub fn cooked_summary(&self) -> Option<String> {
+        let last_cooked_at = self.last_cooked_at?;
+        let times = match self.times_cooked {
+            1 => "once".to_owned(),
+            v => format!("{v} times"),
+        };
+        Some(format!(
+            "Cooked {times}, last on {}",
+            last_cooked_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+        ))
+    }
 }
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    recipe::{Info, Ingredient, Step},
+    user::User,
+};
+
+/// The editable content of a recipe, at a point in time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeSnapshot {
+    pub title: String,
+    #[serde(default)]
+    pub info: Info,
+    #[serde(default)]
+    pub short_description: Option<String>,
+    #[serde(default)]
+    pub long_description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub ingredients: Vec<Ingredient>,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+    #[serde(default)]
+    pub source_name: Option<String>,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub equipment: Vec<String>,
+}
+
+/// A saved version of a recipe, made whenever its content changes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeRevision {
+    pub id: String,
+    pub recipe_id: String,
+    pub author: User,
+    pub snapshot: RecipeSnapshot,
+    pub created_at: DateTime<Utc>,
+}
@@ -5,4 +5,5 @@ use serde::{Deserialize, Serialize};
 pub struct AccountStats {
     pub user_count: usize,
     pub recipe_count: usize,
+    pub book_count: usize,
 }
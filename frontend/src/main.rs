@@ -29,6 +29,12 @@ pub enum Route {
     RecipePrint { id: String },
     #[at("/new")]
     NewRecipe,
+    #[at("/books")]
+    Books,
+    #[at("/book/:id")]
+    Book { id: String },
+    #[at("/pantry")]
+    Pantry,
 }
 
 fn switch(routes: Route) -> Html {
@@ -38,9 +44,13 @@ fn switch(routes: Route) -> Html {
         Route::Login => html! {<Login/>},
         Route::Logout => html! {<Logout/>},
         Route::Recipes => html! {<Recipes/>},
-        Route::Recipe { id } => html! {<Recipe id={id}/>},
+        // keyed, so navigating between recipes loads the new one
+        Route::Recipe { id } => html! {<Recipe key={id.clone()} id={id.clone()}/>},
         Route::RecipePrint { id } => html! {<RecipePrintView id={id}/>},
         Route::NewRecipe => html! {<NewRecipe/>},
+        Route::Books => html! {<Books/>},
+        Route::Book { id } => html! {<Book id={id}/>},
+        Route::Pantry => html! {<Pantry/>},
     }
 }
 
@@ -1,4 +1,7 @@
+pub mod book;
+pub mod pantry;
 pub mod recipe;
+pub mod saved_search;
 
 use yew::prelude::*;
 
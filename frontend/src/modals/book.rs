@@ -0,0 +1,9 @@
+pub mod add_recipe;
+pub mod details;
+pub mod image;
+pub mod new;
+
+pub use add_recipe::AddRecipeToBook;
+pub use details::EditDetails;
+pub use image::SetImage;
+pub use new::NewBook;
@@ -0,0 +1,114 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{contexts::login::use_login, Route};
+
+use crate::modals::Modal;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_hooks::prelude::{use_async_with_options, UseAsyncOptions};
+use yew_router::prelude::Link;
+
+#[derive(Properties, PartialEq)]
+pub struct AddRecipeToBookProps {
+    pub recipe_id: String,
+    /// the title of the book the recipe was added to, when saved
+    pub onclose: Callback<Option<String>>,
+}
+
+#[function_component(AddRecipeToBook)]
+pub fn add_recipe_to_book(props: &AddRecipeToBookProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let selected_state = use_state(|| Option::<usize>::None);
+    let is_loading_state = use_state(bool::default);
+
+    let get_books = {
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        let api = login_ctx.http_api.clone();
+        use_async_with_options(
+            async move {
+                let api = api.expect("expected api to exist");
+                match api.get_books().await {
+                    Ok(v) => Ok(v),
+                    Err(err) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&err, "loading books"));
+                        logout_on_401(&err, &login_ctx);
+                        Err(err)
+                    }
+                }
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+    let books = get_books.data.clone().unwrap_or_default();
+
+    let on_save = {
+        let recipe_id = props.recipe_id.clone();
+        let on_close_callback = props.onclose.clone();
+        let is_loading_state = is_loading_state.clone();
+        let selected_book = selected_state.and_then(|i| books.get(i).cloned());
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let recipe_id = recipe_id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let is_loading_state = is_loading_state.clone();
+            if let Some(book) = selected_book.clone() {
+                wasm_bindgen_futures::spawn_local(async move {
+                    is_loading_state.set(true);
+                    let result = api.post_add_book_recipe(&book.id, &recipe_id).await;
+                    is_loading_state.set(false);
+                    match result {
+                        Ok(_) => {
+                            on_close_callback.emit(Some(book.title));
+                        }
+                        Err(e) => {
+                            push_toast(
+                                &toasts_ctx,
+                                api_error_to_toast(&e, "adding recipe to book"),
+                            );
+                            logout_on_401(&e, &login_ctx);
+                        }
+                    };
+                });
+            }
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_book_change = {
+        let selected_state = selected_state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            // first option is the placeholder
+            selected_state.set((input.selected_index() - 1).try_into().ok());
+        })
+    };
+
+    html! {
+        <Modal title={"Add To Book"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state || get_books.loading}>
+            if get_books.data.is_some() && books.is_empty() {
+                <p class="my-4">
+                    {"You have no books yet, "}
+                    <Link<Route> to={Route::Books} classes="link">{"create one first"}</Link<Route>>
+                    {"."}
+                </p>
+            } else {
+                <select class="select select-bordered w-full my-4" onchange={on_book_change} required=true aria-label="Book">
+                    <option disabled=true selected={selected_state.is_none()} value="">{"Choose a book..."}</option>
+                    { for books.iter().enumerate().map(|(i, book)| html!{
+                        <option selected={*selected_state == Some(i)}>{book.title.clone()}</option>
+                    })}
+                </select>
+            }
+        </Modal>
+    }
+}
@@ -0,0 +1,115 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{contexts::login::use_login, core::types::book::UpdateBook};
+
+use crate::modals::Modal;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct EditDetailsProps {
+    pub id: String,
+    pub title: AttrValue,
+    pub description: Option<String>,
+    /// the new title & description, when saved
+    pub onclose: Callback<Option<(String, String)>>,
+}
+
+#[function_component(EditDetails)]
+pub fn book_details(props: &EditDetailsProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let title_state = use_state(|| props.title.to_string());
+    let description_state = use_state(|| props.description.clone().unwrap_or_default());
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let id = props.id.clone();
+        let on_close_callback = props.onclose.clone();
+        let title_state = title_state.clone();
+        let description_state = description_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let id = id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let is_loading_state = is_loading_state.clone();
+            let title = (*title_state).clone();
+            let description = (*description_state).clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api
+                    .patch_update_book(
+                        &id,
+                        &UpdateBook {
+                            title: Some(title.clone()),
+                            description: Some(description.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(_) => {
+                        on_close_callback.emit(Some((title, description)));
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "saving book details"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_title_input = {
+        let title_state = title_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            title_state.set(input.value());
+        })
+    };
+
+    let on_description_input = {
+        let description_state = description_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            description_state.set(input.value());
+        })
+    };
+
+    html! {
+        <Modal title={"Edit Book"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="form-control mb-2">
+                <label class="label" for="edit-book-title">{"Title"}</label>
+                <input
+                    id="edit-book-title"
+                    oninput={on_title_input}
+                    value={(*title_state).clone()}
+                    class="input input-bordered w-full"
+                    maxlength="60"
+                    required=true
+                />
+            </div>
+            <div class="form-control">
+                <label class="label" for="edit-book-description">{"Description"}</label>
+                <textarea
+                    id="edit-book-description"
+                    oninput={on_description_input}
+                    value={(*description_state).clone()}
+                    class="textarea textarea-bordered w-full"
+                    maxlength="256"
+                />
+            </div>
+        </Modal>
+    }
+}
@@ -0,0 +1,108 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    contexts::login::use_login,
+    core::types::book::{Book, CreateBook},
+};
+
+use crate::modals::Modal;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct NewBookProps {
+    pub onclose: Callback<Option<Book>>,
+}
+
+#[function_component(NewBook)]
+pub fn new_book(props: &NewBookProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let title_state = use_state(String::default);
+    let description_state = use_state(String::default);
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let on_close_callback = props.onclose.clone();
+        let title_state = title_state.clone();
+        let description_state = description_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let on_close_callback = on_close_callback.clone();
+            let is_loading_state = is_loading_state.clone();
+            let new_book = CreateBook {
+                title: (*title_state).clone(),
+                description: match description_state.is_empty() {
+                    true => None,
+                    false => Some((*description_state).clone()),
+                },
+            };
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api.post_new_book(&new_book).await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(book) => {
+                        on_close_callback.emit(Some(book));
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "creating book"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_title_input = {
+        let title_state = title_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            title_state.set(input.value());
+        })
+    };
+
+    let on_description_input = {
+        let description_state = description_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            description_state.set(input.value());
+        })
+    };
+
+    html! {
+        <Modal title={"New Book"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="form-control mb-2">
+                <label class="label" for="new-book-title">{"Title"}</label>
+                <input
+                    id="new-book-title"
+                    oninput={on_title_input}
+                    value={(*title_state).clone()}
+                    class="input input-bordered w-full"
+                    maxlength="60"
+                    required=true
+                />
+            </div>
+            <div class="form-control">
+                <label class="label" for="new-book-description">{"Description"}</label>
+                <textarea
+                    id="new-book-description"
+                    oninput={on_description_input}
+                    value={(*description_state).clone()}
+                    class="textarea textarea-bordered w-full"
+                    maxlength="256"
+                />
+            </div>
+        </Modal>
+    }
+}
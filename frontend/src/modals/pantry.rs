@@ -0,0 +1,197 @@
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+
+use crate::components::input::FractionalNumberInput;
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    contexts::login::use_login,
+    core::types::pantry::{PantryItem, UpdatePantryItem},
+};
+
+use crate::modals::Modal;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The end of a day in the user's timezone, so an item expires once its date has passed
+fn parse_expiry(date: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .and_then(|date| Local.from_local_datetime(&date).single())
+        .map(|date| date.with_timezone(&Utc))
+}
+
+pub fn format_expiry(expires_at: &DateTime<Utc>) -> String {
+    expires_at
+        .with_timezone(&Local)
+        .format(DATE_FORMAT)
+        .to_string()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct EditPantryItemProps {
+    /// the item to edit, none to add a new one
+    #[prop_or_default]
+    pub item: Option<PantryItem>,
+    /// the added or edited item, when saved
+    pub onclose: Callback<Option<PantryItem>>,
+}
+
+#[function_component(EditPantryItem)]
+pub fn edit_pantry_item(props: &EditPantryItemProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let item = props.item.as_ref();
+    let name_state = use_state(|| item.map(|v| v.name.clone()).unwrap_or_default());
+    let quantity_state = use_state(|| item.and_then(|v| v.quantity));
+    let unit_state = use_state(|| item.and_then(|v| v.unit_type.clone()).unwrap_or_default());
+    let expiry_state = use_state(|| {
+        item.and_then(|v| v.expires_at.as_ref())
+            .map(format_expiry)
+            .unwrap_or_default()
+    });
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let item = props.item.clone();
+        let on_close_callback = props.onclose.clone();
+        let name_state = name_state.clone();
+        let quantity_state = quantity_state.clone();
+        let unit_state = unit_state.clone();
+        let expiry_state = expiry_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let item = item.clone();
+            let on_close_callback = on_close_callback.clone();
+            let is_loading_state = is_loading_state.clone();
+            let unit = unit_state.trim().to_owned();
+            let update = UpdatePantryItem {
+                name: name_state.trim().to_owned(),
+                quantity: *quantity_state,
+                unit_type: (!unit.is_empty()).then_some(unit),
+                expires_at: parse_expiry(&expiry_state),
+            };
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = match item {
+                    Some(item) => api
+                        .patch_update_pantry_item(&item.id, &update)
+                        .await
+                        .map(|_| PantryItem {
+                            name: update.name,
+                            quantity: update.quantity,
+                            unit_type: update.unit_type,
+                            expires_at: update.expires_at,
+                            ..item
+                        }),
+                    None => api.post_new_pantry_item(&update).await,
+                };
+                is_loading_state.set(false);
+                match result {
+                    Ok(item) => {
+                        on_close_callback.emit(Some(item));
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "saving pantry item"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_name_input = {
+        let name_state = name_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name_state.set(input.value());
+        })
+    };
+
+    let on_quantity_input = {
+        let quantity_state = quantity_state.clone();
+        Callback::from(move |quantity: Option<f32>| quantity_state.set(quantity))
+    };
+
+    let on_unit_input = {
+        let unit_state = unit_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            unit_state.set(input.value());
+        })
+    };
+
+    let on_expiry_input = {
+        let expiry_state = expiry_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            expiry_state.set(input.value());
+        })
+    };
+
+    let title = match props.item {
+        Some(_) => "Edit Pantry Item",
+        None => "Add To Pantry",
+    };
+
+    html! {
+        <Modal {title} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="form-control mb-2">
+                <label class="label" for="pantry-item-name">{"Name"}</label>
+                <input
+                    id="pantry-item-name"
+                    oninput={on_name_input}
+                    value={(*name_state).clone()}
+                    class="input input-bordered w-full"
+                    placeholder="spring onions"
+                    maxlength="100"
+                    required=true
+                />
+            </div>
+            <div class="flex gap-2 mb-2">
+                <div class="form-control w-full">
+                    <span class="label">{"Quantity"}</span>
+                    <FractionalNumberInput
+                        classes="input-bordered w-full"
+                        value={*quantity_state}
+                        oninput={on_quantity_input}
+                        placeholder="optional"
+                    />
+                </div>
+                <div class="form-control w-full">
+                    <label class="label" for="pantry-item-unit">{"Unit"}</label>
+                    <input
+                        id="pantry-item-unit"
+                        oninput={on_unit_input}
+                        value={(*unit_state).clone()}
+                        class="input input-bordered w-full"
+                        placeholder="optional"
+                        maxlength="30"
+                    />
+                </div>
+            </div>
+            <div class="form-control">
+                <label class="label" for="pantry-item-expiry">{"Expires"}</label>
+                <input
+                    id="pantry-item-expiry"
+                    type="date"
+                    oninput={on_expiry_input}
+                    value={(*expiry_state).clone()}
+                    class="input input-bordered w-full"
+                />
+            </div>
+        </Modal>
+    }
+}
@@ -1,15 +1,29 @@
+pub mod classification;
+pub mod cooked;
 pub mod description;
+pub mod equipment;
 pub mod image;
 pub mod info;
 pub mod ingredients;
 pub mod long_description;
+pub mod pan;
+pub mod review;
+pub mod section;
+pub mod source;
+pub mod step_description;
 pub mod steps;
 pub mod title;
 
+pub use classification::EditClassification;
+pub use cooked::LogCook;
 pub use description::EditDescription;
+pub use equipment::EditEquipment;
 pub use image::SetImage;
 pub use info::EditInfo;
 pub use ingredients::EditIngredients;
 pub use long_description::EditLongDescription;
+pub use pan::ConvertPan;
+pub use review::EditReview;
+pub use source::EditSource;
 pub use steps::EditSteps;
 pub use title::EditTitle;
@@ -0,0 +1,187 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    contexts::login::use_login,
+    core::{
+        search,
+        types::recipe::{difficulty_label, Info, UpdateInfo, UpdateRecipe, DIFFICULTIES},
+    },
+};
+
+use crate::modals::Modal;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+/// Courses suggested alongside those already used
+const COURSES: [&str; 7] = [
+    "Breakfast",
+    "Lunch",
+    "Dinner",
+    "Starter",
+    "Side",
+    "Dessert",
+    "Snack",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ClassificationField {
+    Cuisine,
+    Course,
+    Difficulty,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct EditClassificationProps {
+    pub id: AttrValue,
+    pub info: Info,
+    pub onclose: Callback<Option<Info>>,
+}
+
+/// Edit the cuisine, course & difficulty of a recipe
+#[function_component(EditClassification)]
+pub fn recipe_classification(props: &EditClassificationProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let info_state = use_state(|| props.info.clone());
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let id = props.id.to_string();
+        let on_close_callback = props.onclose.clone();
+        let info_state = info_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let id = id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let info = (*info_state).clone();
+            // an empty value clears it
+            let clearing_empty = |value: &Option<String>| Some(value.clone().unwrap_or_default());
+            let update_info = UpdateInfo {
+                cuisine: clearing_empty(&info.cuisine),
+                course: clearing_empty(&info.course),
+                difficulty: clearing_empty(&info.difficulty),
+                ..Default::default()
+            };
+            let is_loading_state = is_loading_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api
+                    .patch_update_recipe(
+                        id,
+                        &UpdateRecipe {
+                            info: update_info,
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(_) => {
+                        on_close_callback.emit(Some(info));
+                    }
+                    Err(e) => {
+                        push_toast(
+                            &toasts_ctx,
+                            api_error_to_toast(&e, "saving recipe classification"),
+                        );
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_change = {
+        let info_state = info_state.clone();
+        Callback::from(move |(field, value): (ClassificationField, String)| {
+            let mut info = (*info_state).clone();
+            let value = value.trim();
+            *match field {
+                ClassificationField::Cuisine => &mut info.cuisine,
+                ClassificationField::Course => &mut info.course,
+                ClassificationField::Difficulty => &mut info.difficulty,
+            } = (!value.is_empty()).then(|| value.to_owned());
+            info_state.set(info);
+        })
+    };
+    let on_text_input = |field: ClassificationField| {
+        on_change.reform(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            (field, input.value())
+        })
+    };
+    let on_difficulty_change = on_change.reform(|e: Event| {
+        let input: HtmlSelectElement = e.target_unchecked_into();
+        (ClassificationField::Difficulty, input.value())
+    });
+
+    let mut courses = search::courses();
+    for course in COURSES {
+        if !courses.iter().any(|v| v.eq_ignore_ascii_case(course)) {
+            courses.push(course.to_owned());
+        }
+    }
+
+    html! {
+        <Modal title={"Edit Classification"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="form-control">
+                <label class="label" for="edit-classification-cuisine">{"Cuisine"}</label>
+                <input
+                    id="edit-classification-cuisine"
+                    oninput={on_text_input(ClassificationField::Cuisine)}
+                    value={info_state.cuisine.clone().unwrap_or_default()}
+                    class="input input-bordered w-full"
+                    type="text"
+                    maxlength="50"
+                    list="recipe-cuisines"
+                    placeholder="e.g. Italian"
+                />
+                <datalist id="recipe-cuisines">
+                    { for search::cuisines().iter().map(|cuisine| html!{<option value={cuisine.clone()} />}) }
+                </datalist>
+            </div>
+            <div class="form-control">
+                <label class="label" for="edit-classification-course">{"Course"}</label>
+                <input
+                    id="edit-classification-course"
+                    oninput={on_text_input(ClassificationField::Course)}
+                    value={info_state.course.clone().unwrap_or_default()}
+                    class="input input-bordered w-full"
+                    type="text"
+                    maxlength="50"
+                    list="recipe-courses"
+                    placeholder="e.g. Dinner"
+                />
+                <datalist id="recipe-courses">
+                    { for courses.iter().map(|course| html!{<option value={course.clone()} />}) }
+                </datalist>
+            </div>
+            <div class="form-control">
+                <label class="label" for="edit-classification-difficulty">{"Difficulty"}</label>
+                <select
+                    id="edit-classification-difficulty"
+                    class="select select-bordered w-full"
+                    onchange={on_difficulty_change}
+                >
+                    <option value="" selected={info_state.difficulty.is_none()}>{"Not given"}</option>
+                    { for DIFFICULTIES.iter().map(|difficulty| html!{
+                        <option
+                            value={*difficulty}
+                            selected={info_state.difficulty.as_deref() == Some(*difficulty)}
+                        >{difficulty_label(difficulty)}</option>
+                    })}
+                </select>
+            </div>
+        </Modal>
+    }
+}
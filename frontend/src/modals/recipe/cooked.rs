@@ -0,0 +1,171 @@
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    components::rating::Rating,
+    contexts::login::use_login,
+    core::types::history::{CookLog, CreateCookLog},
+};
+
+use crate::modals::Modal;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Properties, PartialEq)]
+pub struct LogCookProps {
+    pub id: String,
+    pub onclose: Callback<Option<CookLog>>,
+}
+
+#[function_component(LogCook)]
+pub fn log_cook(props: &LogCookProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let date_state = use_state(|| Local::now().format(DATE_FORMAT).to_string());
+    let scale_state = use_state(|| "1".to_owned());
+    let rating_state = use_state(u8::default);
+    let notes_state = use_state(String::default);
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let id = props.id.to_string();
+        let on_close_callback = props.onclose.clone();
+        let date_state = date_state.clone();
+        let scale_state = scale_state.clone();
+        let rating_state = rating_state.clone();
+        let notes_state = notes_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let id = id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let is_loading_state = is_loading_state.clone();
+            // midday, so the date stays the same when viewed from nearby timezones
+            let cooked_at = NaiveDate::parse_from_str(&date_state, DATE_FORMAT)
+                .ok()
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .and_then(|date| Local.from_local_datetime(&date).single())
+                .map(|date| date.with_timezone(&Utc))
+                .unwrap_or_else(Utc::now);
+            let notes = notes_state.trim().to_owned();
+            let cook_log = CreateCookLog {
+                cooked_at,
+                scale: scale_state
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|v| *v > 0.0)
+                    .unwrap_or(1.0),
+                rating: (*rating_state != 0).then_some(*rating_state),
+                notes: (!notes.is_empty()).then_some(notes),
+            };
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api.post_recipe_history(&id, &cook_log).await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(cook_log) => {
+                        on_close_callback.emit(Some(cook_log));
+                    }
+                    Err(e) => {
+                        push_toast(
+                            &toasts_ctx,
+                            api_error_to_toast(&e, "saving cooking history"),
+                        );
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_date_input = {
+        let date_state = date_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            date_state.set(input.value());
+        })
+    };
+
+    let on_scale_input = {
+        let scale_state = scale_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            scale_state.set(input.value());
+        })
+    };
+
+    let on_rating_change = {
+        let rating_state = rating_state.clone();
+        Callback::from(move |rating: u8| rating_state.set(rating))
+    };
+
+    let on_notes_input = {
+        let notes_state = notes_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            notes_state.set(input.value());
+        })
+    };
+
+    html! {
+        <Modal title={"Cooked It"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="flex gap-2 mb-2">
+                <div class="form-control w-full">
+                    <label class="label" for="log-cook-date">{"Date"}</label>
+                    <input
+                        id="log-cook-date"
+                        type="date"
+                        oninput={on_date_input}
+                        value={(*date_state).clone()}
+                        class="input input-bordered w-full"
+                        required=true
+                    />
+                </div>
+                <div class="form-control w-full">
+                    <label class="label" for="log-cook-scale">{"Scale"}</label>
+                    <input
+                        id="log-cook-scale"
+                        type="number"
+                        min="0.05"
+                        step="0.05"
+                        oninput={on_scale_input}
+                        value={(*scale_state).clone()}
+                        class="input input-bordered w-full"
+                        required=true
+                    />
+                </div>
+            </div>
+            <div class="form-control mb-2">
+                <span class="label">{"Rating"}</span>
+                <Rating
+                    name="log-cook-rating"
+                    rating={Some(*rating_state)}
+                    onchange={on_rating_change}
+                />
+            </div>
+            <div class="form-control">
+                <label class="label" for="log-cook-notes">{"Notes"}</label>
+                <textarea
+                    id="log-cook-notes"
+                    oninput={on_notes_input}
+                    value={(*notes_state).clone()}
+                    class="textarea textarea-bordered w-full"
+                    placeholder="less salt next time..."
+                    maxlength="280"
+                />
+            </div>
+        </Modal>
+    }
+}
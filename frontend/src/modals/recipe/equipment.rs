@@ -0,0 +1,123 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{contexts::login::use_login, core::types::recipe::UpdateRecipe};
+
+use crate::modals::Modal;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct EditEquipmentProps {
+    pub id: String,
+    pub equipment: Vec<String>,
+    pub onclose: Callback<Option<Vec<String>>>,
+}
+
+/// Edit the equipment needed to make a recipe, such as "stand mixer"
+#[function_component(EditEquipment)]
+pub fn recipe_equipment(props: &EditEquipmentProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let equipment_state = use_state(|| props.equipment.clone());
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let id = props.id.to_string();
+        let on_close_callback = props.onclose.clone();
+        let equipment_state = equipment_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let id = id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let equipment: Vec<String> = equipment_state
+                .iter()
+                .map(|v| v.trim().to_owned())
+                .filter(|v| !v.is_empty())
+                .collect();
+            let is_loading_state = is_loading_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api
+                    .patch_update_recipe(
+                        id,
+                        &UpdateRecipe {
+                            equipment: Some(equipment.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(_) => {
+                        on_close_callback.emit(Some(equipment));
+                    }
+                    Err(e) => {
+                        push_toast(
+                            &toasts_ctx,
+                            api_error_to_toast(&e, "saving recipe equipment"),
+                        );
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_item_input = {
+        let equipment_state = equipment_state.clone();
+        Callback::from(move |(index, e): (usize, InputEvent)| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut equipment = (*equipment_state).clone();
+            equipment[index] = input.value();
+            equipment_state.set(equipment);
+        })
+    };
+
+    let on_delete = {
+        let equipment_state = equipment_state.clone();
+        Callback::from(move |index: usize| {
+            let mut equipment = (*equipment_state).clone();
+            equipment.remove(index);
+            equipment_state.set(equipment);
+        })
+    };
+
+    let on_add = {
+        let equipment_state = equipment_state.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut equipment = (*equipment_state).clone();
+            equipment.push(String::new());
+            equipment_state.set(equipment);
+        })
+    };
+
+    html! {
+        <Modal title={"Edit Equipment"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="flex flex-col gap-2">
+                { for equipment_state.iter().enumerate().map(|(index, item)| html!{
+                    <div class="flex gap-2">
+                        <input
+                            class="input input-bordered w-full"
+                            oninput={on_item_input.reform(move |e| (index, e))}
+                            value={item.clone()}
+                            type="text"
+                            placeholder="e.g. stand mixer"
+                        />
+                        <button class="btn" type="button" onclick={on_delete.reform(move |_| index)}>{"X"}</button>
+                    </div>
+                })}
+                <button class="btn w-full" onclick={on_add} type="button">{"Add Equipment"}</button>
+            </div>
+        </Modal>
+    }
+}
@@ -1,11 +1,15 @@
+use crate::components::input::FractionalNumberInput;
 use crate::contexts::prelude::push_toast;
 use crate::core::handlers::{api_error_to_toast, logout_on_401};
 use crate::modals::Modal;
 use crate::{
     contexts::{login::use_login, prelude::use_toasts},
-    core::types::recipe::{Info, InfoYields, UpdateRecipe},
+    core::{
+        duration,
+        types::recipe::{Info, InfoYields, Pan, PanShape, UpdateInfo, UpdateRecipe},
+    },
 };
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -44,17 +48,24 @@ pub struct YieldInputProps {
 pub fn yield_input(props: &YieldInputProps) -> Html {
     let yield_state = use_state(|| props.yields.clone());
 
+    // the value is required, so being empty is shown as invalid and fails to save
     let on_value_input = {
         let onchange_callback = props.onchange.clone();
         let yield_state = yield_state.clone();
-        Callback::from(move |e: InputEvent| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            let v = match input.value().parse::<usize>() {
-                Ok(v) => v,
-                Err(_) => return,
-            };
+        Callback::from(move |value: Option<f32>| {
+            let mut yield_v = (*yield_state).clone();
+            yield_v.value = value.unwrap_or_default();
+            yield_state.set(yield_v.clone());
+            onchange_callback.emit(yield_v);
+        })
+    };
+
+    let on_value_max_input = {
+        let onchange_callback = props.onchange.clone();
+        let yield_state = yield_state.clone();
+        Callback::from(move |value: Option<f32>| {
             let mut yield_v = (*yield_state).clone();
-            yield_v.value = v;
+            yield_v.value_max = value;
             yield_state.set(yield_v.clone());
             onchange_callback.emit(yield_v);
         })
@@ -76,11 +87,20 @@ pub fn yield_input(props: &YieldInputProps) -> Html {
         <div class="flex">
             <label class="input-group w-auto">
                 <span>{"Amount"}</span>
-                <input
-                    class="input input-bordered w-24"
+                <FractionalNumberInput
+                    classes="input-bordered w-24"
                     oninput={on_value_input}
-                    value={(*yield_state).clone().value.to_string()}
-                    type="number" min=1 required=true
+                    value={Some(yield_state.value)}
+                    required=true
+                />
+            </label>
+            <label class="input-group w-auto">
+                <span>{"To"}</span>
+                <FractionalNumberInput
+                    classes="input-bordered w-24"
+                    oninput={on_value_max_input}
+                    value={yield_state.value_max}
+                    placeholder="optional"
                 />
             </label>
             <label class="input-group">
@@ -97,6 +117,175 @@ pub fn yield_input(props: &YieldInputProps) -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct DurationInputProps {
+    pub label: &'static str,
+    /// in minutes
+    pub minutes: Option<u32>,
+    /// emitted with the minutes when the text is a duration, or none when it is empty
+    pub onchange: Callback<Option<u32>>,
+}
+
+/// A duration written as "1h 30m", "90 min", "1:30" or "PT1H30M"
+#[function_component(DurationInput)]
+pub fn duration_input(props: &DurationInputProps) -> Html {
+    let text_state = use_state(|| props.minutes.map(duration::format).unwrap_or_default());
+    let minutes = duration::parse(&text_state);
+    let is_invalid = !text_state.trim().is_empty() && minutes.is_none();
+
+    let on_input = {
+        let onchange_callback = props.onchange.clone();
+        let text_state = text_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let text = input.value();
+            match duration::parse(&text) {
+                Some(minutes) => onchange_callback.emit(Some(minutes)),
+                None if text.trim().is_empty() => onchange_callback.emit(None),
+                None => {}
+            }
+            text_state.set(text);
+        })
+    };
+
+    html! {
+        <div class="form-control">
+            <label class="input-group">
+                <span class="w-32 flex-none">{props.label}</span>
+                <input
+                    class={classes!("input", "input-bordered", "w-full", is_invalid.then_some("input-error"))}
+                    oninput={on_input}
+                    value={(*text_state).clone()}
+                    type="text"
+                    placeholder="e.g. 1h 30m"
+                />
+            </label>
+            <label class="label py-1">
+                <span class={classes!("label-text-alt", is_invalid.then_some("text-error"))}>
+                    {match (minutes, is_invalid) {
+                        (_, true) => "Not a duration, try 1h 30m or 90 min".to_owned(),
+                        (Some(minutes), false) => duration::format(minutes),
+                        (None, false) => "".to_owned(),
+                    }}
+                </span>
+            </label>
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PanField {
+    Width,
+    Length,
+    Depth,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PanInputProps {
+    pub pan: Option<Pan>,
+    pub onchange: Callback<Option<Pan>>,
+    /// whether "No pan" can be chosen
+    #[prop_or_default]
+    pub is_optional: bool,
+}
+
+/// The shape & size of a pan, in cm
+#[function_component(PanInput)]
+pub fn pan_input(props: &PanInputProps) -> Html {
+    let pan_state = use_state(|| props.pan.clone());
+
+    let on_shape_change = {
+        let onchange_callback = props.onchange.clone();
+        let pan_state = pan_state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            let shape = PanShape::ALL
+                .into_iter()
+                .find(|shape| shape.label() == input.value());
+            let pan = shape.map(|shape| Pan {
+                shape,
+                ..(*pan_state).clone().unwrap_or_default()
+            });
+            pan_state.set(pan.clone());
+            onchange_callback.emit(pan);
+        })
+    };
+
+    let on_size_input = {
+        let onchange_callback = props.onchange.clone();
+        let pan_state = pan_state.clone();
+        Callback::from(move |(field, value): (PanField, Option<f32>)| {
+            let mut pan = (*pan_state).clone().unwrap_or_default();
+            match field {
+                PanField::Width => pan.width = value.unwrap_or_default(),
+                PanField::Length => pan.length = value,
+                PanField::Depth => pan.depth = value,
+            }
+            pan_state.set(Some(pan.clone()));
+            onchange_callback.emit(Some(pan));
+        })
+    };
+
+    let shape = pan_state.as_ref().map(|v| v.shape);
+    html! {
+        <div class="flex flex-col gap-2">
+            <select class="select select-bordered w-full" onchange={on_shape_change} aria-label="Pan Shape">
+                if props.is_optional {
+                    <option value="" selected={shape.is_none()}>{"No pan"}</option>
+                }
+                { for PanShape::ALL.iter().map(|v| html!{
+                    <option value={v.label()} selected={shape == Some(*v)}>{v.label()}</option>
+                })}
+            </select>
+            if let Some(pan) = &*pan_state {
+                <div class="flex flex-wrap gap-2">
+                    <label class="input-group w-auto">
+                        <span>{pan.shape.width_label()}</span>
+                        <FractionalNumberInput
+                            classes="input-bordered w-20"
+                            oninput={on_size_input.reform(|v| (PanField::Width, v))}
+                            value={Some(pan.width)}
+                            required=true
+                        />
+                        <span>{"cm"}</span>
+                    </label>
+                    if pan.shape.has_length() {
+                        <label class="input-group w-auto">
+                            <span>{"Length"}</span>
+                            <FractionalNumberInput
+                                classes="input-bordered w-20"
+                                oninput={on_size_input.reform(|v| (PanField::Length, v))}
+                                value={pan.length}
+                                required=true
+                            />
+                            <span>{"cm"}</span>
+                        </label>
+                    }
+                    <label class="input-group w-auto">
+                        <span>{"Depth"}</span>
+                        <FractionalNumberInput
+                            classes="input-bordered w-20"
+                            oninput={on_size_input.reform(|v| (PanField::Depth, v))}
+                            value={pan.depth}
+                            placeholder={if pan.shape.has_depth() { "" } else { "optional" }}
+                            required={pan.shape.has_depth()}
+                        />
+                        <span>{"cm"}</span>
+                    </label>
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimeField {
+    Prep,
+    Cook,
+    Rest,
+    Total,
+}
+
 #[derive(Properties, PartialEq)]
 pub struct EditInfoProps {
     pub id: AttrValue,
@@ -122,7 +311,22 @@ pub fn recipe_info(props: &EditInfoProps) -> Html {
             let api = login_ctx.http_api.clone().unwrap();
             let id = id.clone();
             let on_close_callback = on_close_callback.clone();
-            let info = (*info_state).clone();
+            let mut info = (*info_state).clone();
+            // a range must end above where it starts
+            if let Some(yields) = &mut info.yields {
+                yields.value_max = yields.value_max.filter(|max| *max > yields.value);
+            }
+            info.pan = info.pan.filter(|pan| pan.width > 0.0);
+            // a time of zero clears it, as does a pan with no width
+            let clearing_zero = |time: Option<u32>| Some(time.unwrap_or(0));
+            let update_info = UpdateInfo {
+                pan: Some(info.pan.clone().unwrap_or_default()),
+                prep_time: clearing_zero(info.prep_time),
+                cook_time: clearing_zero(info.cook_time),
+                rest_time: clearing_zero(info.rest_time),
+                total_time: clearing_zero(info.total_time),
+                ..info.clone()
+            };
             let is_loading_state = is_loading_state.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 is_loading_state.set(true);
@@ -130,7 +334,7 @@ pub fn recipe_info(props: &EditInfoProps) -> Html {
                     .patch_update_recipe(
                         id,
                         &UpdateRecipe {
-                            info: info.clone(),
+                            info: update_info,
                             ..Default::default()
                         },
                     )
@@ -165,6 +369,37 @@ pub fn recipe_info(props: &EditInfoProps) -> Html {
         })
     };
 
+    let on_time_change = {
+        let info_state = info_state.clone();
+        Callback::from(move |(field, minutes): (TimeField, Option<u32>)| {
+            let mut info = (*info_state).clone();
+            *match field {
+                TimeField::Prep => &mut info.prep_time,
+                TimeField::Cook => &mut info.cook_time,
+                TimeField::Rest => &mut info.rest_time,
+                TimeField::Total => &mut info.total_time,
+            } = minutes;
+            info_state.set(info);
+        })
+    };
+
+    let on_pan_change = {
+        let info_state = info_state.clone();
+        Callback::from(move |pan: Option<Pan>| {
+            let mut info = (*info_state).clone();
+            info.pan = pan;
+            info_state.set(info);
+        })
+    };
+
+    let total_time_hint = match (info_state.total_time, info_state.total_time()) {
+        (None, Some(minutes)) => format!(
+            "Leave empty to use the other times added up, {}",
+            duration::format(minutes)
+        ),
+        _ => "Leave empty to use the other times added up".to_owned(),
+    };
+
     html! {
         <Modal title={"Edit Info"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
             <h2 class="text-lg mb-2">{"Serving Size"}</h2>
@@ -172,6 +407,30 @@ pub fn recipe_info(props: &EditInfoProps) -> Html {
                 yields={info_state.yields.clone().unwrap_or_default()}
                 onchange={on_yield_change}
             />
+            <h2 class="text-lg mt-4 mb-2">{"Time"}</h2>
+            <DurationInput
+                label="Prep"
+                minutes={props.info.prep_time}
+                onchange={on_time_change.reform(|v| (TimeField::Prep, v))}
+            />
+            <DurationInput
+                label="Cook"
+                minutes={props.info.cook_time}
+                onchange={on_time_change.reform(|v| (TimeField::Cook, v))}
+            />
+            <DurationInput
+                label="Rest"
+                minutes={props.info.rest_time}
+                onchange={on_time_change.reform(|v| (TimeField::Rest, v))}
+            />
+            <DurationInput
+                label="Total"
+                minutes={props.info.total_time}
+                onchange={on_time_change.reform(|v| (TimeField::Total, v))}
+            />
+            <p class="text-xs opacity-70">{total_time_hint}</p>
+            <h2 class="text-lg mt-4 mb-2">{"Pan"}</h2>
+            <PanInput pan={props.info.pan.clone()} onchange={on_pan_change} is_optional=true />
         </Modal>
     }
 }
@@ -1,11 +1,20 @@
 use crate::components::input::FractionalNumberInput;
-use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::components::reorder::{drop_position_attribute, DragHandle};
+use crate::contexts::prelude::{push_toast, use_toasts, Toast};
+use crate::core::api::ApiError;
 use crate::core::handlers::{api_error_to_toast, logout_on_401};
-use crate::core::types::recipe::{UpdateIngredient, UpdateRecipe};
+use crate::core::ingredient_references;
+use crate::core::search::{self, index::SearchResult, use_search_index};
+use crate::core::sections::{self, ItemPosition, Keyed, Section};
+use crate::core::types::recipe::{
+    ensure_ingredient_ids, Step, UpdateIngredient, UpdateRecipe, UpdateStep,
+};
 use crate::modals::Modal;
+
+use super::section::{EditSection, SectionSelector};
 use crate::{contexts::login::use_login, core::types::recipe::Ingredient};
 use wasm_bindgen::JsCast;
-use web_sys::{EventTarget, HtmlInputElement};
+use web_sys::{EventTarget, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -42,91 +51,159 @@ pub fn unit_selector(props: &UnitSelectorProps) -> Html {
 
 #[derive(Properties, PartialEq)]
 pub struct EditIngredientProps {
-    pub len: usize,
-    pub index: usize,
+    pub position: ItemPosition,
     pub ingredient: Ingredient,
-    pub on_input: Callback<(usize, Ingredient)>,
-    pub on_delete: Callback<usize>,
+    /// names of every section, to move the ingredient between
+    pub sections: Vec<Option<String>>,
+    /// recipes that can be used as the ingredient
+    pub recipes: Vec<SearchResult>,
+    pub on_input: Callback<(ItemPosition, Ingredient)>,
+    pub on_move_section: Callback<(ItemPosition, usize)>,
+    pub on_delete: Callback<ItemPosition>,
+    /// whether an ingredient being dragged would be dropped before this one
+    pub is_drop_target: bool,
+    pub on_drag: Callback<Option<ItemPosition>>,
+    pub on_drop: Callback<(ItemPosition, ItemPosition)>,
+    pub on_move: Callback<(ItemPosition, bool)>,
 }
 
 #[function_component(EditIngredient)]
 pub fn recipe_ingredient(props: &EditIngredientProps) -> Html {
-    let ingredient_state = use_state(|| props.ingredient.clone());
-
     let on_delete = {
         let on_delete_callback = props.on_delete.clone();
-        let index = props.index;
+        let position = props.position;
         Callback::from(move |_| {
-            on_delete_callback.emit(index);
+            on_delete_callback.emit(position);
+        })
+    };
+
+    let on_section_change = {
+        let on_move_section_callback = props.on_move_section.clone();
+        let position = props.position;
+        Callback::from(move |section: usize| {
+            on_move_section_callback.emit((position, section));
         })
     };
 
     let on_name_input = {
         let on_input_callback = props.on_input.clone();
-        let index = props.index;
-        let ingredient_state = ingredient_state.clone();
+        let position = props.position;
+        let ingredient = props.ingredient.clone();
         Callback::from(move |e: InputEvent| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
             if let Some(input) = input {
-                let mut ingredient = (*ingredient_state).clone();
+                let mut ingredient = ingredient.clone();
                 ingredient.name = input.value();
-                ingredient_state.set(ingredient.clone());
-                on_input_callback.emit((index, ingredient));
+                on_input_callback.emit((position, ingredient));
             }
         })
     };
 
     let on_amount_input = {
         let on_input_callback = props.on_input.clone();
-        let index = props.index;
-        let ingredient_state = ingredient_state.clone();
+        let position = props.position;
+        let ingredient = props.ingredient.clone();
         Callback::from(move |new_value| {
-            let mut ingredient = (*ingredient_state).clone();
+            let mut ingredient = ingredient.clone();
             ingredient.amount = new_value;
-            ingredient_state.set(ingredient.clone());
-            on_input_callback.emit((index, ingredient));
+            on_input_callback.emit((position, ingredient));
+        })
+    };
+
+    let on_amount_max_input = {
+        let on_input_callback = props.on_input.clone();
+        let position = props.position;
+        let ingredient = props.ingredient.clone();
+        Callback::from(move |new_value| {
+            let mut ingredient = ingredient.clone();
+            ingredient.amount_max = new_value;
+            on_input_callback.emit((position, ingredient));
+        })
+    };
+
+    let on_optional_change = {
+        let on_input_callback = props.on_input.clone();
+        let position = props.position;
+        let ingredient = props.ingredient.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut ingredient = ingredient.clone();
+            ingredient.optional = input.checked();
+            on_input_callback.emit((position, ingredient));
         })
     };
 
     let on_unit_input = {
         let on_input_callback = props.on_input.clone();
-        let index = props.index;
-        let ingredient_state = ingredient_state.clone();
+        let position = props.position;
+        let ingredient = props.ingredient.clone();
         Callback::from(move |e: InputEvent| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
             if let Some(input) = input {
-                let mut ingredient = (*ingredient_state).clone();
+                let mut ingredient = ingredient.clone();
                 ingredient.unit_type = input.value();
-                ingredient_state.set(ingredient.clone());
-                on_input_callback.emit((index, ingredient));
+                on_input_callback.emit((position, ingredient));
+            }
+        })
+    };
+
+    let on_recipe_change = {
+        let on_input_callback = props.on_input.clone();
+        let position = props.position;
+        let recipes = props.recipes.clone();
+        let ingredient = props.ingredient.clone();
+        Callback::from(move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+            if let Some(input) = input {
+                let mut ingredient = ingredient.clone();
+                ingredient.recipe_id = recipes
+                    .iter()
+                    .find(|recipe| recipe.id == input.value())
+                    .map(|recipe| {
+                        if ingredient.name.is_empty() {
+                            ingredient.name = recipe.title.clone();
+                        }
+                        recipe.id.clone()
+                    });
+                on_input_callback.emit((position, ingredient));
             }
         })
     };
 
     let on_description_input = {
         let on_input_callback = props.on_input.clone();
-        let index = props.index;
+        let position = props.position;
+        let ingredient = props.ingredient.clone();
         Callback::from(move |e: InputEvent| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
             if let Some(input) = input {
-                let mut ingredient = (*ingredient_state).clone();
+                let mut ingredient = ingredient.clone();
                 if input.value().is_empty() {
                     ingredient.description = None;
                 } else {
                     ingredient.description = Some(input.value());
                 }
-                ingredient_state.set(ingredient.clone());
-                on_input_callback.emit((index, ingredient));
+                on_input_callback.emit((position, ingredient));
             }
         })
     };
 
     html! {
-        <div class="mb-4 p-4 rounded bg-base-200">
-            <div class="grid grid-cols-[auto_3rem] gap-2 mb-2">
+        <div
+            class={classes!("mb-4", "p-4", "rounded", "bg-base-200", "border-t-4", if props.is_drop_target { "border-primary" } else { "border-transparent" })}
+            data-drop-position={drop_position_attribute(props.position)}
+        >
+            <div class="grid grid-cols-[auto_1fr_auto_3rem] gap-2 mb-2">
+                <DragHandle
+                    position={props.position}
+                    on_drag={props.on_drag.clone()}
+                    on_drop={props.on_drop.clone()}
+                    on_move={props.on_move.clone()}
+                />
                 <input
                     class="input input-bordered w-full"
                     oninput={on_name_input}
@@ -135,15 +212,30 @@ pub fn recipe_ingredient(props: &EditIngredientProps) -> Html {
                     placeholder="name..."
                     required=true
                 />
+                if props.sections.len() > 1 {
+                    <SectionSelector
+                        sections={props.sections.clone()}
+                        selected={props.position.0}
+                        on_change={on_section_change}
+                    />
+                } else {
+                    <span></span>
+                }
                 <button class="btn" type="button" onclick={on_delete}>{"X"}</button>
             </div>
-            <div class="grid grid-cols-[8rem_auto] gap-2 mb-2">
+            // amounts can be left empty, e.g. for salt "to taste", and given a range, e.g. "2–3"
+            <div class="grid grid-cols-[6rem_6rem_auto] gap-2 mb-2">
                 <FractionalNumberInput
                     classes="input-bordered w-full"
                     oninput={on_amount_input}
                     value={props.ingredient.amount}
                     placeholder="amount..."
-                    required=true
+                />
+                <FractionalNumberInput
+                    classes="input-bordered w-full"
+                    oninput={on_amount_max_input}
+                    value={props.ingredient.amount_max}
+                    placeholder="to..."
                 />
                 <input
                     class="input input-bordered w-full"
@@ -152,10 +244,27 @@ pub fn recipe_ingredient(props: &EditIngredientProps) -> Html {
                     type="text"
                     placeholder="unit..."
                     list="units"
-                    required=true
                 />
                 <UnitSelector id="units" />
             </div>
+            <label class="label cursor-pointer justify-start gap-2 mb-2">
+                <input
+                    type="checkbox"
+                    class="checkbox"
+                    checked={props.ingredient.optional}
+                    onchange={on_optional_change}
+                />
+                <span class="label-text">{"Optional"}</span>
+            </label>
+            <select class="select select-bordered w-full mb-2" onchange={on_recipe_change}>
+                <option value="" selected={props.ingredient.recipe_id.is_none()}>{"Not a recipe"}</option>
+                { for props.recipes.iter().map(|recipe| html!{
+                    <option
+                        value={recipe.id.clone()}
+                        selected={props.ingredient.recipe_id.as_ref() == Some(&recipe.id)}
+                    >{format!("Recipe: {}", recipe.title)}</option>
+                })}
+            </select>
             <input
                 class="textarea textarea-bordered w-full"
                 oninput={on_description_input}
@@ -171,28 +280,72 @@ pub fn recipe_ingredient(props: &EditIngredientProps) -> Html {
 pub struct EditIngredientsProps {
     pub id: String,
     pub ingredients: Vec<Ingredient>,
-    pub onclose: Callback<Option<Vec<Ingredient>>>,
+    /// steps of the recipe, which may refer to the ingredients
+    pub steps: Vec<Step>,
+    /// emitted with the ingredients and steps once saved,
+    /// as steps referring to removed or renamed ingredients are updated
+    pub onclose: Callback<Option<(Vec<Ingredient>, Vec<Step>)>>,
 }
 
 #[function_component(EditIngredients)]
 pub fn recipe_ingredients(props: &EditIngredientsProps) -> Html {
     let login_ctx = use_login().unwrap();
     let toasts_ctx = use_toasts().unwrap();
-    let ingredients_state = use_state(|| props.ingredients.clone());
+    let sections_state = use_state(|| {
+        let ingredients: Vec<Keyed<Ingredient>> =
+            props.ingredients.iter().cloned().map(Keyed::new).collect();
+        let mut sections = sections::group(&ingredients);
+        if sections.is_empty() {
+            sections.push(Section::new(None));
+        }
+        sections
+    });
     let is_loading_state = use_state(bool::default);
+    let drop_target_state = use_state(|| Option::<ItemPosition>::None);
+    use_search_index();
+    // a recipe cannot be used as an ingredient of itself
+    let recipes: Vec<SearchResult> = search::all()
+        .into_iter()
+        .filter(|recipe| recipe.id != props.id)
+        .collect();
+    let section_names: Vec<Option<String>> =
+        sections_state.iter().map(|v| v.name.clone()).collect();
 
     let on_save = {
         let id = props.id.to_string();
         let on_close_callback = props.onclose.clone();
-        let ingredients_state = ingredients_state.clone();
+        let sections_state = sections_state.clone();
+        let old_steps = props.steps.clone();
         Callback::from(move |_| {
             let login_ctx = login_ctx.clone();
             let toasts_ctx = toasts_ctx.clone();
+            if let Err(message) = sections::check(&sections_state) {
+                push_toast(&toasts_ctx, Toast { message });
+                return;
+            }
             let api = login_ctx.http_api.clone().unwrap();
             let id = id.clone();
             let on_close_callback = on_close_callback.clone();
             let is_loading_state = is_loading_state.clone();
-            let ingredients = (*ingredients_state).clone();
+            let mut ingredients: Vec<Ingredient> = sections::flatten(&sections_state)
+                .into_iter()
+                .map(|v| v.item)
+                .collect();
+            ensure_ingredient_ids(&mut ingredients);
+            // keep steps from referring to ingredients that were removed or renamed
+            let steps: Vec<Step> = old_steps
+                .iter()
+                .map(|step| {
+                    let mut step = step.clone();
+                    step.description =
+                        ingredient_references::update_references(&step.description, &ingredients);
+                    step.ingredients.retain(|id| {
+                        ingredient_references::find_ingredient(&ingredients, id).is_some()
+                    });
+                    step
+                })
+                .collect();
+            let is_steps_changed = steps != old_steps;
             wasm_bindgen_futures::spawn_local(async move {
                 is_loading_state.set(true);
                 let result = api
@@ -200,16 +353,10 @@ pub fn recipe_ingredients(props: &EditIngredientsProps) -> Html {
                         id,
                         &UpdateRecipe {
                             ingredients: Some(
-                                ingredients
-                                    .iter()
-                                    .map(|i| UpdateIngredient {
-                                        name: Some(i.name.clone()),
-                                        amount: Some(i.amount),
-                                        unit_type: Some(i.unit_type.clone()),
-                                        description: i.description.clone(),
-                                    })
-                                    .collect(),
+                                ingredients.iter().map(UpdateIngredient::from).collect(),
                             ),
+                            steps: is_steps_changed
+                                .then(|| steps.iter().map(UpdateStep::from).collect()),
                             ..Default::default()
                         },
                     )
@@ -217,7 +364,16 @@ pub fn recipe_ingredients(props: &EditIngredientsProps) -> Html {
                 is_loading_state.set(false);
                 match result {
                     Ok(_) => {
-                        on_close_callback.emit(Some(ingredients));
+                        on_close_callback.emit(Some((ingredients, steps)));
+                    }
+                    Err(ApiError::Response(e)) if e.status_code == 409 => {
+                        push_toast(
+                            &toasts_ctx,
+                            Toast {
+                                message: "A recipe used as an ingredient already uses this recipe"
+                                    .to_owned(),
+                            },
+                        );
                     }
                     Err(e) => {
                         push_toast(
@@ -239,52 +395,162 @@ pub fn recipe_ingredients(props: &EditIngredientsProps) -> Html {
     };
 
     let on_ingredient_input = {
-        let ingredients_state = ingredients_state.clone();
-        Callback::from(move |(index, ingredient): (usize, Ingredient)| {
-            let mut ingredients = (*ingredients_state).clone();
-            ingredients[index] = ingredient;
-            ingredients_state.set(ingredients);
+        let sections_state = sections_state.clone();
+        Callback::from(
+            move |((section, index), ingredient): (ItemPosition, Ingredient)| {
+                let mut sections = (*sections_state).clone();
+                sections[section].items[index].item = ingredient;
+                sections_state.set(sections);
+            },
+        )
+    };
+
+    let on_move_ingredient_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(position, to_section): (ItemPosition, usize)| {
+            let mut sections = (*sections_state).clone();
+            sections::move_item(&mut sections, position, to_section);
+            sections_state.set(sections);
+        })
+    };
+
+    let on_ingredient_drag = {
+        let drop_target_state = drop_target_state.clone();
+        Callback::from(move |target: Option<ItemPosition>| drop_target_state.set(target))
+    };
+
+    let on_ingredient_drop = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(from, to): (ItemPosition, ItemPosition)| {
+            let mut sections = (*sections_state).clone();
+            sections::move_item_to(&mut sections, from, to);
+            sections_state.set(sections);
+        })
+    };
+
+    let on_ingredient_move = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(from, is_up): (ItemPosition, bool)| {
+            let mut sections = (*sections_state).clone();
+            sections::move_item_by_one(&mut sections, from, is_up);
+            sections_state.set(sections);
         })
     };
 
     let on_delete_ingredient = {
-        let ingredients_state = ingredients_state.clone();
-        Callback::from(move |index: usize| {
-            let mut ingredients = (*ingredients_state).clone();
-            ingredients.remove(index);
-            ingredients_state.set(ingredients);
+        let sections_state = sections_state.clone();
+        Callback::from(move |(section, index): ItemPosition| {
+            let mut sections = (*sections_state).clone();
+            sections[section].items.remove(index);
+            sections_state.set(sections);
         })
     };
 
     let on_add_ingredient = {
-        let ingredients_state = ingredients_state.clone();
-        Callback::from(move |_| {
-            let mut ingredients = (*ingredients_state).clone();
-            ingredients.push(Ingredient {
+        let sections_state = sections_state.clone();
+        Callback::from(move |section: usize| {
+            let mut sections = (*sections_state).clone();
+            sections[section].items.push(Keyed::new(Ingredient {
+                id: None,
                 name: String::from(""),
-                amount: 0.0,
+                amount: None,
+                amount_max: None,
                 unit_type: String::from(""),
+                optional: false,
                 description: None,
-            });
-            ingredients_state.set(ingredients);
+                recipe_id: None,
+                section: None,
+            }));
+            sections_state.set(sections);
+        })
+    };
+
+    let on_rename_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(section, name): (usize, Option<String>)| {
+            let mut sections = (*sections_state).clone();
+            sections[section].name = name;
+            sections_state.set(sections);
+        })
+    };
+
+    let on_section_move_up = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |index: usize| {
+            if index != 0 {
+                let mut sections = (*sections_state).clone();
+                sections.swap(index, index - 1);
+                sections_state.set(sections);
+            }
+        })
+    };
+
+    let on_section_move_down = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |index: usize| {
+            let mut sections = (*sections_state).clone();
+            if index != sections.len() - 1 {
+                sections.swap(index, index + 1);
+                sections_state.set(sections);
+            }
+        })
+    };
+
+    let on_delete_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |index: usize| {
+            let mut sections = (*sections_state).clone();
+            sections::remove_section(&mut sections, index);
+            sections_state.set(sections);
+        })
+    };
+
+    let on_add_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |_| {
+            let mut sections = (*sections_state).clone();
+            sections.push(Section::new(Some(format!("Part {}", sections.len() + 1))));
+            sections_state.set(sections);
         })
     };
 
     html! {
         <Modal title={"Edit Ingredients"} oncancel={on_cancel} onsave={on_save}>
             <div class="max-h-[50vh] lg:max-h-[60vh] overflow-y-auto">
-                <div>
-                    { for (*ingredients_state).clone().iter().enumerate().map(|(i, ingredient)| {
-                        html! {<EditIngredient
-                            len={(*ingredients_state).clone().len()}
-                            index={i}
-                            ingredient={ingredient.clone()}
-                            on_input={on_ingredient_input.clone()}
-                            on_delete={on_delete_ingredient.clone()}
-                        />}
-                    })}
-                </div>
-                <button class="btn w-full" onclick={on_add_ingredient} type="button">{"Add Ingredient"}</button>
+                { for sections_state.iter().enumerate().map(|(section_index, section)| {
+                    html! {
+                        <EditSection
+                            len={sections_state.len()}
+                            index={section_index}
+                            name={section.name.clone()}
+                            add_label="Add Ingredient"
+                            on_rename={on_rename_section.clone()}
+                            on_move_up={on_section_move_up.clone()}
+                            on_move_down={on_section_move_down.clone()}
+                            on_delete={on_delete_section.clone()}
+                            on_add={on_add_ingredient.clone()}
+                            is_drop_target={*drop_target_state == Some((section_index, section.items.len()))}
+                        >
+                            { for section.items.iter().enumerate().map(|(i, ingredient)| {
+                                html! {<EditIngredient
+                                    key={ingredient.key}
+                                    position={(section_index, i)}
+                                    ingredient={ingredient.item.clone()}
+                                    sections={section_names.clone()}
+                                    recipes={recipes.clone()}
+                                    on_input={on_ingredient_input.clone()}
+                                    on_move_section={on_move_ingredient_section.clone()}
+                                    on_delete={on_delete_ingredient.clone()}
+                                    is_drop_target={*drop_target_state == Some((section_index, i))}
+                                    on_drag={on_ingredient_drag.clone()}
+                                    on_drop={on_ingredient_drop.clone()}
+                                    on_move={on_ingredient_move.clone()}
+                                />}
+                            })}
+                        </EditSection>
+                    }
+                })}
+                <button class="btn w-full" onclick={on_add_section} type="button">{"Add Section"}</button>
             </div>
         </Modal>
     }
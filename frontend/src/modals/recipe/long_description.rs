@@ -1,3 +1,4 @@
+use crate::components::markdown::{Markdown, PreviewTabs};
 use crate::contexts::prelude::{push_toast, use_toasts};
 use crate::core::handlers::{api_error_to_toast, logout_on_401};
 use crate::{contexts::login::use_login, core::types::recipe::UpdateRecipe};
@@ -87,7 +88,14 @@ pub fn recipe_long_description(props: &EditLongDescriptionProps) -> Html {
 
     html! {
         <Modal title={"Edit Notes"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
-            <textarea oninput={on_description_input} value={(*description_state).clone()} class="my-4 input input-bordered w-full h-56"/>
+            <PreviewTabs preview={html!{
+                <div class="my-4 h-56 overflow-y-auto">
+                    <Markdown text={(*description_state).clone().unwrap_or_default()}/>
+                </div>
+            }}>
+                <textarea oninput={on_description_input} value={(*description_state).clone()} class="my-4 input input-bordered w-full h-56"/>
+                <p class="text-xs opacity-70">{"Supports Markdown, such as *emphasis*, **bold**, lists, links and # headings"}</p>
+            </PreviewTabs>
         </Modal>
     }
 }
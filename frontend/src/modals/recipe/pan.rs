@@ -0,0 +1,63 @@
+use yew::prelude::*;
+
+use super::info::PanInput;
+use crate::{
+    core::{
+        pan,
+        types::recipe::{Ingredient, Pan},
+    },
+    modals::Modal,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct ConvertPanProps {
+    /// the pan the recipe is made in
+    pub pan: Pan,
+    pub ingredients: Vec<Ingredient>,
+    pub onclose: Callback<()>,
+}
+
+/// Scale a recipe's ingredients to be made in another pan
+#[function_component(ConvertPan)]
+pub fn convert_pan(props: &ConvertPanProps) -> Html {
+    let to_state = use_state(|| props.pan.clone());
+
+    let on_to_change = {
+        let to_state = to_state.clone();
+        Callback::from(move |pan: Option<Pan>| {
+            if let Some(pan) = pan {
+                to_state.set(pan);
+            }
+        })
+    };
+
+    let scale = pan::scale(&props.pan, &to_state);
+    html! {
+        <Modal title={"Convert Pan"} oncancel={props.onclose.clone()}>
+            <p class="mb-2">{format!("Made in a {} pan, to make in:", pan::summary(&props.pan))}</p>
+            <PanInput pan={Some(props.pan.clone())} onchange={on_to_change} />
+            <div class="mt-4">
+                {match scale {
+                    Ok(scale) => html! {
+                        <>
+                        <p class="mb-2 font-bold">
+                            {format!("Multiply amounts by {}", (scale * 100.0).round() / 100.0)}
+                        </p>
+                        <table class="table table-compact table-zebra w-full">
+                            <tbody>
+                                { for props.ingredients.iter().map(|ingredient| html!{
+                                    <tr>
+                                        <td class="whitespace-normal">{ingredient.scaled_quantity(scale)}</td>
+                                        <td class="whitespace-normal">{&ingredient.name}</td>
+                                    </tr>
+                                })}
+                            </tbody>
+                        </table>
+                        </>
+                    },
+                    Err(err) => html! { <p class="text-error">{err.to_string()}</p> },
+                }}
+            </div>
+        </Modal>
+    }
+}
@@ -0,0 +1,117 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    components::rating::Rating, contexts::login::use_login, core::types::recipe::UpdateRecipe,
+};
+
+use crate::modals::Modal;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+/// The saved rating & review
+pub type Review = (Option<u8>, Option<String>);
+
+#[derive(Properties, PartialEq)]
+pub struct EditReviewProps {
+    pub id: String,
+    pub rating: Option<u8>,
+    pub review: Option<AttrValue>,
+    pub onclose: Callback<Option<Review>>,
+}
+
+#[function_component(EditReview)]
+pub fn recipe_review(props: &EditReviewProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let rating_state = use_state(|| props.rating.unwrap_or_default());
+    let review_state = use_state(|| props.review.clone().unwrap_or_default().to_string());
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let id = props.id.to_string();
+        let on_close_callback = props.onclose.clone();
+        let rating_state = rating_state.clone();
+        let review_state = review_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let id = id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let rating = *rating_state;
+            let review = review_state.trim().to_owned();
+            let is_loading_state = is_loading_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api
+                    .patch_update_recipe(
+                        id,
+                        &UpdateRecipe {
+                            rating: Some(rating),
+                            review: Some(review.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(_) => {
+                        on_close_callback.emit(Some((
+                            (rating != 0).then_some(rating),
+                            (!review.is_empty()).then_some(review),
+                        )));
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "saving recipe review"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_rating_change = {
+        let rating_state = rating_state.clone();
+        Callback::from(move |rating: u8| rating_state.set(rating))
+    };
+
+    let on_review_input = {
+        let review_state = review_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            review_state.set(input.value());
+        })
+    };
+
+    html! {
+        <Modal title={"Edit Review"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="form-control mb-2">
+                <span class="label">{"Rating"}</span>
+                <Rating
+                    name="edit-review-rating"
+                    rating={Some(*rating_state)}
+                    onchange={on_rating_change}
+                    classes="rating-lg"
+                />
+            </div>
+            <div class="form-control">
+                <label class="label" for="edit-review-text">{"Review"}</label>
+                <textarea
+                    id="edit-review-text"
+                    oninput={on_review_input}
+                    value={(*review_state).clone()}
+                    class="textarea textarea-bordered w-full"
+                    maxlength="280"
+                />
+            </div>
+        </Modal>
+    }
+}
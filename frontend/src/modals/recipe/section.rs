@@ -0,0 +1,135 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::components::reorder::drop_position_attribute;
+
+#[derive(Properties, PartialEq)]
+pub struct EditSectionProps {
+    pub len: usize,
+    pub index: usize,
+    pub name: Option<String>,
+    /// label of the button adding an item to the section
+    pub add_label: &'static str,
+    pub on_rename: Callback<(usize, Option<String>)>,
+    pub on_move_up: Callback<usize>,
+    pub on_move_down: Callback<usize>,
+    pub on_delete: Callback<usize>,
+    pub on_add: Callback<usize>,
+    /// whether an item being dragged would be dropped at the end of the section
+    #[prop_or_default]
+    pub is_drop_target: bool,
+    pub children: Children,
+}
+
+/// A named group of items being edited, such as "For the dough"
+#[function_component(EditSection)]
+pub fn edit_section(props: &EditSectionProps) -> Html {
+    let on_name_input = {
+        let on_rename_callback = props.on_rename.clone();
+        let index = props.index;
+        Callback::from(move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            if let Some(input) = input {
+                let name = Some(input.value()).filter(|v| !v.is_empty());
+                on_rename_callback.emit((index, name));
+            }
+        })
+    };
+
+    let on_move_up = {
+        let on_move_up_callback = props.on_move_up.clone();
+        let index = props.index;
+        Callback::from(move |_| on_move_up_callback.emit(index))
+    };
+
+    let on_move_down = {
+        let on_move_down_callback = props.on_move_down.clone();
+        let index = props.index;
+        Callback::from(move |_| on_move_down_callback.emit(index))
+    };
+
+    let on_delete = {
+        let on_delete_callback = props.on_delete.clone();
+        let index = props.index;
+        Callback::from(move |_| on_delete_callback.emit(index))
+    };
+
+    let on_add = {
+        let on_add_callback = props.on_add.clone();
+        let index = props.index;
+        Callback::from(move |_| on_add_callback.emit(index))
+    };
+
+    html! {
+        <fieldset
+            class={classes!("mb-4", "p-2", "rounded", "border", if props.is_drop_target { "border-primary" } else { "border-base-300" })}
+            data-drop-position={drop_position_attribute((props.index, props.children.len()))}
+        >
+            <div class="flex mb-2">
+                <input
+                    class="input input-bordered w-full mr-2 font-bold"
+                    oninput={on_name_input}
+                    value={props.name.clone().unwrap_or_default()}
+                    type="text"
+                    placeholder="section name, e.g. For the dough..."
+                    aria-label="Section Name"
+                />
+                <div class="btn-group">
+                    <button
+                        type="button"
+                        class={classes!("btn", (props.index == 0).then_some("btn-disabled"))}
+                        onclick={on_move_up}
+                    >{"Up"}</button>
+                    <button
+                        type="button"
+                        class={classes!("btn", (props.index + 1 == props.len).then_some("btn-disabled"))}
+                        onclick={on_move_down}
+                    >{"Down"}</button>
+                    <button
+                        type="button"
+                        class="btn"
+                        onclick={on_delete}
+                        title="Remove the section, keeping its items"
+                    >{"X"}</button>
+                </div>
+            </div>
+            { for props.children.iter() }
+            <button class="btn btn-sm w-full" type="button" onclick={on_add}>{props.add_label}</button>
+        </fieldset>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SectionSelectorProps {
+    /// names of every section
+    pub sections: Vec<Option<String>>,
+    pub selected: usize,
+    pub on_change: Callback<usize>,
+}
+
+/// Choose which section an item is in
+#[function_component(SectionSelector)]
+pub fn section_selector(props: &SectionSelectorProps) -> Html {
+    let on_change = {
+        let on_change_callback = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+            if let Some(index) = input.and_then(|input| input.value().parse().ok()) {
+                on_change_callback.emit(index);
+            }
+        })
+    };
+
+    html! {
+        <select class="select select-bordered mr-2" onchange={on_change} aria-label="Section">
+            { for props.sections.iter().enumerate().map(|(i, name)| html!{
+                <option value={i.to_string()} selected={i == props.selected}>
+                    {name.clone().unwrap_or_else(|| format!("Section {}", i + 1))}
+                </option>
+            })}
+        </select>
+    }
+}
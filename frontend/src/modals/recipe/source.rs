@@ -0,0 +1,123 @@
+use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{contexts::login::use_login, core::types::recipe::UpdateRecipe};
+
+use crate::modals::Modal;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// The saved source name & url
+pub type Source = (Option<String>, Option<String>);
+
+#[derive(Properties, PartialEq)]
+pub struct EditSourceProps {
+    pub id: String,
+    pub name: Option<AttrValue>,
+    pub url: Option<AttrValue>,
+    pub onclose: Callback<Option<Source>>,
+}
+
+#[function_component(EditSource)]
+pub fn recipe_source(props: &EditSourceProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let name_state = use_state(|| props.name.clone().unwrap_or_default().to_string());
+    let url_state = use_state(|| props.url.clone().unwrap_or_default().to_string());
+    let is_loading_state = use_state(bool::default);
+
+    let on_save = {
+        let id = props.id.to_string();
+        let on_close_callback = props.onclose.clone();
+        let name_state = name_state.clone();
+        let url_state = url_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        Callback::from(move |_| {
+            let login_ctx = login_ctx.clone();
+            let toasts_ctx = toasts_ctx.clone();
+            let api = login_ctx.http_api.clone().unwrap();
+            let id = id.clone();
+            let on_close_callback = on_close_callback.clone();
+            let name = name_state.trim().to_owned();
+            let url = url_state.trim().to_owned();
+            let is_loading_state = is_loading_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                is_loading_state.set(true);
+                let result = api
+                    .patch_update_recipe(
+                        id,
+                        &UpdateRecipe {
+                            // empty values clear them
+                            source_name: Some(name.clone()),
+                            source_url: Some(url.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+                is_loading_state.set(false);
+                match result {
+                    Ok(_) => {
+                        on_close_callback.emit(Some((
+                            (!name.is_empty()).then_some(name),
+                            (!url.is_empty()).then_some(url),
+                        )));
+                    }
+                    Err(e) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&e, "saving recipe source"));
+                        logout_on_401(&e, &login_ctx);
+                    }
+                };
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_name_input = {
+        let name_state = name_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name_state.set(input.value());
+        })
+    };
+
+    let on_url_input = {
+        let url_state = url_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            url_state.set(input.value());
+        })
+    };
+
+    html! {
+        <Modal title={"Edit Source"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <div class="form-control">
+                <label class="label" for="edit-source-name">{"Name"}</label>
+                <input
+                    id="edit-source-name"
+                    oninput={on_name_input}
+                    value={(*name_state).clone()}
+                    class="input input-bordered w-full"
+                    type="text"
+                    maxlength="100"
+                    placeholder="e.g. a cookbook, website or person"
+                />
+            </div>
+            <div class="form-control">
+                <label class="label" for="edit-source-url">{"Link"}</label>
+                <input
+                    id="edit-source-url"
+                    oninput={on_url_input}
+                    value={(*url_state).clone()}
+                    class="input input-bordered w-full"
+                    type="url"
+                    placeholder="https://..."
+                />
+            </div>
+        </Modal>
+    }
+}
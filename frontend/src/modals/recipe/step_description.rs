@@ -0,0 +1,221 @@
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use crate::core::ingredient_references;
+use crate::core::types::recipe::Ingredient;
+
+/// Most ingredients suggested at once
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Text typed after an `@`, to find an ingredient to refer to
+#[derive(Debug, Clone, PartialEq)]
+struct Lookup {
+    /// byte index of the `@`
+    start: usize,
+    /// byte index of the end of the typed text
+    end: usize,
+    query: String,
+}
+
+/// The ingredient being looked up when the caret is at a byte index in the text, if any
+fn lookup_at(text: &str, caret: usize) -> Option<Lookup> {
+    let before = text.get(..caret)?;
+    let start = before.rfind('@')?;
+    let query = &before[start + 1..];
+    // not part of a word, such as an email address
+    let is_word_start = !before[..start].ends_with(char::is_alphanumeric);
+    // names can have spaces, but not span lines
+    if !is_word_start || query.contains('\n') || query.chars().count() > 30 {
+        return None;
+    }
+    Some(Lookup {
+        start,
+        end: caret,
+        query: query.to_owned(),
+    })
+}
+
+fn matching_ingredients<'a>(ingredients: &'a [Ingredient], query: &str) -> Vec<&'a Ingredient> {
+    let query = query.to_lowercase();
+    ingredients
+        .iter()
+        .filter(|v| v.id.is_some() && v.name.to_lowercase().contains(&query))
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// Byte index in the text of a position counted in UTF-16 code units, as the browser does
+fn byte_index(text: &str, utf16_index: u32) -> usize {
+    let mut count = 0;
+    for (i, c) in text.char_indices() {
+        if count >= utf16_index as usize {
+            return i;
+        }
+        count += c.len_utf16();
+    }
+    text.len()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct StepDescriptionInputProps {
+    pub value: String,
+    /// ingredients of the recipe, which can be referred to
+    pub ingredients: Vec<Ingredient>,
+    pub on_input: Callback<String>,
+}
+
+/// Text of a step, suggesting ingredients to refer to when typing `@`
+#[function_component(StepDescriptionInput)]
+pub fn step_description_input(props: &StepDescriptionInputProps) -> Html {
+    let textarea_ref = use_node_ref();
+    let lookup_state = use_state(|| Option::<Lookup>::None);
+    let selected_state = use_state(usize::default);
+    // where to put the caret once the text with an inserted reference is shown
+    let pending_caret_ref = use_mut_ref(|| Option::<(String, u32)>::None);
+
+    {
+        let textarea_ref = textarea_ref.clone();
+        let pending_caret_ref = pending_caret_ref.clone();
+        use_effect(move || {
+            if let Some(textarea) = textarea_ref.cast::<HtmlTextAreaElement>() {
+                let is_shown = matches!(&*pending_caret_ref.borrow(), Some((text, _)) if *text == textarea.value());
+                if is_shown {
+                    if let Some((_, caret)) = pending_caret_ref.take() {
+                        let _ = textarea.focus();
+                        let _ = textarea.set_selection_range(caret, caret);
+                    }
+                }
+            }
+        });
+    }
+
+    let suggestions: Rc<Vec<Ingredient>> = Rc::new(match &*lookup_state {
+        Some(lookup) => matching_ingredients(&props.ingredients, &lookup.query)
+            .into_iter()
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    });
+
+    let on_insert = {
+        let on_input_callback = props.on_input.clone();
+        let value = props.value.clone();
+        let lookup_state = lookup_state.clone();
+        Callback::from(move |ingredient: Ingredient| {
+            let (lookup, reference) = match (
+                (*lookup_state).clone(),
+                ingredient_references::reference(&ingredient),
+            ) {
+                (Some(lookup), Some(reference)) => (lookup, reference),
+                _ => return,
+            };
+            let after = &value[lookup.end..];
+            let separator = match after.starts_with(char::is_whitespace) {
+                true => "",
+                false => " ",
+            };
+            let before = format!("{}{reference}{separator}", &value[..lookup.start]);
+            let caret = before.encode_utf16().count() as u32;
+            let text = format!("{before}{after}");
+            *pending_caret_ref.borrow_mut() = Some((text.clone(), caret));
+            lookup_state.set(None);
+            on_input_callback.emit(text);
+        })
+    };
+
+    let on_input = {
+        let on_input_callback = props.on_input.clone();
+        let lookup_state = lookup_state.clone();
+        let selected_state = selected_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlTextAreaElement>().ok());
+            if let Some(input) = input {
+                let text = input.value();
+                let caret = input.selection_start().ok().flatten();
+                lookup_state.set(caret.and_then(|v| lookup_at(&text, byte_index(&text, v))));
+                selected_state.set(0);
+                on_input_callback.emit(text);
+            }
+        })
+    };
+
+    let on_key_down = {
+        let lookup_state = lookup_state.clone();
+        let selected_state = selected_state.clone();
+        let suggestions = suggestions.clone();
+        let on_insert = on_insert.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if suggestions.is_empty() {
+                return;
+            }
+            let selected = (*selected_state).min(suggestions.len() - 1);
+            match e.key().as_str() {
+                "ArrowDown" => selected_state.set((selected + 1) % suggestions.len()),
+                "ArrowUp" => {
+                    selected_state.set((selected + suggestions.len() - 1) % suggestions.len())
+                }
+                "Enter" | "Tab" => on_insert.emit(suggestions[selected].clone()),
+                "Escape" => lookup_state.set(None),
+                _ => return,
+            };
+            e.prevent_default();
+            // keep the modal from closing on escape
+            e.stop_propagation();
+        })
+    };
+
+    let on_blur = {
+        let lookup_state = lookup_state.clone();
+        Callback::from(move |_: FocusEvent| lookup_state.set(None))
+    };
+
+    html! {
+        <>
+        <div class="relative">
+            <textarea
+                ref={textarea_ref}
+                class="textarea textarea-bordered w-full"
+                oninput={on_input}
+                onkeydown={on_key_down}
+                onblur={on_blur}
+                value={props.value.clone()}
+                placeholder="Description here..."
+                aria-autocomplete="list"
+                aria-expanded={(!suggestions.is_empty()).to_string()}
+                required=true
+            />
+            if !suggestions.is_empty() {
+                <ul class="menu menu-compact bg-base-100 rounded-box shadow absolute left-0 right-0 top-full z-10" role="listbox" aria-label="Ingredients">
+                    { for suggestions.iter().enumerate().map(|(i, ingredient)| {
+                        let is_selected = i == (*selected_state).min(suggestions.len() - 1);
+                        let on_mouse_down = {
+                            let on_insert = on_insert.clone();
+                            let ingredient = ingredient.clone();
+                            Callback::from(move |e: MouseEvent| {
+                                // keep the focus in the text
+                                e.prevent_default();
+                                on_insert.emit(ingredient.clone());
+                            })
+                        };
+                        html!{
+                            <li role="option" aria-selected={is_selected.to_string()}>
+                                <a class={classes!(is_selected.then_some("active"))} onmousedown={on_mouse_down}>
+                                    {ingredient.summary()}
+                                </a>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+        </div>
+        if !props.ingredients.is_empty() {
+            <p class="text-xs opacity-70">{"Type @ to use an ingredient's amount, which stays up to date when it changes"}</p>
+        }
+        <p class="text-xs opacity-70">{"Supports Markdown, such as *emphasis*, **bold**, lists and links"}</p>
+        </>
+    }
+}
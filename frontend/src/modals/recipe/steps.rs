@@ -1,107 +1,180 @@
-use crate::contexts::prelude::{push_toast, use_toasts};
+use crate::components::markdown::PreviewTabs;
+use crate::components::recipe::Steps;
+use crate::components::reorder::{drop_position_attribute, DragHandle};
+use crate::contexts::prelude::{push_toast, use_toasts, Toast};
 use crate::core::handlers::{api_error_to_toast, logout_on_401};
-use crate::core::types::recipe::{Step, UpdateStep};
+use crate::core::ingredient_references;
+use crate::core::sections::{self, ItemPosition, Keyed, Section};
+use crate::core::types::recipe::{
+    ensure_ingredient_ids, Ingredient, Step, UpdateIngredient, UpdateStep,
+};
 use crate::{contexts::login::use_login, core::types::recipe::UpdateRecipe};
 
 use crate::modals::Modal;
+
+use super::section::{EditSection, SectionSelector};
+use super::step_description::StepDescriptionInput;
 use wasm_bindgen::JsCast;
-use web_sys::{EventTarget, HtmlInputElement, HtmlTextAreaElement};
+use web_sys::{EventTarget, HtmlInputElement};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct EditStepProps {
+    /// number of steps in the section
     pub len: usize,
-    pub index: usize,
+    pub position: ItemPosition,
+    /// number of the step across every section
+    pub number: usize,
     pub step: Step,
-    pub on_input: Callback<(usize, Step)>,
-    pub on_move_up: Callback<usize>,
-    pub on_move_down: Callback<usize>,
-    pub on_delete: Callback<usize>,
+    /// ingredients of the recipe, which the step can use
+    pub ingredients: Vec<Ingredient>,
+    /// names of every section, to move the step between
+    pub sections: Vec<Option<String>>,
+    pub on_input: Callback<(ItemPosition, Step)>,
+    pub on_move_up: Callback<ItemPosition>,
+    pub on_move_down: Callback<ItemPosition>,
+    pub on_move_section: Callback<(ItemPosition, usize)>,
+    pub on_delete: Callback<ItemPosition>,
+    /// whether a step being dragged would be dropped before this one
+    pub is_drop_target: bool,
+    pub on_drag: Callback<Option<ItemPosition>>,
+    pub on_drop: Callback<(ItemPosition, ItemPosition)>,
+    pub on_move: Callback<(ItemPosition, bool)>,
+}
+
+/// Ids of used ingredients, in the same order as the ingredients of the recipe
+fn in_ingredients_order(ids: &[String], ingredients: &[Ingredient]) -> Vec<String> {
+    ingredients
+        .iter()
+        .filter_map(|v| v.id.clone())
+        .filter(|v| ids.contains(v))
+        .collect()
 }
 
 #[function_component(EditStep)]
 pub fn recipe_step(props: &EditStepProps) -> Html {
-    let step_state = use_state(|| props.step.clone());
-
     let on_move_up = {
         let on_move_up_callback = props.on_move_up.clone();
-        let index = props.index;
+        let position = props.position;
         Callback::from(move |_| {
-            on_move_up_callback.emit(index);
+            on_move_up_callback.emit(position);
         })
     };
 
     let on_move_down = {
         let on_move_down_callback = props.on_move_down.clone();
-        let index = props.index;
+        let position = props.position;
         Callback::from(move |_| {
-            on_move_down_callback.emit(index);
+            on_move_down_callback.emit(position);
         })
     };
 
     let on_delete = {
         let on_delete_callback = props.on_delete.clone();
-        let index = props.index;
+        let position = props.position;
         Callback::from(move |_| {
-            on_delete_callback.emit(index);
+            on_delete_callback.emit(position);
+        })
+    };
+
+    let on_section_change = {
+        let on_move_section_callback = props.on_move_section.clone();
+        let position = props.position;
+        Callback::from(move |section: usize| {
+            on_move_section_callback.emit((position, section));
+        })
+    };
+
+    let on_ingredient_toggle = {
+        let on_input_callback = props.on_input.clone();
+        let position = props.position;
+        let step = props.step.clone();
+        let ingredients = props.ingredients.clone();
+        Callback::from(move |id: String| {
+            let mut step = step.clone();
+            match step.ingredients.contains(&id) {
+                true => step.ingredients.retain(|v| *v != id),
+                false => step.ingredients.push(id),
+            };
+            step.ingredients = in_ingredients_order(&step.ingredients, &ingredients);
+            on_input_callback.emit((position, step));
         })
     };
 
     let on_title_input = {
         let on_input_callback = props.on_input.clone();
-        let index = props.index;
-        let step_state = step_state.clone();
+        let position = props.position;
+        let step = props.step.clone();
         Callback::from(move |e: InputEvent| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
             if let Some(input) = input {
-                let mut step = (*step_state).clone();
+                let mut step = step.clone();
                 if input.value().is_empty() {
                     step.title = None;
                 } else {
                     step.title = Some(input.value());
                 }
-                step_state.set(step.clone());
-                on_input_callback.emit((index, step));
+                on_input_callback.emit((position, step));
             }
         })
     };
 
     let on_description_input = {
         let on_input_callback = props.on_input.clone();
-        let index = props.index;
-        Callback::from(move |e: InputEvent| {
-            let target: Option<EventTarget> = e.target();
-            let input = target.and_then(|t| t.dyn_into::<HtmlTextAreaElement>().ok());
-            if let Some(input) = input {
-                let mut step = (*step_state).clone();
-                step.description = input.value();
-                step_state.set(step.clone());
-                on_input_callback.emit((index, step));
+        let position = props.position;
+        let step = props.step.clone();
+        let ingredients = props.ingredients.clone();
+        Callback::from(move |description: String| {
+            let mut step = step.clone();
+            // referring to an ingredient in the text means the step uses it
+            for id in ingredient_references::referenced_ids(&description) {
+                if !step.ingredients.iter().any(|v| v == id) {
+                    step.ingredients.push(id.to_owned());
+                }
             }
+            step.ingredients = in_ingredients_order(&step.ingredients, &ingredients);
+            step.description = description;
+            on_input_callback.emit((position, step));
         })
     };
 
     html! {
-        <li class="mb-4 p-4 rounded bg-base-200">
+        <li
+            class={classes!("mb-4", "p-4", "rounded", "bg-base-200", "border-t-4", if props.is_drop_target { "border-primary" } else { "border-transparent" })}
+            data-drop-position={drop_position_attribute(props.position)}
+        >
             <div class="flex mb-2">
+                <DragHandle
+                    position={props.position}
+                    on_drag={props.on_drag.clone()}
+                    on_drop={props.on_drop.clone()}
+                    on_move={props.on_move.clone()}
+                />
                 <input
                     class="input input-bordered w-full mr-2"
                     oninput={on_title_input}
                     value={props.step.title.clone().unwrap_or_default()}
                     type="text"
-                    placeholder={format!("Step {}", props.index+1)}
+                    placeholder={format!("Step {}", props.number)}
                 />
+                if props.sections.len() > 1 {
+                    <SectionSelector
+                        sections={props.sections.clone()}
+                        selected={props.position.0}
+                        on_change={on_section_change}
+                    />
+                }
                 <div class="btn-group">
                     {
-                        if props.index == 0 {
+                        if props.position.1 == 0 {
                             html!{<button type="button" class="btn btn-disabled">{"Up"}</button>}
                         } else {
                             html!{<button type="button" class="btn" onclick={on_move_up}>{"Up"}</button>}
                         }
                     }
                     {
-                        if props.len == props.index+1 {
+                        if props.len == props.position.1 + 1 {
                             html!{<button type="button" class="btn btn-disabled">{"Down"}</button>}
                         } else {
                             html!{<button type="button" class="btn" onclick={on_move_down}>{"Down"}</button>}
@@ -110,13 +183,29 @@ pub fn recipe_step(props: &EditStepProps) -> Html {
                     <button type="button" class="btn" onclick={on_delete}>{"X"}</button>
                 </div>
             </div>
-            <textarea
-                class="textarea textarea-bordered w-full"
-                oninput={on_description_input}
+            <StepDescriptionInput
                 value={props.step.description.clone()}
-                placeholder="Description here..."
-                required=true
+                ingredients={props.ingredients.clone()}
+                on_input={on_description_input}
             />
+            if !props.ingredients.is_empty() {
+                <div class="flex flex-wrap items-center gap-1 mt-2" role="group" aria-label="Ingredients Used">
+                    <span class="text-sm mr-1">{"Uses:"}</span>
+                    { for props.ingredients.iter().filter_map(|ingredient| {
+                        let id = ingredient.id.clone()?;
+                        let is_used = props.step.ingredients.contains(&id);
+                        let on_click = on_ingredient_toggle.reform(move |_| id.clone());
+                        Some(html!{
+                            <button
+                                type="button"
+                                class={classes!("btn", "btn-xs", if is_used { "btn-primary" } else { "btn-outline" })}
+                                aria-pressed={is_used.to_string()}
+                                onclick={on_click}
+                            >{ingredient.name.clone()}</button>
+                        })
+                    })}
+                </div>
+            }
         </li>
     }
 }
@@ -125,44 +214,73 @@ pub fn recipe_step(props: &EditStepProps) -> Html {
 pub struct EditStepsProps {
     pub id: String,
     pub steps: Vec<Step>,
-    pub onclose: Callback<Option<Vec<Step>>>,
+    pub ingredients: Vec<Ingredient>,
+    /// emitted with the steps and ingredients once saved,
+    /// as ingredients may have been given ids so steps can refer to them
+    pub onclose: Callback<Option<(Vec<Step>, Vec<Ingredient>)>>,
 }
 
 #[function_component(EditSteps)]
 pub fn recipe_steps(props: &EditStepsProps) -> Html {
     let login_ctx = use_login().unwrap();
     let toasts_ctx = use_toasts().unwrap();
-    let steps_state = use_state(|| props.steps.clone());
+    let sections_state = use_state(|| {
+        let steps: Vec<Keyed<Step>> = props.steps.iter().cloned().map(Keyed::new).collect();
+        let mut sections = sections::group(&steps);
+        if sections.is_empty() {
+            sections.push(Section::new(None));
+        }
+        sections
+    });
     let is_loading_state = use_state(bool::default);
+    let drop_target_state = use_state(|| Option::<ItemPosition>::None);
+    // ingredients from before they had ids are given them, so they can be used by steps
+    let ingredients_state = use_state(|| {
+        let mut ingredients = props.ingredients.clone();
+        let is_changed = ensure_ingredient_ids(&mut ingredients);
+        (ingredients, is_changed)
+    });
+    let section_names: Vec<Option<String>> =
+        sections_state.iter().map(|v| v.name.clone()).collect();
 
     let on_save = {
         let id = props.id.to_string();
         let on_close_callback = props.onclose.clone();
-        let steps_state = steps_state.clone();
+        let sections_state = sections_state.clone();
+        let ingredients_state = ingredients_state.clone();
         let is_loading_state = is_loading_state.clone();
         Callback::from(move |_| {
             let login_ctx = login_ctx.clone();
             let api = login_ctx.http_api.clone().unwrap();
             let toasts_ctx = toasts_ctx.clone();
+            if let Err(message) = sections::check(&sections_state) {
+                push_toast(&toasts_ctx, Toast { message });
+                return;
+            }
             let id = id.clone();
             let on_close_callback = on_close_callback.clone();
             let is_loading_state = is_loading_state.clone();
-            let steps = (*steps_state).clone();
+            let mut steps: Vec<Step> = sections::flatten(&sections_state)
+                .into_iter()
+                .map(|v| v.item)
+                .collect();
+            let (ingredients, is_ingredients_changed) = (*ingredients_state).clone();
+            // forget ingredients removed since they were chosen
+            for step in steps.iter_mut() {
+                step.description =
+                    ingredient_references::update_references(&step.description, &ingredients);
+                step.ingredients
+                    .retain(|id| ingredients.iter().any(|v| v.id.as_ref() == Some(id)));
+            }
             wasm_bindgen_futures::spawn_local(async move {
                 is_loading_state.set(true);
                 let result = api
                     .patch_update_recipe(
                         id,
                         &UpdateRecipe {
-                            steps: Some(
-                                steps
-                                    .iter()
-                                    .map(|step| UpdateStep {
-                                        title: step.title.clone(),
-                                        description: Some(step.description.clone()),
-                                    })
-                                    .collect(),
-                            ),
+                            steps: Some(steps.iter().map(UpdateStep::from).collect()),
+                            ingredients: is_ingredients_changed
+                                .then(|| ingredients.iter().map(UpdateIngredient::from).collect()),
                             ..Default::default()
                         },
                     )
@@ -170,7 +288,7 @@ pub fn recipe_steps(props: &EditStepsProps) -> Html {
                 is_loading_state.set(false);
                 match result {
                     Ok(_) => {
-                        on_close_callback.emit(Some(steps));
+                        on_close_callback.emit(Some((steps, ingredients)));
                     }
                     Err(e) => {
                         push_toast(&toasts_ctx, api_error_to_toast(&e, "saving recipe steps"));
@@ -189,77 +307,198 @@ pub fn recipe_steps(props: &EditStepsProps) -> Html {
     };
 
     let on_step_input = {
-        let steps_state = steps_state.clone();
-        Callback::from(move |update: (usize, Step)| {
-            let mut steps = (*steps_state).clone();
-            steps[update.0] = update.1;
-            steps_state.set(steps);
+        let sections_state = sections_state.clone();
+        Callback::from(move |((section, index), step): (ItemPosition, Step)| {
+            let mut sections = (*sections_state).clone();
+            sections[section].items[index].item = step;
+            sections_state.set(sections);
         })
     };
 
     let on_step_move_up = {
-        let steps_state = steps_state.clone();
-        Callback::from(move |index: usize| {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(section, index): ItemPosition| {
             if index != 0 {
-                let mut steps = (*steps_state).clone();
-                steps.swap(index, index - 1);
-                steps_state.set(steps.clone());
+                let mut sections = (*sections_state).clone();
+                sections[section].items.swap(index, index - 1);
+                sections_state.set(sections);
             }
         })
     };
 
     let on_step_move_down = {
-        let steps_state = steps_state.clone();
-        Callback::from(move |index: usize| {
-            let mut steps = (*steps_state).clone();
-            if index != steps.len() - 1 {
-                steps.swap(index, index + 1);
-                steps_state.set(steps);
+        let sections_state = sections_state.clone();
+        Callback::from(move |(section, index): ItemPosition| {
+            let mut sections = (*sections_state).clone();
+            if index != sections[section].items.len() - 1 {
+                sections[section].items.swap(index, index + 1);
+                sections_state.set(sections);
             }
         })
     };
 
+    let on_move_step_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(position, to_section): (ItemPosition, usize)| {
+            let mut sections = (*sections_state).clone();
+            sections::move_item(&mut sections, position, to_section);
+            sections_state.set(sections);
+        })
+    };
+
+    let on_step_drag = {
+        let drop_target_state = drop_target_state.clone();
+        Callback::from(move |target: Option<ItemPosition>| drop_target_state.set(target))
+    };
+
+    let on_step_drop = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(from, to): (ItemPosition, ItemPosition)| {
+            let mut sections = (*sections_state).clone();
+            sections::move_item_to(&mut sections, from, to);
+            sections_state.set(sections);
+        })
+    };
+
+    let on_step_move = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(from, is_up): (ItemPosition, bool)| {
+            let mut sections = (*sections_state).clone();
+            sections::move_item_by_one(&mut sections, from, is_up);
+            sections_state.set(sections);
+        })
+    };
+
     let on_delete_step = {
-        let steps_state = steps_state.clone();
-        Callback::from(move |index: usize| {
-            let mut steps = (*steps_state).clone();
-            steps.remove(index);
-            steps_state.set(steps);
+        let sections_state = sections_state.clone();
+        Callback::from(move |(section, index): ItemPosition| {
+            let mut sections = (*sections_state).clone();
+            sections[section].items.remove(index);
+            sections_state.set(sections);
         })
     };
 
     let on_add_step = {
-        let steps_state = steps_state.clone();
-        Callback::from(move |_| {
-            let mut steps = (*steps_state).clone();
-            steps.push(Step {
+        let sections_state = sections_state.clone();
+        Callback::from(move |section: usize| {
+            let mut sections = (*sections_state).clone();
+            sections[section].items.push(Keyed::new(Step {
                 title: None,
                 description: "".to_owned(),
-            });
-            steps_state.set(steps);
+                section: None,
+                ingredients: Vec::new(),
+            }));
+            sections_state.set(sections);
+        })
+    };
+
+    let on_rename_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |(section, name): (usize, Option<String>)| {
+            let mut sections = (*sections_state).clone();
+            sections[section].name = name;
+            sections_state.set(sections);
         })
     };
 
+    let on_section_move_up = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |index: usize| {
+            if index != 0 {
+                let mut sections = (*sections_state).clone();
+                sections.swap(index, index - 1);
+                sections_state.set(sections);
+            }
+        })
+    };
+
+    let on_section_move_down = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |index: usize| {
+            let mut sections = (*sections_state).clone();
+            if index != sections.len() - 1 {
+                sections.swap(index, index + 1);
+                sections_state.set(sections);
+            }
+        })
+    };
+
+    let on_delete_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |index: usize| {
+            let mut sections = (*sections_state).clone();
+            sections::remove_section(&mut sections, index);
+            sections_state.set(sections);
+        })
+    };
+
+    let on_add_section = {
+        let sections_state = sections_state.clone();
+        Callback::from(move |_| {
+            let mut sections = (*sections_state).clone();
+            sections.push(Section::new(Some(format!("Part {}", sections.len() + 1))));
+            sections_state.set(sections);
+        })
+    };
+
+    let preview_steps: Vec<Step> = sections::flatten(&sections_state)
+        .into_iter()
+        .map(|v| v.item)
+        .collect();
+    // steps are numbered across every section
+    let mut number = 0;
+
     html! {
         <Modal title={"Edit Steps"} oncancel={on_cancel} onsave={on_save} loading={*is_loading_state}>
+            <PreviewTabs preview={html!{
+                <div class="max-h-[50vh] lg:max-h-[60vh] overflow-y-auto">
+                    <Steps items={preview_steps} ingredients={ingredients_state.0.clone()}/>
+                </div>
+            }}>
             <div class="max-h-[50vh] lg:max-h-[60vh] overflow-y-auto">
-                <ol>
-                {
-                    for (*steps_state).clone().iter().enumerate().map(|(i, step)| {
-                        html!{<EditStep
-                            len={(*steps_state).clone().len()}
-                            index={i}
-                            step={step.clone()}
-                            on_input={on_step_input.clone()}
-                            on_move_up={on_step_move_up.clone()}
-                            on_move_down={on_step_move_down.clone()}
-                            on_delete={on_delete_step.clone()}
-                        />}
-                    })
-                }
-                </ol>
-                <button type="button" class="btn w-full" onclick={on_add_step}>{"Add Step"}</button>
+                { for sections_state.iter().enumerate().map(|(section_index, section)| {
+                    html! {
+                        <EditSection
+                            len={sections_state.len()}
+                            index={section_index}
+                            name={section.name.clone()}
+                            add_label="Add Step"
+                            on_rename={on_rename_section.clone()}
+                            on_move_up={on_section_move_up.clone()}
+                            on_move_down={on_section_move_down.clone()}
+                            on_delete={on_delete_section.clone()}
+                            on_add={on_add_step.clone()}
+                            is_drop_target={*drop_target_state == Some((section_index, section.items.len()))}
+                        >
+                            <ol>
+                            { for section.items.iter().enumerate().map(|(i, step)| {
+                                number += 1;
+                                html!{<EditStep
+                                    key={step.key}
+                                    len={section.items.len()}
+                                    position={(section_index, i)}
+                                    number={number}
+                                    step={step.item.clone()}
+                                    ingredients={ingredients_state.0.clone()}
+                                    sections={section_names.clone()}
+                                    on_input={on_step_input.clone()}
+                                    on_move_up={on_step_move_up.clone()}
+                                    on_move_down={on_step_move_down.clone()}
+                                    on_move_section={on_move_step_section.clone()}
+                                    on_delete={on_delete_step.clone()}
+                                    is_drop_target={*drop_target_state == Some((section_index, i))}
+                                    on_drag={on_step_drag.clone()}
+                                    on_drop={on_step_drop.clone()}
+                                    on_move={on_step_move.clone()}
+                                />}
+                            })}
+                            </ol>
+                        </EditSection>
+                    }
+                })}
+                <button type="button" class="btn w-full" onclick={on_add_section}>{"Add Section"}</button>
             </div>
+            </PreviewTabs>
         </Modal>
     }
 }
@@ -0,0 +1,63 @@
+use crate::core::storage::SavedSearch;
+
+use crate::modals::Modal;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct SaveSearchProps {
+    pub query: AttrValue,
+    pub onclose: Callback<Option<SavedSearch>>,
+}
+
+#[function_component(SaveSearch)]
+pub fn save_search(props: &SaveSearchProps) -> Html {
+    let name_state = use_state(String::default);
+
+    let on_save = {
+        let on_close_callback = props.onclose.clone();
+        let name_state = name_state.clone();
+        let query = props.query.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(Some(SavedSearch {
+                name: name_state.trim().to_owned(),
+                query: query.to_string(),
+            }));
+        })
+    };
+
+    let on_cancel = {
+        let on_close_callback = props.onclose.clone();
+        Callback::from(move |_| {
+            on_close_callback.emit(None);
+        })
+    };
+
+    let on_name_input = {
+        let name_state = name_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name_state.set(input.value());
+        })
+    };
+
+    html! {
+        <Modal title={"Save Search"} oncancel={on_cancel} onsave={on_save}>
+            <div class="form-control mb-2">
+                <label class="label" for="save-search-name">{"Name"}</label>
+                <input
+                    id="save-search-name"
+                    oninput={on_name_input}
+                    value={(*name_state).clone()}
+                    class="input input-bordered w-full"
+                    maxlength="60"
+                    required=true
+                />
+            </div>
+            <div class="form-control">
+                <span class="label">{"Query"}</span>
+                <code class="p-2 rounded bg-base-200 break-all">{props.query.clone()}</code>
+            </div>
+        </Modal>
+    }
+}
@@ -1,16 +1,22 @@
+pub mod book;
+pub mod books;
 pub mod home;
 pub mod login;
 pub mod logout;
 pub mod new_recipe;
+pub mod pantry;
 pub mod recipe;
 pub mod recipe_print_view;
 pub mod recipes;
 pub mod signup;
 
+pub use book::Book;
+pub use books::Books;
 pub use home::Home;
 pub use login::Login;
 pub use logout::Logout;
 pub use new_recipe::NewRecipe;
+pub use pantry::Pantry;
 pub use recipe::Recipe;
 pub use recipe_print_view::RecipePrintView;
 pub use recipes::Recipes;
@@ -0,0 +1,72 @@
+use yew::prelude::*;
+use yew_hooks::prelude::{use_async_with_options, UseAsyncOptions};
+
+use crate::contexts::prelude::{push_toast, use_login, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    components::{book::BookContent, drawer, saved_searches::SavedSearches},
+    core::effects::{use_login_redirect_effect, LoginState},
+    Route,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct BookProps {
+    pub id: AttrValue,
+}
+
+#[function_component(Book)]
+pub fn book(props: &BookProps) -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+
+    let get_book = {
+        let id = props.id.to_string();
+        let login_ctx = login_ctx.clone();
+        let api = login_ctx.http_api.clone();
+        use_async_with_options(
+            async move {
+                let api = api.expect("expected api to exist");
+                let result = match api.get_book_by_id(&id).await {
+                    Ok(book) => api
+                        .get_book_recipes(&id)
+                        .await
+                        .map(|recipes| (book, recipes)),
+                    Err(err) => Err(err),
+                };
+                match result {
+                    Ok(v) => Ok(v),
+                    Err(err) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&err, "loading book"));
+                        logout_on_401(&err, &login_ctx);
+                        Err(err)
+                    }
+                }
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    use_login_redirect_effect(LoginState::HasLogin, Route::Login);
+
+    html! {
+        <drawer::Drawer r#for="main-drawer">
+            <drawer::DrawerContent header=true>
+                if let (false, Some((book, recipes))) = (get_book.loading, &get_book.data) {
+                    <BookContent
+                        book={book.clone()}
+                        recipes={recipes.clone()}
+                        media_url={login_ctx.login.as_ref().unwrap().media_url.clone()}
+                    />
+                }
+            </drawer::DrawerContent>
+            <drawer::DrawerDraw r#for="main-drawer">
+                <drawer::DrawerLink to={Route::Home}>{"Home"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::NewRecipe}>{"New Recipe"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Recipes}>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books}>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry}>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
+            </drawer::DrawerDraw>
+        </drawer::Drawer>
+    }
+}
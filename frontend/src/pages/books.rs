@@ -0,0 +1,109 @@
+use yew::prelude::*;
+use yew_hooks::prelude::{use_async_with_options, UseAsyncOptions};
+use yew_router::prelude::use_navigator;
+
+use crate::{
+    components::{
+        drawer,
+        saved_searches::SavedSearches,
+        loading::LoadingButton,
+        thumbnail_link_grid::{GridItemProps, WindowedGrid},
+    },
+    contexts::prelude::{push_toast, use_login, use_toasts},
+    core::{
+        effects::{use_login_redirect_effect, LoginState},
+        handlers::{api_error_to_toast, logout_on_401},
+        types::book::Book,
+    },
+    modals::{self, ModalController},
+    Route,
+};
+
+#[function_component(Books)]
+pub fn books() -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+    let navigator = use_navigator().unwrap();
+    let modal_html_state: UseStateHandle<Option<Html>> = use_state(Option::default);
+
+    let get_books = {
+        let login_ctx = login_ctx.clone();
+        let api = login_ctx.http_api.clone();
+        use_async_with_options(
+            async move {
+                let api = api.expect("expected api to exist");
+                match api.get_books().await {
+                    Ok(v) => Ok(v),
+                    Err(err) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&err, "loading books"));
+                        logout_on_401(&err, &login_ctx);
+                        Err(err)
+                    }
+                }
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    use_login_redirect_effect(LoginState::HasLogin, Route::Login);
+
+    let new_book_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        Callback::from(move |new_book: Option<Book>| {
+            modal_html_state.set(None);
+            if let Some(book) = new_book {
+                navigator.push(&Route::Book { id: book.id });
+            }
+        })
+    };
+
+    let on_new_book_click = {
+        let modal_html_state = modal_html_state.clone();
+        Callback::from(move |_: MouseEvent| {
+            modal_html_state.set(Some(html! {
+                <modals::book::NewBook onclose={new_book_modal_closed.clone()} />
+            }));
+        })
+    };
+
+    let media_url = &login_ctx.login.as_ref().unwrap().media_url;
+
+    html! {
+        <drawer::Drawer r#for="main-drawer">
+            <drawer::DrawerContent header=true>
+                <ModalController modal={(*modal_html_state).clone()}/>
+                <div class="p-4 rounded bg-base-200">
+                    <div class="flex items-center mb-2">
+                        <h1 class="text-3xl font-bold mr-auto">{ "Books" }</h1>
+                        <button class="btn" onclick={on_new_book_click}>{"New Book"}</button>
+                    </div>
+                    if get_books.loading {
+                        <LoadingButton classes="btn-block" r#type="button" />
+                    } else if let Some(books) = &get_books.data {
+                        if books.is_empty() {
+                            <div class="text-center">{"No Books Yet"}</div>
+                        } else {
+                            <WindowedGrid items={
+                                books.iter().map(|book| GridItemProps {
+                                    navigate_to: Route::Book { id: book.id.clone() },
+                                    title: book.title.clone().into(),
+                                    image_src: book.image_id.as_ref().map(|v| format!("{media_url}/book-image/{v}").into()),
+                                    favourite: false,
+                                    rating: None,
+                                }).collect::<Vec<_>>()
+                            } />
+                        }
+                    }
+                </div>
+            </drawer::DrawerContent>
+            <drawer::DrawerDraw r#for="main-drawer">
+                <drawer::DrawerLink to={Route::Home}>{"Home"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::NewRecipe}>{"New Recipe"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Recipes}>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books} active=true>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry}>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
+            </drawer::DrawerDraw>
+        </drawer::Drawer>
+    }
+}
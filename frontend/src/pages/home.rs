@@ -1,8 +1,9 @@
 use yew::prelude::*;
+use yew_router::prelude::Link;
 
 use crate::{
-    components::drawer,
     components::stats,
+    components::{drawer, pantry::CookableRecipes, saved_searches::SavedSearches},
     contexts::prelude::{push_toast, use_login, use_toasts},
     core::effects::{use_login_redirect_effect, LoginState},
     core::{
@@ -47,12 +48,60 @@ fn home_account_stats() -> HtmlResult {
             <stats::Stats>
                 <stats::Stat title={"Number Of Users"} value={format!("{}", stats.user_count)}/>
                 <stats::Stat title={"Number Of Recipes"} value={format!("{}", stats.recipe_count)}/>
-                <stats::Stat title={"Number Of Books"} value={"0"}/>
+                <stats::Stat title={"Number Of Books"} value={format!("{}", stats.book_count)}/>
             </stats::Stats>
         }
     })
 }
 
+/// Most recipes shown as nearly ready to cook
+const NEARLY_READY_LIMIT: usize = 5;
+
+#[function_component(HomeNearlyReady)]
+fn home_nearly_ready() -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+
+    let items_state: UseStateHandle<Option<Vec<types::pantry::PantryItem>>> =
+        use_state(Option::default);
+
+    {
+        let items_state = items_state.clone();
+        use_effect_with_deps(
+            move |_| {
+                let api = match &login_ctx.http_api {
+                    Some(v) => v.clone(),
+                    None => return,
+                };
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api.get_pantry_items().await {
+                        Ok(v) => items_state.set(Some(v)),
+                        Err(err) => {
+                            push_toast(&toasts_ctx, api_error_to_toast(&err, "loading pantry"));
+                            logout_on_401(&err, &login_ctx);
+                        }
+                    };
+                });
+            },
+            (),
+        );
+    }
+
+    html! {
+        if let Some(items) = (*items_state).clone() {
+            if items.is_empty() {
+                <p>
+                    {"Add what you have to your "}
+                    <Link<Route> to={Route::Pantry} classes="link">{"pantry"}</Link<Route>>
+                    {" to see what you can cook"}
+                </p>
+            } else {
+                <CookableRecipes {items} near_complete_only=true limit={NEARLY_READY_LIMIT} />
+            }
+        }
+    }
+}
+
 #[function_component(Home)]
 pub fn home() -> Html {
     use_login_redirect_effect(LoginState::HasLogin, Route::Login);
@@ -65,11 +114,18 @@ pub fn home() -> Html {
                     <h2 class="text-2xl mb-2">{"Your Stats"}</h2>
                     <HomeAccountStats/>
                 </div>
+                <div class="p-4 mt-4 rounded bg-base-200">
+                    <h2 class="text-2xl mb-2">{"Nearly Ready To Cook"}</h2>
+                    <HomeNearlyReady/>
+                </div>
             </drawer::DrawerContent>
             <drawer::DrawerDraw r#for="main-drawer">
                 <drawer::DrawerLink to={Route::Home} active=true>{"Home"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::NewRecipe}>{"New Recipe"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::Recipes}>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books}>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry}>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
             </drawer::DrawerDraw>
         </drawer::Drawer>
     }
@@ -4,7 +4,7 @@ use yew::prelude::*;
 use yew_hooks::use_async;
 use yew_router::prelude::use_navigator;
 
-use crate::components::drawer;
+use crate::components::{drawer, saved_searches::SavedSearches};
 use crate::components::loading::LoadingButton;
 use crate::contexts::prelude::{push_toast, use_login, use_toasts};
 use crate::core::effects::{use_login_redirect_effect, LoginState};
@@ -112,6 +112,9 @@ pub fn new_recipe() -> Html {
                 <drawer::DrawerLink to={Route::Home}>{"Home"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::NewRecipe} active=true>{"New Recipe"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::Recipes}>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books}>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry}>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
             </drawer::DrawerDraw>
         </drawer::Drawer>
     }
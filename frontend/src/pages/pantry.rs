@@ -0,0 +1,59 @@
+use yew::prelude::*;
+use yew_hooks::prelude::{use_async_with_options, UseAsyncOptions};
+
+use crate::contexts::prelude::{push_toast, use_login, use_toasts};
+use crate::core::handlers::{api_error_to_toast, logout_on_401};
+use crate::{
+    components::{
+        drawer, loading::LoadingButton, pantry::PantryContent, saved_searches::SavedSearches,
+    },
+    core::effects::{use_login_redirect_effect, LoginState},
+    Route,
+};
+
+#[function_component(Pantry)]
+pub fn pantry() -> Html {
+    let login_ctx = use_login().unwrap();
+    let toasts_ctx = use_toasts().unwrap();
+
+    let get_items = {
+        let login_ctx = login_ctx.clone();
+        let api = login_ctx.http_api.clone();
+        use_async_with_options(
+            async move {
+                let api = api.expect("expected api to exist");
+                match api.get_pantry_items().await {
+                    Ok(v) => Ok(v),
+                    Err(err) => {
+                        push_toast(&toasts_ctx, api_error_to_toast(&err, "loading pantry"));
+                        logout_on_401(&err, &login_ctx);
+                        Err(err)
+                    }
+                }
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    use_login_redirect_effect(LoginState::HasLogin, Route::Login);
+
+    html! {
+        <drawer::Drawer r#for="main-drawer">
+            <drawer::DrawerContent header=true>
+                if get_items.loading {
+                    <LoadingButton classes="btn-block" r#type="button" />
+                } else if let Some(items) = &get_items.data {
+                    <PantryContent items={items.clone()} />
+                }
+            </drawer::DrawerContent>
+            <drawer::DrawerDraw r#for="main-drawer">
+                <drawer::DrawerLink to={Route::Home}>{"Home"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::NewRecipe}>{"New Recipe"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Recipes}>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books}>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry} active=true>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
+            </drawer::DrawerDraw>
+        </drawer::Drawer>
+    }
+}
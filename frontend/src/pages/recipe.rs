@@ -4,7 +4,7 @@ use yew_hooks::prelude::{use_async_with_options, UseAsyncOptions};
 use crate::contexts::prelude::{push_toast, use_login, use_toasts};
 use crate::core::handlers::{api_error_to_toast, logout_on_401};
 use crate::{
-    components::{drawer, recipe::RecipeContent},
+    components::{drawer, recipe::RecipeContent, saved_searches::SavedSearches},
     core::effects::{use_login_redirect_effect, LoginState},
     Route,
 };
@@ -55,6 +55,9 @@ pub fn recipe(props: &RecipeProps) -> Html {
                 <drawer::DrawerLink to={Route::Home}>{"Home"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::NewRecipe}>{"New Recipe"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::Recipes}>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books}>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry}>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
             </drawer::DrawerDraw>
         </drawer::Drawer>
     }
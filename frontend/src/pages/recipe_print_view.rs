@@ -1,7 +1,18 @@
+use std::collections::HashMap;
+
 use yew::prelude::*;
 use yew_hooks::{use_async_with_options, UseAsyncOptions};
 
-use crate::{contexts::login::use_login, core::types::Fraction};
+use crate::{
+    components::{input::FractionalNumberInput, markdown::Markdown, step_text::StepText},
+    contexts::login::use_login,
+    core::{
+        api::ApiError,
+        duration, pan, sections, storage,
+        sub_recipes::{expand_ingredients, load_sub_recipes},
+        types::Fraction,
+    },
+};
 
 #[derive(Properties, PartialEq)]
 pub struct RecipePrintViewProps {
@@ -11,6 +22,17 @@ pub struct RecipePrintViewProps {
 #[function_component(RecipePrintView)]
 pub fn recipe_print_view(props: &RecipePrintViewProps) -> Html {
     let login_ctx = use_login().unwrap();
+    let expand_state = use_state(|| true);
+    // what the amounts are multiplied by, carried through into sub-recipes
+    let scale_state = use_state(|| Option::<f32>::None);
+    // temperatures are converted as the user chose on the recipe page
+    let temperatures = storage::read_temperature_display(
+        &login_ctx
+            .login
+            .as_ref()
+            .and_then(|login| login.token.subject())
+            .unwrap_or_default(),
+    );
 
     let get_recipe = {
         let id = props.id.to_string();
@@ -18,34 +40,98 @@ pub fn recipe_print_view(props: &RecipePrintViewProps) -> Html {
         use_async_with_options(
             async move {
                 let api = api.expect("expected api to exist");
-                api.get_recipe_by_id(id).await
+                let recipe = api.get_recipe_by_id(id).await?;
+                let sub_recipes = load_sub_recipes(&api, &recipe.ingredients).await?;
+                Ok::<_, ApiError>((recipe, sub_recipes))
             },
             UseAsyncOptions::enable_auto(),
         )
     };
 
+    let on_expand_change = {
+        let expand_state = expand_state.clone();
+        Callback::from(move |_: Event| expand_state.set(!*expand_state))
+    };
+
+    let on_scale_input = {
+        let scale_state = scale_state.clone();
+        Callback::from(move |scale: Option<f32>| scale_state.set(scale))
+    };
+    let scale = (*scale_state).filter(|v| *v > 0.0).unwrap_or(1.0);
+
+    let no_sub_recipes = HashMap::new();
+    // steps are numbered across every section
+    let mut step_number = 0;
+
     html! {
         <div class="p-2" data-theme="light">
-            if !get_recipe.loading && get_recipe.error.is_none() && get_recipe.data.is_some() {
-                if get_recipe.data.as_ref().unwrap().image_id.is_some() {
+            if let (false, None, Some((recipe, sub_recipes))) = (get_recipe.loading, &get_recipe.error, &get_recipe.data) {
+                if recipe.image_id.is_some() {
                     <figure class="h-64 w-full mb-4">
                         <img
                             class="object-cover w-full h-full rounded"
-                            src={format!("{}/recipe-image/{}", {login_ctx.login.as_ref().unwrap().media_url.clone()}, get_recipe.data.as_ref().unwrap().image_id.as_ref().unwrap())}
+                            src={format!("{}/recipe-image/{}", {login_ctx.login.as_ref().unwrap().media_url.clone()}, recipe.image_id.as_ref().unwrap())}
                         />
                     </figure>
                 }
-                <h1 class="text-3xl font-bold mb-4">{get_recipe.data.as_ref().unwrap().title.clone()}</h1>
+                <h1 class="text-3xl font-bold mb-4">{recipe.title.clone()}</h1>
+                if let times @ [_, ..] = recipe.info.times().as_slice() {
+                    <p class="mb-4">
+                        {times.iter().map(|(label, minutes)| format!("{label}: {}", duration::format(*minutes))).collect::<Vec<_>>().join(" · ")}
+                    </p>
+                }
+                if let Some(pan) = &recipe.info.pan {
+                    <p class="mb-4">{format!("Pan: {}", pan::summary(pan))}</p>
+                }
+                if let classifications @ [_, ..] = recipe.info.classifications().as_slice() {
+                    <p class="mb-4">
+                        {classifications.iter().map(|(label, value)| format!("{label}: {value}")).collect::<Vec<_>>().join(" · ")}
+                    </p>
+                }
+                // the url is written out, as a link cannot be followed on paper
+                if let Some(source) = match (&recipe.source_name, &recipe.source_url) {
+                    (Some(name), Some(url)) => Some(format!("{name} ({url})")),
+                    (Some(source), None) | (None, Some(source)) => Some(source.clone()),
+                    (None, None) => None,
+                } {
+                    <p class="mb-4">{format!("From {source}")}</p>
+                }
                 <div class="mb-4">
                     <h2 class="text-xl font-bold mb-1">{"Description"}</h2>
-                    <p>{get_recipe.data.as_ref().unwrap().short_description.clone()}</p>
+                    <p>{recipe.short_description.clone()}</p>
                 </div>
                 <div class="mb-4">
                     <h2 class="text-xl font-bold mb-1">{"Notes"}</h2>
-                    <pre class="whitespace-normal text-base font-sans">{get_recipe.data.as_ref().unwrap().long_description.clone()}</pre>
+                    <div class="text-base"><Markdown text={recipe.long_description.clone().unwrap_or_default()}/></div>
                 </div>
+                if !recipe.equipment.is_empty() {
+                    <div class="mb-4">
+                        <h2 class="text-xl font-bold mb-1">{"Equipment"}</h2>
+                        <p>{recipe.equipment.join(", ")}</p>
+                    </div>
+                }
                 <div class="mb-4">
-                    <h2 class="text-xl font-bold mb-1">{"Ingredients"}</h2>
+                    <div class="flex items-center mb-1">
+                        <h2 class="text-xl font-bold mr-auto">{"Ingredients"}</h2>
+                        if scale != 1.0 {
+                            <span class="hidden print:inline">{format!("× {}", Fraction::from(scale))}</span>
+                        }
+                        <label class="input-group input-group-sm w-auto print:hidden">
+                            <span>{"Scale"}</span>
+                            <FractionalNumberInput
+                                classes="input-bordered input-sm w-20"
+                                oninput={on_scale_input}
+                                value={*scale_state}
+                                placeholder={"1"}
+                            />
+                        </label>
+                        if !sub_recipes.is_empty() {
+                            <label class="label cursor-pointer gap-2 print:hidden">
+                                <span class="label-text">{"Expand sub-recipes"}</span>
+                                <input type="checkbox" class="checkbox" checked={*expand_state} onchange={on_expand_change} />
+                            </label>
+                        }
+                    </div>
                     <table class="table table-compact table-zebra w-full">
                         <thead>
                             <tr>
@@ -54,35 +140,72 @@ pub fn recipe_print_view(props: &RecipePrintViewProps) -> Html {
                                 <th>{"Notes"}</th>
                             </tr>
                         </thead>
+                        { for sections::group(&recipe.ingredients).into_iter().map(|section| html!{
                         <tbody>
+                            if let Some(name) = section.name {
+                                <tr>
+                                    <th colspan="3" class="text-base normal-case">{name}</th>
+                                </tr>
+                            }
                             {
-                                for get_recipe.data.as_ref().unwrap().ingredients.iter().map(|ingredient| {
+                                for expand_ingredients(
+                                    &recipe.id,
+                                    &section.items,
+                                    if *expand_state { sub_recipes } else { &no_sub_recipes },
+                                    scale,
+                                ).into_iter().map(|expanded| {
+                                    // already scaled when expanded
+                                    let ingredient = expanded.ingredient;
                                     html!{
                                         <tr>
-                                            <td class="whitespace-normal">{format!("{} {}", Fraction::from(ingredient.amount), {&ingredient.unit_type})}</td>
-                                            <td class="whitespace-normal">{&ingredient.name}</td>
+                                            <td class="whitespace-normal">{ingredient.scaled_quantity(1.0)}</td>
+                                            <td
+                                                class={classes!("whitespace-normal", expanded.is_sub_recipe.then_some("font-bold"))}
+                                                style={format!("padding-left: {}rem", 1 + expanded.depth * 2)}
+                                            >
+                                                {&ingredient.name}
+                                                if ingredient.optional {
+                                                    <span class="italic">{" (optional)"}</span>
+                                                }
+                                            </td>
                                             <td class="whitespace-normal">{&ingredient.description.clone().unwrap_or_default()}</td>
                                         </tr>
                                     }
                                 })
                             }
                         </tbody>
+                        })}
                     </table>
                 </div>
                 <div class="mb-4">
                     <h2 class="text-xl font-bold mb-1">{"Steps"}</h2>
-                    <ul>
-                    {
-                        for get_recipe.data.as_ref().unwrap().steps.iter().enumerate().map(|(i, step)| {
-                            html!{
-                                <li class="mb-2">
-                                    <h2 class="text-l font-bold mb-2">{&step.title.clone().unwrap_or(format!("Step {}", i+1))}</h2>
-                                    <pre class="whitespace-normal text-base font-sans">{&step.description}</pre>
-                                </li>
-                            }
-                        })
-                    }
-                    </ul>
+                    { for sections::group(&recipe.steps).into_iter().map(|section| html!{
+                        <>
+                        if let Some(name) = section.name {
+                            <h3 class="text-lg font-bold mb-2 border-b">{name}</h3>
+                        }
+                        <ul>
+                        {
+                            for section.items.iter().map(|step| {
+                                step_number += 1;
+                                html!{
+                                    <li class="mb-2">
+                                        <h2 class="text-l font-bold mb-2">{&step.title.clone().unwrap_or(format!("Step {step_number}"))}</h2>
+                                        <div class="text-base">
+                                            <StepText text={step.description.clone()} ingredients={recipe.ingredients.clone()} {temperatures} {scale}/>
+                                        </div>
+                                        if let used_ingredients @ [_, ..] = step.used_ingredients(&recipe.ingredients).as_slice() {
+                                            <p class="mt-1 text-sm">
+                                                {format!("You need: {}", used_ingredients.iter().map(|v| v.scaled_summary(scale)).collect::<Vec<_>>().join(", "))}
+                                            </p>
+                                        }
+                                    </li>
+                                }
+                            })
+                        }
+                        </ul>
+                        </>
+                    })}
                 </div>
             } else {
                 <div>{"Loading..."}</div>
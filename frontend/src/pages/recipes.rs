@@ -1,123 +1,779 @@
+use gloo::events::{EventListener, EventListenerOptions};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
-use yew_hooks::{use_async_with_options, UseAsyncOptions};
+use yew_router::prelude::{use_location, use_navigator};
 
 use crate::{
-    components::{drawer, loading::LoadingButton, thumbnail_link_grid},
-    contexts::prelude::{push_toast, use_login, use_toasts},
+    components::{
+        drawer,
+        loading::LoadingButton,
+        recipe_list::{List, ListItemProps},
+        recipe_table::Table,
+        saved_searches::SavedSearches,
+        thumbnail_link_grid::{GridItemProps, WindowedGrid},
+    },
+    contexts::prelude::{push_toast, use_login, use_toasts, Toast},
     core::{
-        effects::{use_login_redirect_effect, LoginState},
+        duration,
+        effects::{use_login_redirect_effect, use_visible_effect, LoginState},
         handlers::{api_error_to_toast, logout_on_401},
-        types::{query::RecipesFilter, recipe},
+        search::{self, use_search_index},
+        smart_query,
+        storage::{self, RecipesListCache, RecipesView, SavedSearch},
+        types::{
+            query::{RecipesFilter, RecipesSort},
+            recipe::{difficulty_label, DIFFICULTIES},
+        },
     },
+    modals::{self, ModalController},
     Route,
 };
 
+const SEARCH_RESULTS_LIMIT: usize = 100;
+/// Choices for the most minutes a recipe can take in total
+const MAX_TOTAL_TIMES: [u32; 5] = [15, 30, 45, 60, 120];
+
+/// Classifications recipes can be filtered to a single value of
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Facet {
+    Cuisine,
+    Course,
+    Difficulty,
+}
+
+/// Whether a classification matches a facet filter, ignoring case
+fn matches_facet(value: &Option<String>, filter: &Option<String>) -> bool {
+    match (value, filter) {
+        (_, None) => true,
+        (Some(value), Some(filter)) => value.eq_ignore_ascii_case(filter),
+        (None, Some(_)) => false,
+    }
+}
+
+/// Get the element that holds the scrolled page content
+fn drawer_content_element() -> Option<web_sys::Element> {
+    gloo::utils::document()
+        .query_selector(".drawer-content")
+        .ok()
+        .flatten()
+}
+
+/// Scroll the page back to a cached position
+fn restore_scroll_position(cache: &RecipesListCache) {
+    if let Some(element) = drawer_content_element() {
+        element.set_scroll_top(cache.content_scroll_top);
+    }
+    gloo::utils::window().scroll_to_with_x_and_y(0.0, cache.window_scroll_y);
+}
+
 #[function_component(Recipes)]
 pub fn recipes() -> Html {
     let login_ctx = use_login().unwrap();
     let toasts_ctx = use_toasts().unwrap();
+    let navigator = use_navigator().unwrap();
+    let location = use_location().unwrap();
+    let is_search_ready = use_search_index();
 
-    let filters_state = use_state(RecipesFilter::default);
-    let recipes_state: UseStateHandle<Vec<recipe::Recipe>> = use_state(Vec::default);
+    // filters are kept in the URL, so they survive navigating away and back
+    let filters = location.query::<RecipesFilter>().unwrap_or_default();
+    // restore the loaded recipes, when returning to the same list
+    let loaded_state = use_state(|| {
+        storage::read_recipes_list_cache()
+            .filter(|cache| cache.filters == filters)
+            .unwrap_or_else(|| RecipesListCache {
+                filters: RecipesFilter {
+                    page: 0,
+                    ..filters.clone()
+                },
+                recipes: Vec::new(),
+                total_count: 0,
+                next_cursor: None,
+                window_scroll_y: 0.0,
+                content_scroll_top: 0,
+            })
+    });
+    let is_loading_state = use_state(bool::default);
+    let load_failed_state = use_state(bool::default);
+    // increased by retrying, so the same pages are loaded again
+    let retries_state = use_state(|| 0_u32);
+    // the latest load, so responses for earlier filters are ignored
+    let load_request = use_mut_ref(|| 0_u32);
+    // index of the first recipe of a page the user jumped to
+    let jump_to_item_state = use_state(|| Option::<usize>::None);
+    // latest list state, used to fill the cache when leaving the page
+    let latest_loaded = use_mut_ref(|| (*loaded_state).clone());
+    {
+        // scroll positions are tracked separately by the scroll listener
+        let mut latest_loaded = latest_loaded.borrow_mut();
+        latest_loaded.filters = loaded_state.filters.clone();
+        latest_loaded.recipes = loaded_state.recipes.clone();
+        latest_loaded.total_count = loaded_state.total_count;
+        latest_loaded.next_cursor = loaded_state.next_cursor;
+    }
+
+    // the chosen view is remembered for each user
+    let user_id = login_ctx
+        .login
+        .as_ref()
+        .and_then(|login| login.token.subject())
+        .unwrap_or_default();
+    let view_state = use_state(|| storage::read_recipes_view(&user_id));
+    let modal_html_state: UseStateHandle<Option<Html>> = use_state(Option::default);
 
     use_login_redirect_effect(LoginState::HasLogin, crate::Route::Login);
 
-    let current_page = {
-        let login_ctx = login_ctx.clone();
-        let api = login_ctx.http_api.clone();
-        let filters = (*filters_state).clone();
-        use_async_with_options(
-            async move {
-                let api = api.expect("expected api to exist");
-                match api.get_recipes(&filters).await {
-                    Ok(v) => Ok(v),
-                    Err(err) => {
-                        push_toast(
-                            &toasts_ctx,
-                            api_error_to_toast(
-                                &err,
-                                &format!("loading recipes page {}", &filters.page),
-                            ),
-                        );
-                        logout_on_401(&err, &login_ctx);
-                        Err(err)
-                    }
-                }
-            },
-            UseAsyncOptions::enable_auto(),
-        )
-    };
+    let smart_query = filters.query.as_deref().map(smart_query::parse);
+    // instant results come from the search index rather than the API,
+    // smart queries can only be evaluated by the index so always use it
+    let use_search_index_results =
+        filters.query.is_some() || (is_search_ready && filters.search.is_some());
 
+    // load any pages not yet loaded for the current filters
     {
-        let filters_state = filters_state.clone();
-        let current_page = current_page.clone();
+        let loaded_state = loaded_state.clone();
+        let is_loading_state = is_loading_state.clone();
+        let load_failed_state = load_failed_state.clone();
+        let load_request = load_request.clone();
+        let jump_to_item_state = jump_to_item_state.clone();
+        let login_ctx = login_ctx.clone();
+        let toasts_ctx = toasts_ctx.clone();
+        let navigator = navigator.clone();
         use_effect_with_deps(
-            move |_| {
-                current_page.run();
+            move |(filters, use_search_index_results, _)| {
+                let request = {
+                    let mut load_request = load_request.borrow_mut();
+                    *load_request += 1;
+                    *load_request
+                };
+                let loaded = (*loaded_state).clone();
+                let is_same_query = loaded.filters.is_same_query(filters);
+                if *use_search_index_results
+                    || (is_same_query && loaded.filters.page >= filters.page)
+                {
+                    // any load still running is for other filters
+                    is_loading_state.set(false);
+                    load_failed_state.set(false);
+                    return;
+                }
+                let (first_page, mut recipes) = match is_same_query {
+                    true => (loaded.filters.page + 1, loaded.recipes),
+                    false => (1, Vec::new()),
+                };
+                let filters = filters.clone();
+                let api = login_ctx.http_api.clone().expect("expected api to exist");
+                let is_latest = move || *load_request.borrow() == request;
+                is_loading_state.set(true);
+                load_failed_state.set(false);
+                wasm_bindgen_futures::spawn_local(async move {
+                    let mut total_count = loaded.total_count;
+                    let mut next_cursor = Some(first_page);
+                    let mut last_page = first_page - 1;
+                    while let Some(page) = next_cursor.filter(|page| *page <= filters.page) {
+                        let page_filters = RecipesFilter {
+                            page,
+                            ..filters.clone()
+                        };
+                        let result = api.get_recipes(&page_filters).await;
+                        if !is_latest() {
+                            return;
+                        }
+                        match result {
+                            Ok(recipes_page) => {
+                                recipes.extend(recipes_page.items);
+                                total_count = recipes_page.total_count;
+                                next_cursor = recipes_page.next_cursor;
+                                last_page = page;
+                            }
+                            Err(err) => {
+                                push_toast(
+                                    &toasts_ctx,
+                                    api_error_to_toast(
+                                        &err,
+                                        &format!("loading recipes page {page}"),
+                                    ),
+                                );
+                                logout_on_401(&err, &login_ctx);
+                                load_failed_state.set(true);
+                                // the page jumped to won't be loaded
+                                jump_to_item_state.set(None);
+                                break;
+                            }
+                        }
+                    }
+                    if next_cursor.is_none() {
+                        // the end was reached, so no further pages exist to load
+                        last_page = filters.page;
+                    } else if last_page != 0 && last_page < filters.page {
+                        // a page failed to load, so go back to the last that did
+                        navigator
+                            .replace_with_query(
+                                &Route::Recipes,
+                                &RecipesFilter {
+                                    page: last_page,
+                                    ..filters.clone()
+                                },
+                            )
+                            .expect("failed to update recipes filters");
+                    }
+                    loaded_state.set(RecipesListCache {
+                        filters: RecipesFilter {
+                            page: last_page,
+                            ..filters
+                        },
+                        recipes,
+                        total_count,
+                        next_cursor,
+                        window_scroll_y: 0.0,
+                        content_scroll_top: 0,
+                    });
+                    is_loading_state.set(false);
+                });
             },
-            filters_state,
+            (filters.clone(), use_search_index_results, *retries_state),
         );
     }
 
+    // track the scroll position & restore it when coming back to a cached list
     {
-        let recipes_state = recipes_state.clone();
-        let current_page = current_page.clone();
-        let current_page_ref = current_page.clone();
+        let latest_loaded = latest_loaded.clone();
         use_effect_with_deps(
             move |_| {
-                if !&current_page.loading
-                    && current_page.error.as_ref().is_none()
-                    && current_page.data.as_ref().is_some()
-                {
-                    let mut recipes = (*recipes_state).clone();
-                    let current_page_data = current_page.data.as_ref().unwrap().clone();
-                    recipes.extend(current_page_data);
-                    recipes_state.set(recipes);
+                restore_scroll_position(&latest_loaded.borrow());
+                let scroll_listener = {
+                    let latest_loaded = latest_loaded.clone();
+                    EventListener::new_with_options(
+                        &gloo::utils::document(),
+                        "scroll",
+                        EventListenerOptions::run_in_capture_phase(),
+                        move |_| {
+                            let mut loaded = latest_loaded.borrow_mut();
+                            loaded.window_scroll_y =
+                                gloo::utils::window().scroll_y().unwrap_or_default();
+                            if let Some(element) = drawer_content_element() {
+                                loaded.content_scroll_top = element.scroll_top();
+                            }
+                        },
+                    )
+                };
+                move || {
+                    drop(scroll_listener);
+                    let loaded = latest_loaded.borrow();
+                    if loaded.filters.page > 0 {
+                        storage::set_recipes_list_cache(&loaded);
+                    }
                 }
             },
-            current_page_ref,
+            (),
         );
     }
 
-    let on_load_more_click = {
-        let filters_state = filters_state.clone();
+    let set_filters = {
+        let navigator = navigator.clone();
+        Callback::from(move |filters: RecipesFilter| {
+            // replace, so "back" leaves the page rather than undoing a filter change
+            navigator
+                .replace_with_query(&Route::Recipes, &filters)
+                .expect("failed to update recipes filters");
+        })
+    };
+
+    let loaded = (*loaded_state).clone();
+    let is_loaded = loaded.filters == filters;
+    let can_load_more = is_loaded && !*is_loading_state && loaded.next_cursor.is_some();
+
+    let on_load_more = {
+        let set_filters = set_filters.clone();
+        use_callback(
+            move |_, (filters, can_load_more)| {
+                if *can_load_more {
+                    set_filters.emit(RecipesFilter {
+                        page: filters.page + 1,
+                        ..filters.clone()
+                    });
+                }
+            },
+            (filters.clone(), can_load_more),
+        )
+    };
+    let on_retry_load = {
+        let retries_state = retries_state.clone();
+        Callback::from(move |_: MouseEvent| retries_state.set(*retries_state + 1))
+    };
+    // load the next page as the bottom of the list comes into view
+    let load_more_ref = use_node_ref();
+    use_visible_effect(load_more_ref.clone(), on_load_more.clone());
+
+    let on_jump_to_page = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        let loaded_page = loaded.filters.page;
+        let jump_to_item_state = jump_to_item_state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            let page = input.selected_index() as usize + 1;
+            jump_to_item_state.set(Some((page - 1) * filters.per_page));
+            // earlier pages are already loaded, so only need scrolling to
+            if page > loaded_page {
+                set_filters.emit(RecipesFilter {
+                    page,
+                    ..filters.clone()
+                });
+            }
+        })
+    };
+    let on_jump_scrolled = {
+        let jump_to_item_state = jump_to_item_state.clone();
+        Callback::from(move |_| jump_to_item_state.set(None))
+    };
+
+    let on_view_click = {
+        let user_id = user_id.clone();
+        let view_state = view_state.clone();
+        let jump_to_item_state = jump_to_item_state.clone();
+        Callback::from(move |view: RecipesView| {
+            storage::set_recipes_view(&user_id, view);
+            view_state.set(view);
+            jump_to_item_state.set(None);
+        })
+    };
+
+    let on_search_input = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let search = input.value();
+            set_filters.emit(RecipesFilter {
+                page: 1,
+                search: (!search.trim().is_empty()).then_some(search),
+                ..filters.clone()
+            });
+        })
+    };
+
+    let on_tags_change = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            set_filters.emit(RecipesFilter {
+                page: 1,
+                tags: input
+                    .value()
+                    .split(',')
+                    .map(|tag| tag.trim().to_owned())
+                    .filter(|tag| !tag.is_empty())
+                    .collect(),
+                ..filters.clone()
+            });
+        })
+    };
+
+    let on_query_change = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let query = input.value();
+            set_filters.emit(RecipesFilter {
+                page: 1,
+                query: (!query.trim().is_empty()).then_some(query),
+                ..filters.clone()
+            });
+        })
+    };
+
+    let save_search_modal_closed = {
+        let modal_html_state = modal_html_state.clone();
+        Callback::from(move |saved_search: Option<SavedSearch>| {
+            modal_html_state.set(None);
+            if let Some(saved_search) = saved_search {
+                let mut saved_searches = storage::read_saved_searches(&user_id);
+                push_toast(
+                    &toasts_ctx,
+                    Toast {
+                        message: format!("Saved search \"{}\"", saved_search.name),
+                    },
+                );
+                saved_searches.push(saved_search);
+                storage::set_saved_searches(&user_id, saved_searches);
+            }
+        })
+    };
+
+    let on_save_search_click = {
+        let modal_html_state = modal_html_state.clone();
+        let query = filters.query.clone().unwrap_or_default();
         Callback::from(move |_: MouseEvent| {
-            let mut filters = (*filters_state).clone();
-            filters.page += 1;
-            filters_state.set(filters);
+            modal_html_state.set(Some(html! {
+                <modals::saved_search::SaveSearch
+                    query={query.clone()}
+                    onclose={save_search_modal_closed.clone()}
+                />
+            }));
+        })
+    };
+
+    let on_favourites_change = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            set_filters.emit(RecipesFilter {
+                page: 1,
+                favourites: input.checked(),
+                ..filters.clone()
+            });
+        })
+    };
+
+    let on_max_total_time_change = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            set_filters.emit(RecipesFilter {
+                page: 1,
+                max_total_time: input.value().parse().ok(),
+                ..filters.clone()
+            });
+        })
+    };
+
+    let on_facet_change = {
+        let set_filters = set_filters.clone();
+        let filters = filters.clone();
+        Callback::from(move |(facet, e): (Facet, Event)| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            let value = Some(input.value()).filter(|v| !v.is_empty());
+            let mut filters = RecipesFilter {
+                page: 1,
+                ..filters.clone()
+            };
+            *match facet {
+                Facet::Cuisine => &mut filters.cuisine,
+                Facet::Course => &mut filters.course,
+                Facet::Difficulty => &mut filters.difficulty,
+            } = value;
+            set_filters.emit(filters);
         })
     };
+    // values from the library, keeping the selected one should it no longer be used
+    let facet_choices = |mut choices: Vec<String>, selected: &Option<String>| {
+        if let Some(selected) = selected {
+            if !choices.iter().any(|v| v.eq_ignore_ascii_case(selected)) {
+                choices.push(selected.clone());
+            }
+        }
+        choices
+    };
+    let cuisines = facet_choices(search::cuisines(), &filters.cuisine);
+    let courses = facet_choices(search::courses(), &filters.course);
+
+    let on_sort_change = {
+        let filters = filters.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlSelectElement = e.target_unchecked_into();
+            let sort = RecipesSort::ALL
+                .get(input.selected_index() as usize)
+                .copied()
+                .unwrap_or_default();
+            set_filters.emit(RecipesFilter {
+                page: 1,
+                sort,
+                ..filters.clone()
+            });
+        })
+    };
+
+    let mut search_results = match (&smart_query, is_search_ready && use_search_index_results) {
+        (_, false) | (Some(Err(_)), _) => Vec::new(),
+        (Some(Ok(query)), true) => match filters.search.as_deref() {
+            Some(search) => search::search_matching(search, query, SEARCH_RESULTS_LIMIT),
+            None => search::matching(query),
+        },
+        (None, true) => search::search(
+            filters.search.as_deref().unwrap_or_default(),
+            SEARCH_RESULTS_LIMIT,
+        ),
+    }
+    .into_iter()
+    .filter(|result| {
+        filters
+            .tags
+            .iter()
+            .all(|tag| result.tags.iter().any(|v| v.eq_ignore_ascii_case(tag)))
+    })
+    .filter(|result| !filters.favourites || result.favourite)
+    .filter(|result| match filters.max_total_time {
+        Some(max) => result.total_time.is_some_and(|v| v <= max),
+        None => true,
+    })
+    .filter(|result| {
+        matches_facet(&result.cuisine, &filters.cuisine)
+            && matches_facet(&result.course, &filters.course)
+            && matches_facet(&result.difficulty, &filters.difficulty)
+    })
+    .collect::<Vec<_>>();
+    // stable, so equal recipes stay in order of relevance
+    match filters.sort {
+        RecipesSort::Newest => {
+            search_results.sort_by_key(|result| std::cmp::Reverse(result.created_at))
+        }
+        RecipesSort::Oldest => search_results.sort_by_key(|result| result.created_at),
+        RecipesSort::Title => search_results.sort_by_key(|result| result.title.to_lowercase()),
+        RecipesSort::TitleDesc => {
+            search_results.sort_by_key(|result| std::cmp::Reverse(result.title.to_lowercase()))
+        }
+        RecipesSort::Rating => {
+            search_results.sort_by_key(|result| std::cmp::Reverse(result.rating))
+        }
+        // recipes without times go last
+        RecipesSort::Quickest => {
+            search_results.sort_by_key(|result| (result.total_time.is_none(), result.total_time))
+        }
+        // not available for search results, so left in order of relevance
+        RecipesSort::Cooked | RecipesSort::MostCooked => {}
+    }
+    // the index does not hold the details needed for a table, so a list is shown instead
+    let shown_view = match *view_state {
+        RecipesView::Table if use_search_index_results => RecipesView::List,
+        view => view,
+    };
+    let media_url = &login_ctx.login.as_ref().unwrap().media_url;
+    let grid_item =
+        |id: &str, title: &str, image_id: &Option<String>, favourite: bool, rating: Option<u8>| {
+            GridItemProps {
+                navigate_to: Route::Recipe { id: id.to_owned() },
+                title: title.to_owned().into(),
+                image_src: image_id
+                    .as_ref()
+                    .map(|v| format!("{media_url}/recipe-image/{v}").into()),
+                favourite,
+                rating,
+            }
+        };
+
+    let list_item = |id: &str, title: &str, image_id: &Option<String>| ListItemProps {
+        navigate_to: Route::Recipe { id: id.to_owned() },
+        title: title.to_owned().into(),
+        image_src: image_id
+            .as_ref()
+            .map(|v| format!("{media_url}/recipe-image/{v}").into()),
+        description: None,
+        tags: Vec::new(),
+        cooked: None,
+    };
+    let page_count = loaded.total_count.div_ceil(filters.per_page.max(1));
 
     html! {
         <drawer::Drawer r#for="main-drawer">
             <drawer::DrawerContent header=true>
+                <ModalController modal={(*modal_html_state).clone()}/>
                 <div class="p-4 rounded bg-base-200">
                     <h1 class={classes!("text-3xl", "font-bold", "mb-2")}>{ "Recipes" }</h1>
-                    <thumbnail_link_grid::Grid>
-                        {
-                            for (*recipes_state).clone().iter().map(|recipe| {
-                            let image_src = match &recipe.image_id {
-                                Some(v) => Some(format!("{}/recipe-image/{}", login_ctx.login.as_ref().unwrap().media_url, v)),
-                                None => None,
-                            };
-                            html!{
-                                <thumbnail_link_grid::GridItem
-                                    key={recipe.id.clone()}
-                                    navigate_to={Route::Recipe { id: recipe.id.clone() }}
-                                    title={recipe.title.clone()}
-                                    image_src={image_src}
-                                />
-                            }
-                        })}
-                    </thumbnail_link_grid::Grid>
-                    if current_page.loading {
-                       <LoadingButton classes="btn-block" r#type="button" />
-                    } else if !current_page.loading && current_page.error.is_none() && current_page.data.is_some() {
-                        if current_page.data.as_ref().unwrap().len() == filters_state.per_page {
-                            <button class="btn btn-block" onclick={on_load_more_click}>{"More"}</button>
+                    <div class="flex flex-col md:flex-row gap-2 mb-2">
+                        <input
+                            class="input input-bordered w-full"
+                            oninput={on_search_input}
+                            value={filters.search.clone().unwrap_or_default()}
+                            type="search"
+                            placeholder="Search titles, tags, ingredients and steps..."
+                        />
+                        <input
+                            class="input input-bordered w-full md:w-64"
+                            onchange={on_tags_change}
+                            value={filters.tags.join(", ")}
+                            type="text"
+                            list="recipe-tags"
+                            placeholder="tags, comma separated..."
+                            aria-label="Filter By Tags"
+                        />
+                        <datalist id="recipe-tags">
+                            { for search::tags().iter().map(|tag| html!{<option value={tag.clone()} />}) }
+                        </datalist>
+                        <label class="label cursor-pointer gap-2 flex-none">
+                            <span class="label-text">{"Favourites"}</span>
+                            <input
+                                type="checkbox"
+                                class="toggle toggle-secondary"
+                                checked={filters.favourites}
+                                onchange={on_favourites_change}
+                            />
+                        </label>
+                        <select class="select select-bordered" onchange={on_max_total_time_change} aria-label="Total Time">
+                            <option value="" selected={filters.max_total_time.is_none()}>{"Any Time"}</option>
+                            { for MAX_TOTAL_TIMES.iter().map(|minutes| html!{
+                                <option value={minutes.to_string()} selected={filters.max_total_time == Some(*minutes)}>
+                                    {format!("Up to {}", duration::format(*minutes))}
+                                </option>
+                            })}
+                        </select>
+                        <select class="select select-bordered" onchange={on_sort_change} aria-label="Sort By">
+                            { for RecipesSort::ALL.iter().map(|sort| html!{
+                                <option
+                                    selected={*sort == filters.sort}
+                                    disabled={use_search_index_results && !sort.is_indexed()}
+                                >{sort.label()}</option>
+                            })}
+                        </select>
+                        <div class="btn-group flex-none" role="group" aria-label="View">
+                            { for RecipesView::ALL.iter().map(|view| {
+                                let view = *view;
+                                let is_unavailable = use_search_index_results && view == RecipesView::Table;
+                                html!{
+                                    <button
+                                        class={classes!("btn", (view == shown_view).then_some("btn-active"))}
+                                        onclick={on_view_click.reform(move |_| view)}
+                                        disabled={is_unavailable}
+                                        title={is_unavailable.then_some("Search results can't be shown as a table")}
+                                    >{view.label()}</button>
+                                }
+                            })}
+                        </div>
+                    </div>
+                    <div class="flex flex-col md:flex-row gap-2 mb-2">
+                        <select
+                            class="select select-bordered w-full md:w-auto"
+                            onchange={on_facet_change.reform(|e| (Facet::Cuisine, e))}
+                            aria-label="Cuisine"
+                        >
+                            <option value="" selected={filters.cuisine.is_none()}>{"Any Cuisine"}</option>
+                            { for cuisines.iter().map(|cuisine| html!{
+                                <option
+                                    value={cuisine.clone()}
+                                    selected={filters.cuisine.as_ref().is_some_and(|v| v.eq_ignore_ascii_case(cuisine))}
+                                >{cuisine.clone()}</option>
+                            })}
+                        </select>
+                        <select
+                            class="select select-bordered w-full md:w-auto"
+                            onchange={on_facet_change.reform(|e| (Facet::Course, e))}
+                            aria-label="Course"
+                        >
+                            <option value="" selected={filters.course.is_none()}>{"Any Course"}</option>
+                            { for courses.iter().map(|course| html!{
+                                <option
+                                    value={course.clone()}
+                                    selected={filters.course.as_ref().is_some_and(|v| v.eq_ignore_ascii_case(course))}
+                                >{course.clone()}</option>
+                            })}
+                        </select>
+                        <select
+                            class="select select-bordered w-full md:w-auto"
+                            onchange={on_facet_change.reform(|e| (Facet::Difficulty, e))}
+                            aria-label="Difficulty"
+                        >
+                            <option value="" selected={filters.difficulty.is_none()}>{"Any Difficulty"}</option>
+                            { for DIFFICULTIES.iter().map(|difficulty| html!{
+                                <option
+                                    value={*difficulty}
+                                    selected={filters.difficulty.as_deref() == Some(*difficulty)}
+                                >{difficulty_label(difficulty)}</option>
+                            })}
+                        </select>
+                    </div>
+                    <div class="form-control mb-2">
+                        <div class="flex gap-2">
+                            <input
+                                class={classes!("input", "input-bordered", "w-full", "font-mono", matches!(smart_query, Some(Err(_))).then_some("input-error"))}
+                                onchange={on_query_change}
+                                value={filters.query.clone().unwrap_or_default()}
+                                type="text"
+                                placeholder="Smart query, e.g. tag:dessert AND NOT ingredient:nuts AND steps<=5"
+                                aria-label="Smart Query"
+                            />
+                            <button
+                                class="btn flex-none"
+                                onclick={on_save_search_click}
+                                disabled={!matches!(smart_query, Some(Ok(_)))}
+                            >{"Save Search"}</button>
+                        </div>
+                        if let Some(Err(err)) = &smart_query {
+                            <label class="label">
+                                <span class="label-text-alt text-error">{format!("Invalid query: {err}")}</span>
+                            </label>
+                        }
+                    </div>
+                    if use_search_index_results {
+                        if search::has_failed() {
+                            <div class="flex flex-col items-center gap-2">
+                                <span>{"Failed To Load Search"}</span>
+                                <button class="btn" onclick={Callback::from(|_| search::retry())}>{"Retry"}</button>
+                            </div>
+                        } else if !is_search_ready {
+                            <LoadingButton classes="btn-block" r#type="button" />
+                        } else if matches!(smart_query, Some(Err(_))) {
+                            // the error is shown with the query
+                        } else if search_results.is_empty() {
+                            <div class="text-center">{"No Matching Recipes"}</div>
                         } else {
-                            <div class="text-center">{"Reached Bottom"}</div>
+                            if shown_view == RecipesView::Grid {
+                                <WindowedGrid items={
+                                    search_results.iter().map(|result| {
+                                        grid_item(&result.id, &result.title, &result.image_id, result.favourite, result.rating)
+                                    }).collect::<Vec<_>>()
+                                } />
+                            } else {
+                                <List items={
+                                    search_results.iter().map(|result| ListItemProps {
+                                        tags: result.tags.iter().cloned().map(AttrValue::from).collect(),
+                                        ..list_item(&result.id, &result.title, &result.image_id)
+                                    }).collect::<Vec<_>>()
+                                } />
+                            }
+                        }
+                    } else {
+                        if is_loaded && loaded.total_count != 0 {
+                            <div class="flex items-center justify-between gap-2 mb-2">
+                                <span>{format!("Showing {} of {}", loaded.recipes.len(), loaded.total_count)}</span>
+                                // only the grid can scroll to a recipe
+                                if *view_state == RecipesView::Grid {
+                                    <select class="select select-bordered select-sm" onchange={on_jump_to_page} aria-label="Jump To Page">
+                                        { for (1..=page_count).map(|page| html!{
+                                            <option selected={page == filters.page}>{format!("Page {page}")}</option>
+                                        })}
+                                    </select>
+                                }
+                            </div>
                         }
+                        {match *view_state {
+                            RecipesView::Grid => html!{
+                                <WindowedGrid
+                                    items={
+                                        loaded.recipes.iter().map(|recipe| {
+                                            grid_item(&recipe.id, &recipe.title, &recipe.image_id, recipe.favourite, recipe.rating)
+                                        }).collect::<Vec<_>>()
+                                    }
+                                    scroll_to_item={*jump_to_item_state}
+                                    on_scrolled={on_jump_scrolled}
+                                />
+                            },
+                            RecipesView::List => html!{
+                                <List items={
+                                    loaded.recipes.iter().map(|recipe| ListItemProps {
+                                        description: recipe.short_description.clone().map(AttrValue::from),
+                                        cooked: recipe.cooked_summary().map(AttrValue::from),
+                                        tags: recipe.tags.iter().cloned().map(AttrValue::from).collect(),
+                                        ..list_item(&recipe.id, &recipe.title, &recipe.image_id)
+                                    }).collect::<Vec<_>>()
+                                } />
+                            },
+                            RecipesView::Table => html!{
+                                <Table recipes={loaded.recipes.clone()} />
+                            },
+                        }}
+                        <div ref={load_more_ref}>
+                            if *load_failed_state && !*is_loading_state {
+                                <button class="btn btn-block" onclick={on_retry_load}>{"Retry"}</button>
+                            } else if *is_loading_state || !is_loaded {
+                               <LoadingButton classes="btn-block" r#type="button" />
+                            } else if loaded.next_cursor.is_some() {
+                                <button class="btn btn-block" onclick={on_load_more.reform(|_: MouseEvent| ())}>{"More"}</button>
+                            } else {
+                                <div class="text-center">{"Reached Bottom"}</div>
+                            }
+                        </div>
                     }
                 </div>
             </drawer::DrawerContent>
@@ -125,6 +781,9 @@ pub fn recipes() -> Html {
                 <drawer::DrawerLink to={Route::Home}>{"Home"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::NewRecipe}>{"New Recipe"}</drawer::DrawerLink>
                 <drawer::DrawerLink to={Route::Recipes} active=true>{"Recipes"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Books}>{"Books"}</drawer::DrawerLink>
+                <drawer::DrawerLink to={Route::Pantry}>{"Pantry"}</drawer::DrawerLink>
+                <SavedSearches />
             </drawer::DrawerDraw>
         </drawer::Drawer>
     }